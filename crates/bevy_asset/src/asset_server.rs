@@ -1,17 +1,28 @@
 use crate::{
+    get_meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    processor::{hash_bytes, process_asset},
+    saver::ErasedAssetSaver,
+    Asset, AssetFolder, AssetFolderEvent, AssetIo, AssetIoError, AssetLifecycle,
+    AssetLifecycleChannel, AssetLifecycleEvent, AssetLoader, AssetLoaderSettings, AssetMeta,
+    AssetProcessor, AssetSaver, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadProgress, LoadState, ProcessedAssetCache, RefChange, RefChangeChannel, SourceInfo,
+    SourceMeta,
 };
 use anyhow::Result;
+use bevy_app::EventWriter;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_log::warn;
+use bevy_reflect::TypeUuid;
 use bevy_tasks::TaskPool;
-use bevy_utils::{HashMap, Uuid};
+use bevy_utils::{HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, BTreeMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an AssetServer
@@ -23,8 +34,20 @@ pub enum AssetServerError {
     MissingAssetLoader { extensions: Vec<String> },
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
+    #[error("the loader did not produce an asset for {path:?}")]
+    MissingLabeledAsset { path: AssetPath<'static> },
+    #[error("the asset {0:?} is directly loaded by its own loader or one it directly loads")]
+    CyclicLoad(PathBuf),
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
+    #[error("encountered an error while reading asset settings: {0}")]
+    AssetSettingsError(anyhow::Error),
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+    #[error("no `AssetSaver` found for the asset type{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver { extensions: Vec<String> },
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
 }
@@ -55,8 +78,35 @@ pub struct AssetServerInternal {
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    processors: RwLock<Vec<Arc<Box<dyn AssetProcessor>>>>,
+    extension_to_processor_index: RwLock<HashMap<String, usize>>,
+    processed_asset_cache: RwLock<Option<Arc<ProcessedAssetCache>>>,
+    savers: RwLock<Vec<Arc<dyn ErasedAssetSaver>>>,
+    // hashes of the bytes last written to each path by `AssetServer::save`
+    saved_asset_hashes: Mutex<HashMap<PathBuf, u64>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
-    task_pool: TaskPool,
+    // sources that were requested and did not start loading yet
+    queued_loads: Mutex<HashSet<SourcePathId>>,
+    // the paths of the files in each live folder
+    live_folders: RwLock<HashMap<PathBuf, Vec<PathBuf>>>,
+    // the files that were created or removed in live folders since the last frame
+    live_folder_events: Mutex<Vec<AssetFolderEvent>>,
+    // the source paths of the assets that read or directly loaded each path while loading
+    loader_dependants: RwLock<HashMap<PathBuf, HashSet<PathBuf>>>,
+    pending_recursive_loads: Mutex<HashSet<AssetPathId>>,
+    pub(crate) task_pool: TaskPool,
+}
+
+/// The bytes of an asset file along with everything needed to load them
+pub(crate) struct AssetSource {
+    pub(crate) loader: Arc<Box<dyn AssetLoader>>,
+    pub(crate) bytes: Vec<u8>,
+    // the size of the source file, which differs from `bytes` if the file was processed
+    pub(crate) bytes_read: u64,
+    pub(crate) settings: Option<Arc<dyn AssetLoaderSettings>>,
+    // the other source files read by the `AssetProcessor`, which are watched like the files read
+    // by the loader
+    pub(crate) process_dependencies: Vec<PathBuf>,
 }
 
 /// Loads assets from the filesystem on background threads
@@ -82,9 +132,19 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                extension_to_processor_index: Default::default(),
+                processed_asset_cache: Default::default(),
+                savers: Default::default(),
+                saved_asset_hashes: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                queued_loads: Default::default(),
+                live_folders: Default::default(),
+                live_folder_events: Default::default(),
+                loader_dependants: Default::default(),
+                pending_recursive_loads: Default::default(),
                 asset_lifecycles: Default::default(),
                 task_pool,
                 asset_io,
@@ -115,6 +175,39 @@ impl AssetServer {
         loaders.push(Arc::new(Box::new(loader)));
     }
 
+    /// Registers a processor that transforms source files with one of its extensions before they
+    /// are loaded. The processed bytes are loaded by the `AssetLoader` registered for
+    /// [`AssetProcessor::processed_extension`].
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let mut processors = self.server.processors.write();
+        let processor_index = processors.len();
+        for extension in processor.extensions().iter() {
+            self.server
+                .extension_to_processor_index
+                .write()
+                .insert(extension.to_string(), processor_index);
+        }
+        processors.push(Arc::new(Box::new(processor)));
+    }
+
+    /// Registers a saver that writes assets of type [`AssetSaver::Asset`] to paths with one of its
+    /// extensions.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        self.server.savers.write().push(Arc::new(saver));
+    }
+
+    /// Sets the cache that processed assets are stored in. Without a cache, assets with a
+    /// registered [`AssetProcessor`] are processed every time they are loaded.
+    pub fn set_processed_asset_cache(&self, cache: Option<ProcessedAssetCache>) {
+        *self.server.processed_asset_cache.write() = cache.map(Arc::new);
+    }
+
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.server.asset_io.watch_for_changes()?;
         Ok(())
@@ -176,6 +269,52 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_processor<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Option<Arc<Box<dyn AssetProcessor>>> {
+        let s = path.as_ref().file_name()?.to_str()?.to_lowercase();
+        let map = self.server.extension_to_processor_index.read();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(&index) = map.get(ext) {
+                return Some(self.server.processors.read()[index].clone());
+            }
+        }
+        None
+    }
+
+    fn get_path_asset_saver<P: AsRef<Path>>(
+        &self,
+        type_uuid: Uuid,
+        path: P,
+    ) -> Result<Arc<dyn ErasedAssetSaver>, AssetServerError> {
+        let s = path
+            .as_ref()
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|s| s.to_lowercase())
+            .unwrap_or_default();
+
+        let savers = self.server.savers.read();
+        let mut exts = Vec::new();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            let saver = savers.iter().rev().find(|saver| {
+                saver.asset_type_uuid() == type_uuid && saver.extensions().contains(&ext)
+            });
+            if let Some(saver) = saver {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
             .handle_to_path
@@ -216,6 +355,92 @@ impl AssetServer {
         load_state
     }
 
+    /// Returns the load progress of the source file of an asset.
+    pub fn get_load_progress<H: Into<HandleId>>(&self, handle: H) -> LoadProgress {
+        self.get_group_load_progress(std::iter::once(handle.into()))
+    }
+
+    /// Returns the combined load progress of the source files of a group of assets, like
+    /// [`AssetServer::get_group_load_state`]. Assets that were not loaded from a path are ignored.
+    pub fn get_group_load_progress(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> LoadProgress {
+        let mut source_path_ids = HashSet::default();
+        for handle_id in handles {
+            if let HandleId::AssetPathId(id) = handle_id {
+                source_path_ids.insert(id.source_path_id());
+            }
+        }
+
+        // the queued loads are copied, so the two locks are never held at the same time
+        let queued_loads = self.server.queued_loads.lock().clone();
+        let asset_sources = self.server.asset_sources.read();
+        let mut progress = LoadProgress::default();
+        for source_path_id in source_path_ids {
+            add_load_progress(
+                &mut progress,
+                asset_sources.get(&source_path_id),
+                queued_loads.contains(&source_path_id),
+            );
+        }
+        progress
+    }
+
+    /// Returns the combined load progress of all asset source files requested from this server.
+    pub fn get_total_load_progress(&self) -> LoadProgress {
+        let queued_loads = self.server.queued_loads.lock().clone();
+        let asset_sources = self.server.asset_sources.read();
+        let mut progress = LoadProgress::default();
+        for (source_path_id, source_info) in asset_sources.iter() {
+            add_load_progress(
+                &mut progress,
+                Some(source_info),
+                queued_loads.contains(source_path_id),
+            );
+        }
+        // queued sources that never started loading before have no `SourceInfo` yet
+        progress.queued += queued_loads
+            .iter()
+            .filter(|source_path_id| !asset_sources.contains_key(source_path_id))
+            .count();
+        progress
+    }
+
+    /// Returns the load state of an asset, taking the transitive dependencies registered with
+    /// [`LoadedAsset::with_dependency`](crate::LoadedAsset::with_dependency) into account.
+    ///
+    /// This is only [`LoadState::Loaded`] once the asset and everything it depends on are loaded.
+    /// A failed dependency makes the whole asset [`LoadState::Failed`].
+    pub fn get_recursive_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        match handle.into() {
+            HandleId::AssetPathId(id) => {
+                let asset_sources = self.server.asset_sources.read();
+                let mut visited = HashSet::default();
+                get_recursive_load_state(&asset_sources, id.source_path_id(), true, &mut visited)
+            }
+            HandleId::Id(_, _) => LoadState::NotLoaded,
+        }
+    }
+
+    pub fn get_group_recursive_load_state(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> LoadState {
+        let mut load_state = LoadState::Loaded;
+        for handle_id in handles {
+            match self.get_recursive_load_state(handle_id) {
+                LoadState::Loaded => continue,
+                LoadState::Loading => {
+                    load_state = LoadState::Loading;
+                }
+                state => return state,
+            }
+        }
+
+        load_state
+    }
+
     /// Loads an Asset at the provided relative path.
     ///
     /// The absolute Path to the asset is "ROOT/ASSET_FOLDER_NAME/path".
@@ -228,6 +453,9 @@ impl AssetServer {
     /// The name of the asset folder is set inside the
     /// [`AssetServerSettings`](crate::AssetServerSettings) resource. The default name is
     /// `"assets"`.
+    ///
+    /// Paths prefixed with a source name, like `"mods://textures/grass.png"`, are loaded from
+    /// that source if the server uses a [`NamespacedAssetIo`](crate::NamespacedAssetIo).
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
         self.load_untyped(path).typed()
@@ -239,6 +467,10 @@ impl AssetServer {
         force: bool,
     ) -> Result<AssetPathId, AssetServerError> {
         let asset_path_id: AssetPathId = asset_path.get_id();
+        self.server
+            .queued_loads
+            .lock()
+            .remove(&asset_path_id.source_path_id());
 
         // load metadata and update source info. this is done in a scope to ensure we release the
         // locks before loading
@@ -253,6 +485,8 @@ impl AssetServer {
                     meta: None,
                     path: asset_path.path().to_owned(),
                     version: 0,
+                    bytes_read: 0,
+                    total_bytes: None,
                 }),
             };
 
@@ -270,9 +504,30 @@ impl AssetServer {
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
+            source_info.bytes_read = 0;
+            source_info.total_bytes = None;
             source_info.version
         };
 
+        // only loads that actually start send an `AssetLoadedWithDependencies` event once done
+        self.server
+            .pending_recursive_loads
+            .lock()
+            .insert(asset_path_id);
+
+        // track the bytes read for the load progress, unless a newer version is being loaded
+        let set_bytes_read = |bytes_read: u64, total_bytes: Option<u64>| {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .get_mut(&asset_path_id.source_path_id())
+                .expect("`AssetSource` should exist at this point.");
+            if source_info.version == version {
+                source_info.bytes_read = bytes_read;
+                source_info.total_bytes = total_bytes;
+            }
+        };
+        set_bytes_read(0, self.server.asset_io.file_size(asset_path.path()));
+
         let set_asset_failed = || {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
@@ -281,35 +536,26 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
-            Ok(loader) => loader,
+        let source = match self.read_asset_source(asset_path.path()).await {
+            Ok(source) => source,
             Err(err) => {
                 set_asset_failed();
                 return Err(err);
             }
         };
-
-        // load the asset bytes
-        let bytes = match self.server.asset_io.load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
-            }
-        };
+        set_bytes_read(source.bytes_read, Some(source.bytes_read));
+        let has_settings = source.loader.has_settings();
 
         // load the asset source using the corresponding AssetLoader
-        let mut load_context = LoadContext::new(
-            asset_path.path(),
-            &self.server.asset_ref_counter.channel,
-            &*self.server.asset_io,
-            version,
-            &self.server.task_pool,
-        );
+        let mut load_context = LoadContext::new(asset_path.path(), self, version);
+        load_context.settings = source.settings;
+        for dependency in source.process_dependencies.iter() {
+            load_context.add_loader_dependency(dependency);
+        }
 
-        if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
+        if let Err(err) = source
+            .loader
+            .load(&source.bytes, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
@@ -317,48 +563,135 @@ impl AssetServer {
             return Err(err);
         }
 
-        // if version has changed since we loaded and grabbed a lock, return. theres is a newer
-        // version being loaded
-        let mut asset_sources = self.server.asset_sources.write();
-        let source_info = asset_sources
-            .get_mut(&asset_path_id.source_path_id())
-            .expect("`AssetSource` should exist at this point.");
-        if version != source_info.version {
-            return Ok(asset_path_id);
-        }
-
-        // if all assets have been committed already (aka there were 0), set state to "Loaded"
-        if source_info.is_loaded() {
-            source_info.load_state = LoadState::Loaded;
-        }
-
-        // reset relevant SourceInfo fields
-        source_info.committed_assets.clear();
-        // TODO: queue free old assets
-        source_info.asset_types.clear();
+        // the dependencies are loaded after releasing the lock, as loading takes other locks
+        let (loader_dependencies, dependencies) = {
+            // if version has changed since we loaded and grabbed a lock, return. theres is a newer
+            // version being loaded
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .get_mut(&asset_path_id.source_path_id())
+                .expect("`AssetSource` should exist at this point.");
+            if version != source_info.version {
+                return Ok(asset_path_id);
+            }
 
-        source_info.meta = Some(SourceMeta {
-            assets: load_context.get_asset_metas(),
-        });
+            // if all assets have been committed already (aka there were 0), set state to "Loaded"
+            if source_info.is_loaded() {
+                source_info.load_state = LoadState::Loaded;
+            }
 
-        // load asset dependencies and prepare asset type hashmap
-        for (label, loaded_asset) in load_context.labeled_assets.iter_mut() {
-            let label_id = LabelId::from(label.as_ref().map(|label| label.as_str()));
-            let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
-            source_info.asset_types.insert(label_id, type_uuid);
-            for dependency in loaded_asset.dependencies.iter() {
-                self.load_untracked(dependency.clone(), false);
+            // reset relevant SourceInfo fields
+            source_info.committed_assets.clear();
+            // TODO: queue free old assets
+            source_info.asset_types.clear();
+
+            let loader_dependencies = load_context.loader_dependencies();
+            source_info.meta = Some(SourceMeta {
+                assets: load_context.get_asset_metas(),
+                loader_dependencies: loader_dependencies.clone(),
+            });
+
+            // collect asset dependencies and prepare asset type hashmap
+            let mut dependencies = Vec::new();
+            for (label, loaded_asset) in load_context.labeled_assets.iter_mut() {
+                let label_id = LabelId::from(label.as_ref().map(|label| label.as_str()));
+                let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
+                source_info.asset_types.insert(label_id, type_uuid);
+                dependencies.extend(loaded_asset.dependencies.iter().cloned());
             }
+            (loader_dependencies, dependencies)
+        };
+
+        for dependency in dependencies {
+            self.load_untracked(dependency, false);
         }
 
         self.server
             .asset_io
             .watch_path_for_changes(asset_path.path())
             .unwrap();
+        // the `.meta` file is watched even if it does not exist, so creating one reloads the asset
+        if has_settings {
+            let meta_path = get_meta_path(asset_path.path());
+            if let Err(err) = self.server.asset_io.watch_path_for_changes(&meta_path) {
+                warn!("Failed to watch {:?} for changes: {}", meta_path, err);
+            }
+        }
+        {
+            let mut loader_dependants = self.server.loader_dependants.write();
+            for dependency in loader_dependencies.iter() {
+                loader_dependants
+                    .entry(dependency.clone())
+                    .or_default()
+                    .insert(asset_path.path().to_owned());
+                self.server
+                    .asset_io
+                    .watch_path_for_changes(dependency)
+                    .unwrap();
+            }
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Reads the bytes of the asset at `path`, processing them if an [`AssetProcessor`] is
+    /// registered for the path, along with the loader and the settings from its `.meta` file.
+    pub(crate) async fn read_asset_source(
+        &self,
+        path: &Path,
+    ) -> Result<AssetSource, AssetServerError> {
+        // get the according asset loader, which loads the output of the asset processor if one
+        // is registered for this path
+        let asset_processor = self.get_path_asset_processor(path);
+        let loader = match asset_processor.as_ref() {
+            Some(processor) => self.get_asset_loader(processor.processed_extension())?,
+            None => self.get_path_asset_loader(path)?,
+        };
+
+        // load the asset bytes
+        let mut bytes = self.server.asset_io.load_path(path).await?;
+        let bytes_read = bytes.len() as u64;
+
+        // process the asset bytes, reusing a cached artifact if the inputs did not change
+        let mut process_dependencies = Vec::new();
+        if let Some(asset_processor) = asset_processor {
+            let cache = self.server.processed_asset_cache.read().clone();
+            let (processed, dependencies) = process_asset(
+                &**asset_processor,
+                cache.as_deref(),
+                path,
+                &bytes,
+                &*self.server.asset_io,
+            )
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+            bytes = processed;
+            process_dependencies = dependencies;
+        }
+
+        // load the asset settings from the `.meta` file next to the asset, if there is one
+        let settings = if loader.has_settings() {
+            match self.server.asset_io.load_path(&get_meta_path(path)).await {
+                Ok(meta) => loader
+                    .deserialize_settings(&meta)
+                    .map_err(AssetServerError::AssetSettingsError)?
+                    .map(Arc::from),
+                Err(AssetIoError::NotFound(_)) => None,
+                Err(err) => return Err(err.into()),
+            }
+        } else {
+            None
+        };
+
+        Ok(AssetSource {
+            loader,
+            bytes_read,
+            bytes,
+            settings,
+            process_dependencies,
+        })
+    }
+
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
         let handle_id = self.load_untracked(path.into(), false);
@@ -366,6 +699,11 @@ impl AssetServer {
     }
 
     pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+        // marked as queued before the load is spawned, as the load removes it again
+        self.server
+            .queued_loads
+            .lock()
+            .insert(asset_path.get_id().source_path_id());
         let server = self.clone();
         let owned_path = asset_path.to_owned();
         self.server
@@ -387,6 +725,130 @@ impl AssetServer {
         asset_path.into()
     }
 
+    /// Writes the asset of `handle` to `path` using the [`AssetSaver`] registered for the asset type
+    /// and the extension of `path`.
+    ///
+    /// The asset is serialized the next time the `Assets<T>` storage is updated and is then written
+    /// on the IO task pool. Errors that occur at that point are logged. Saving an asset does not
+    /// trigger a hot reload of the written file.
+    pub fn save<T: Asset, P: AsRef<Path>>(
+        &self,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Result<(), AssetServerError> {
+        let path = path.as_ref();
+        self.get_path_asset_saver(T::TYPE_UUID, path)?;
+
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        let channel = asset_lifecycles
+            .get(&T::TYPE_UUID)
+            .and_then(|asset_lifecycle| asset_lifecycle.downcast_ref::<AssetLifecycleChannel<T>>())
+            .ok_or(AssetServerError::IncorrectHandleType)?;
+        channel
+            .sender
+            .send(AssetLifecycleEvent::Save(handle.id, path.to_owned()))
+            .unwrap();
+        Ok(())
+    }
+
+    fn write_saved_asset(&self, path: PathBuf, bytes: Vec<u8>) {
+        let server = self.clone();
+        self.server
+            .task_pool
+            .spawn(async move {
+                // remember what was written before writing it, so the change it causes can be
+                // recognized as soon as it is reported
+                server
+                    .server
+                    .saved_asset_hashes
+                    .lock()
+                    .insert(path.clone(), hash_bytes(&bytes));
+                if let Err(err) = server.server.asset_io.write_path(&path, &bytes).await {
+                    server.server.saved_asset_hashes.lock().remove(&path);
+                    warn!("Failed to save asset to {:?}: {}", path, err);
+                }
+            })
+            .detach();
+    }
+
+    /// Reloads the asset at a changed path, unless the change was caused by saving that asset.
+    pub(crate) fn reload_changed_path(&self, path: PathBuf) {
+        if !self.server.saved_asset_hashes.lock().contains_key(&path) {
+            self.reload_with_dependants(path);
+            return;
+        }
+
+        let server = self.clone();
+        self.server
+            .task_pool
+            .spawn(async move {
+                let hash = server
+                    .server
+                    .asset_io
+                    .load_path(&path)
+                    .await
+                    .ok()
+                    .map(|bytes| hash_bytes(&bytes));
+                // the saved contents are forgotten once the change they caused was reported
+                let saved_hash = server.server.saved_asset_hashes.lock().remove(&path);
+                if hash.is_some() && hash == saved_hash {
+                    return;
+                }
+                // the file was changed by someone else since it was saved
+                server.reload_with_dependants(path);
+            })
+            .detach();
+    }
+
+    /// Reloads the asset at `path` and every asset that read or directly loaded it while loading,
+    /// see [`LoadContext::read_asset_bytes`] and [`LoadContext::load_direct`].
+    pub(crate) fn reload_with_dependants(&self, path: PathBuf) {
+        let mut visited = HashSet::default();
+        let mut queue = vec![path];
+        while let Some(path) = queue.pop() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            queue.extend(self.get_loader_dependants(&path));
+
+            // only reload files that were loaded before, not new files or live folders. each lock
+            // is released before taking the next one, as live folders lock them in reverse order
+            let is_loaded = self
+                .server
+                .asset_sources
+                .read()
+                .contains_key(&SourcePathId::from(path.as_path()));
+            let is_live_folder = self.server.live_folders.read().contains_key(&path);
+            if is_loaded && !is_live_folder {
+                self.load_untracked(path.into(), true);
+            }
+        }
+    }
+
+    fn get_loader_dependants(&self, path: &Path) -> Vec<PathBuf> {
+        let asset_sources = self.server.asset_sources.read();
+        let mut loader_dependants = self.server.loader_dependants.write();
+        let dependants = match loader_dependants.get_mut(path) {
+            Some(dependants) => dependants,
+            None => return Vec::new(),
+        };
+
+        // forget dependants that no longer depend on the path since they were last loaded
+        dependants.retain(|dependant| {
+            let meta = asset_sources
+                .get(&SourcePathId::from(dependant.as_path()))
+                .and_then(|source_info| source_info.meta.as_ref());
+            match meta {
+                Some(meta) => meta
+                    .loader_dependencies
+                    .iter()
+                    .any(|dependency| dependency == path),
+                None => true,
+            }
+        });
+        dependants.iter().cloned().collect()
+    }
+
     #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
     pub fn load_folder<P: AsRef<Path>>(
         &self,
@@ -416,6 +878,160 @@ impl AssetServer {
         Ok(handles)
     }
 
+    /// Loads the assets in a folder like [`AssetServer::load_folder`], but returns a handle to an
+    /// [`AssetFolder`] that is kept up to date as files are created, renamed or deleted in the
+    /// folder. This requires the `AssetIo` to watch for changes, see
+    /// [`AssetServer::watch_for_changes`].
+    pub fn load_live_folder<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Handle<AssetFolder>, AssetServerError> {
+        let path = path.as_ref();
+        if !self.server.asset_io.is_directory(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        let asset_path = AssetPath::new_ref(path, None);
+        let handle_id: HandleId = asset_path.get_id().into();
+        self.server
+            .handle_to_path
+            .write()
+            .entry(handle_id)
+            .or_insert_with(|| asset_path.to_owned());
+        let handle = self.get_handle(handle_id);
+
+        self.server
+            .live_folders
+            .write()
+            .entry(path.to_owned())
+            .or_default();
+        self.server.asset_io.watch_path_for_changes(path)?;
+        self.update_live_folder(path, true);
+        Ok(handle)
+    }
+
+    /// Updates the live folders that contain any of the changed paths.
+    pub(crate) fn update_live_folders(&self, changed_paths: &[PathBuf]) {
+        let folders = self
+            .server
+            .live_folders
+            .read()
+            .keys()
+            .filter(|folder| changed_paths.iter().any(|path| path.starts_with(folder)))
+            .cloned()
+            .collect::<Vec<_>>();
+        for folder in folders {
+            self.update_live_folder(&folder, false);
+        }
+    }
+
+    /// Scans a live folder for files and updates its [`AssetFolder`] if they changed, or
+    /// unconditionally if `force` is true.
+    fn update_live_folder(&self, path: &Path, force: bool) {
+        let source_path_id = SourcePathId::from(path);
+
+        // stop updating folders whose `AssetFolder` was freed
+        if !force {
+            let is_unloaded = matches!(
+                self.server.asset_sources.read().get(&source_path_id),
+                Some(source_info) if source_info.load_state == LoadState::Unloaded
+            );
+            if is_unloaded {
+                self.server.live_folders.write().remove(path);
+                return;
+            }
+        }
+
+        let handles = if self.server.asset_io.is_directory(path) {
+            self.load_folder(path).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let handles = handles
+            .into_iter()
+            .filter_map(|handle| {
+                let handle_path = self.get_handle_path(&handle)?.path().to_owned();
+                Some((handle_path, handle))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let paths = handles.keys().cloned().collect::<Vec<_>>();
+
+        let version = {
+            let mut live_folders = self.server.live_folders.write();
+            let folder_paths = match live_folders.get_mut(path) {
+                Some(folder_paths) => folder_paths,
+                None => return,
+            };
+            if !force && *folder_paths == paths {
+                return;
+            }
+
+            let folder = Handle::<AssetFolder>::weak(AssetPath::new_ref(path, None).into());
+            let mut live_folder_events = self.server.live_folder_events.lock();
+            for removed in folder_paths.iter().filter(|old| !paths.contains(old)) {
+                live_folder_events.push(AssetFolderEvent::Removed {
+                    folder: folder.clone(),
+                    path: removed.clone(),
+                });
+            }
+            for created in paths.iter().filter(|new| !folder_paths.contains(new)) {
+                live_folder_events.push(AssetFolderEvent::Created {
+                    folder: folder.clone(),
+                    path: created.clone(),
+                });
+            }
+            drop(live_folder_events);
+
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .entry(source_path_id)
+                .or_insert_with(|| SourceInfo {
+                    asset_types: Default::default(),
+                    committed_assets: Default::default(),
+                    load_state: LoadState::NotLoaded,
+                    meta: None,
+                    path: path.to_owned(),
+                    version: 0,
+                    bytes_read: 0,
+                    total_bytes: None,
+                });
+            if source_info.load_state != LoadState::Loaded {
+                source_info.load_state = LoadState::Loading;
+            }
+            source_info.version += 1;
+            source_info.committed_assets.clear();
+            source_info
+                .asset_types
+                .insert(LabelId::from(None), AssetFolder::TYPE_UUID);
+            source_info.meta = Some(SourceMeta {
+                assets: vec![AssetMeta {
+                    label: None,
+                    dependencies: paths
+                        .iter()
+                        .map(|path| AssetPath::new(path.clone(), None))
+                        .collect(),
+                    type_uuid: AssetFolder::TYPE_UUID,
+                    settings: None,
+                }],
+                loader_dependencies: Vec::new(),
+            });
+            *folder_paths = paths;
+            source_info.version
+        };
+
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        let asset_lifecycle = asset_lifecycles
+            .get(&AssetFolder::TYPE_UUID)
+            .expect("`AssetFolder` should be registered as an asset type to load live folders.");
+        asset_lifecycle.create_asset(
+            AssetPath::new_ref(path, None).into(),
+            Box::new(AssetFolder::new(handles)),
+            version,
+        );
+    }
+
     pub fn free_unused_assets(&self) {
         let mut potential_frees = self.server.asset_ref_counter.mark_unused_assets.lock();
 
@@ -501,6 +1117,7 @@ impl AssetServer {
         let channel = asset_lifecycle
             .downcast_ref::<AssetLifecycleChannel<T>>()
             .unwrap();
+        let mut freed = Vec::new();
 
         loop {
             match channel.receiver.try_recv() {
@@ -522,15 +1139,37 @@ impl AssetServer {
                     assets.set_untracked(result.id, *result.asset);
                 }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
-                    if let HandleId::AssetPathId(id) = handle_id {
-                        let asset_sources = asset_sources_guard
-                            .get_or_insert_with(|| self.server.asset_sources.write());
-                        if let Some(source_info) = asset_sources.get_mut(&id.source_path_id()) {
-                            source_info.committed_assets.remove(&id.label_id());
-                            source_info.load_state = LoadState::Unloaded;
+                    // assets loaded from a path stay loaded within the budget of their type, so
+                    // loading them again is free
+                    let keep = matches!(handle_id, HandleId::AssetPathId(_))
+                        && assets.budget().is_some()
+                        && assets.keep_unused(handle_id);
+                    if !keep {
+                        freed.push(handle_id);
+                    }
+                }
+                Ok(AssetLifecycleEvent::Save(handle_id, path)) => {
+                    let asset = match assets.get(handle_id) {
+                        Some(asset) => asset,
+                        None => {
+                            warn!(
+                                "Failed to save asset to {:?}: the asset does not exist",
+                                path
+                            );
+                            continue;
                         }
+                    };
+                    let bytes = self
+                        .get_path_asset_saver(T::TYPE_UUID, &path)
+                        .and_then(|saver| {
+                            saver
+                                .save(asset, &path)
+                                .map_err(AssetServerError::AssetSaverError)
+                        });
+                    match bytes {
+                        Ok(bytes) => self.write_saved_asset(path, bytes),
+                        Err(err) => warn!("Failed to save asset to {:?}: {}", path, err),
                     }
-                    assets.remove(handle_id);
                 }
                 Err(TryRecvError::Empty) => {
                     break;
@@ -538,22 +1177,170 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected."),
             }
         }
+
+        // free the least recently used assets that exceed the budget
+        {
+            let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+            let is_used = |id| matches!(ref_counts.get(&id), Some(count) if *count > 0);
+            if assets.needs_eviction(is_used) {
+                freed.extend(assets.evict_unused(is_used));
+            }
+        }
+
+        for handle_id in freed {
+            if let HandleId::AssetPathId(id) = handle_id {
+                let asset_sources =
+                    asset_sources_guard.get_or_insert_with(|| self.server.asset_sources.write());
+                if let Some(source_info) = asset_sources.get_mut(&id.source_path_id()) {
+                    source_info.committed_assets.remove(&id.label_id());
+                    source_info.load_state = LoadState::Unloaded;
+                }
+            }
+            assets.remove(handle_id);
+        }
     }
 }
 
-fn free_unused_assets_system_impl(asset_server: &AssetServer) {
-    asset_server.free_unused_assets();
-    asset_server.mark_unused_assets();
+fn get_recursive_load_state(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    source_path_id: SourcePathId,
+    is_root: bool,
+    visited: &mut HashSet<SourcePathId>,
+) -> LoadState {
+    // sources that were already visited are accounted for by the first visit, which also breaks
+    // dependency cycles
+    if !visited.insert(source_path_id) {
+        return LoadState::Loaded;
+    }
+
+    let source_info = match asset_sources.get(&source_path_id) {
+        Some(source_info) => source_info,
+        // dependencies are queued for loading as soon as the asset that needs them is loaded
+        None if !is_root => return LoadState::Loading,
+        None => return LoadState::NotLoaded,
+    };
+    match source_info.load_state {
+        LoadState::Loaded => {}
+        LoadState::NotLoaded if !is_root => return LoadState::Loading,
+        load_state => return load_state,
+    }
+
+    let mut load_state = LoadState::Loaded;
+    let dependencies = source_info
+        .meta
+        .iter()
+        .flat_map(|meta| meta.assets.iter())
+        .flat_map(|asset_meta| asset_meta.dependencies.iter());
+    for dependency in dependencies {
+        let dependency_id = SourcePathId::from(dependency.path());
+        match get_recursive_load_state(asset_sources, dependency_id, false, visited) {
+            LoadState::Loaded => continue,
+            LoadState::Loading => {
+                load_state = LoadState::Loading;
+            }
+            dependency_state => return dependency_state,
+        }
+    }
+
+    load_state
 }
 
-pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
-    free_unused_assets_system_impl(&asset_server);
+/// An event that is sent once an asset and all of its transitive dependencies have finished
+/// loading. See [`AssetServer::get_recursive_load_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetLoadedWithDependencies {
+    pub id: HandleId,
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Sends an [`AssetLoadedWithDependencies`] event for each asset requested from the
+/// [`AssetServer`] whose dependencies have all loaded.
+pub fn recursive_load_event_system(
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<AssetLoadedWithDependencies>,
+) {
+    // the lock is released before computing the load states, which lock the asset sources
+    let pending = asset_server
+        .server
+        .pending_recursive_loads
+        .lock()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        return;
+    }
+
+    let mut finished = Vec::new();
+    for id in pending {
+        match asset_server.get_recursive_load_state(id) {
+            LoadState::Loaded => {
+                events.send(AssetLoadedWithDependencies { id: id.into() });
+                finished.push(id);
+            }
+            LoadState::NotLoaded | LoadState::Loading => {}
+            LoadState::Failed | LoadState::Unloaded => finished.push(id),
+        }
+    }
+
+    let mut pending = asset_server.server.pending_recursive_loads.lock();
+    for id in finished {
+        pending.remove(&id);
+    }
+}
+
+/// Sends an [`AssetFolderEvent`] for each file that was created or removed in a live folder.
+pub fn live_folder_event_system(
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<AssetFolderEvent>,
+) {
+    let mut live_folder_events = asset_server.server.live_folder_events.lock();
+    if !live_folder_events.is_empty() {
+        events.send_batch(live_folder_events.drain(..));
+    }
+}
+
+fn add_load_progress(
+    progress: &mut LoadProgress,
+    source_info: Option<&SourceInfo>,
+    is_queued: bool,
+) {
+    if let Some(source_info) = source_info {
+        progress.bytes_read += source_info.bytes_read;
+        progress.total_bytes += source_info.total_bytes.unwrap_or(0);
+    }
+    match source_info.map(|source_info| source_info.load_state) {
+        Some(LoadState::Loading) => progress.loading += 1,
+        _ if is_queued => progress.queued += 1,
+        Some(LoadState::Loaded) => progress.loaded += 1,
+        Some(LoadState::Failed) => progress.failed += 1,
+        Some(LoadState::NotLoaded) | Some(LoadState::Unloaded) | None => {}
+    }
+}
+
+/// Updates the [`LoadProgress`] resource with the progress of all assets requested from the
+/// [`AssetServer`].
+pub fn load_progress_system(asset_server: Res<AssetServer>, mut progress: ResMut<LoadProgress>) {
+    let total_progress = asset_server.get_total_load_progress();
+    // only trigger change detection when the progress changed
+    if *progress != total_progress {
+        *progress = total_progress;
+    }
+}
+
+fn free_unused_assets_system_impl(asset_server: &AssetServer) {
+    asset_server.free_unused_assets();
+    asset_server.mark_unused_assets();
+}
+
+pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
+    free_unused_assets_system_impl(&asset_server);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
     use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use bevy_app::Events;
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
     use bevy_utils::BoxedFuture;
@@ -562,6 +1349,12 @@ mod test {
     #[uuid = "a5189b72-0572-4290-a2e0-96f73a491c44"]
     struct PngAsset;
 
+    impl crate::AssetSize for PngAsset {
+        fn asset_size(&self) -> usize {
+            10
+        }
+    }
+
     struct FakePngLoader;
     impl AssetLoader for FakePngLoader {
         fn load<'a>(
@@ -608,19 +1401,163 @@ mod test {
         }
     }
 
+    struct FakeDependentLoader;
+    impl AssetLoader for FakeDependentLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(
+                LoadedAsset::new(PngAsset).with_dependency("dependency.png".into()),
+            );
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["dep"]
+        }
+    }
+
+    struct FakeMaterialLoader;
+    impl AssetLoader for FakeMaterialLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let bytes = ctx.read_asset_bytes("raw.bin").await?;
+                assert_eq!(bytes, b"raw");
+                let texture: PngAsset = ctx.load_direct("texture.png").await?;
+                ctx.set_default_asset(LoadedAsset::new(texture));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["mat"]
+        }
+    }
+
+    // directly loads the asset at the path in its contents
+    struct FakeLinkLoader;
+    impl AssetLoader for FakeLinkLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let target = std::str::from_utf8(bytes)?.to_string();
+                let asset: PngAsset = ctx.load_direct(target.as_str()).await?;
+                ctx.set_default_asset(LoadedAsset::new(asset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["link"]
+        }
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct FakeSettings {
+        nearest: bool,
+    }
+
+    struct FakeSettingsLoader;
+    impl AssetLoader for FakeSettingsLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(LoadedAsset::new(PngAsset));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["tex"]
+        }
+
+        fn deserialize_settings(
+            &self,
+            meta: &[u8],
+        ) -> Result<Option<Box<dyn crate::AssetLoaderSettings>>, anyhow::Error> {
+            crate::deserialize_ron_settings::<FakeSettings>(meta)
+        }
+
+        fn has_settings(&self) -> bool {
+            true
+        }
+    }
+
+    struct FakeSvgProcessor {
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+    }
+    impl AssetProcessor for FakeSvgProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            process_context: &'a mut crate::ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Box::pin(async move {
+                process_context.read_asset_bytes("palette.txt").await?;
+                Ok(bytes.to_vec())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["svg"]
+        }
+
+        fn processed_extension(&self) -> &str {
+            "png"
+        }
+    }
+
+    struct FakePngSaver;
+    impl AssetSaver for FakePngSaver {
+        type Asset = PngAsset;
+
+        fn save(&self, _: &PngAsset, _: &Path) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(b"saved png".to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["png"]
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
 
+        setup_io(Box::new(FileAssetIo::new(asset_path)))
+    }
+
+    fn setup_io(asset_io: Box<dyn AssetIo>) -> AssetServer {
         AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                extension_to_processor_index: Default::default(),
+                processed_asset_cache: Default::default(),
+                savers: Default::default(),
+                saved_asset_hashes: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                queued_loads: Default::default(),
+                live_folders: Default::default(),
+                live_folder_events: Default::default(),
+                loader_dependants: Default::default(),
+                pending_recursive_loads: Default::default(),
                 asset_lifecycles: Default::default(),
                 task_pool: Default::default(),
-                asset_io: Box::new(FileAssetIo::new(asset_path)),
+                asset_io,
             }),
         }
     }
@@ -760,6 +1697,719 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_recursive_load_state() {
+        let dir = create_dir_and_file("root.dep");
+        std::fs::write(dir.path().join("dependency.png"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeDependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut world = World::new();
+        world.insert_resource(assets);
+        world.insert_resource(asset_server);
+        world.insert_resource(Events::<AssetLoadedWithDependencies>::default());
+
+        let mut tick = {
+            let mut update_asset_storage_system = update_asset_storage_system::<PngAsset>.system();
+            update_asset_storage_system.initialize(&mut world);
+            let mut recursive_load_event_system = recursive_load_event_system.system();
+            recursive_load_event_system.initialize(&mut world);
+
+            move |world: &mut World| {
+                update_asset_storage_system.run((), world);
+                recursive_load_event_system.run((), world);
+            }
+        };
+
+        let asset_server = world.get_resource::<AssetServer>().unwrap().clone();
+        let handle = asset_server.load_untyped("root.dep");
+        // the load may already have started on the task pool, but nothing is committed yet
+        assert!(matches!(
+            asset_server.get_recursive_load_state(&handle),
+            LoadState::NotLoaded | LoadState::Loading
+        ));
+
+        // the dependency is loaded in the background after the root asset
+        let mut load_state = LoadState::NotLoaded;
+        for _ in 0..500 {
+            tick(&mut world);
+            load_state = asset_server.get_recursive_load_state(&handle);
+            if load_state == LoadState::Loaded {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        assert_eq!(load_state, LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_load_state(AssetPath::from("dependency.png")),
+            LoadState::Loaded
+        );
+
+        let events = world
+            .get_resource::<Events<AssetLoadedWithDependencies>>()
+            .unwrap();
+        let mut reader = events.get_reader();
+        let loaded = reader
+            .iter(events)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert!(loaded.contains(&handle.id));
+
+        // loading an asset that is already loaded does not send the event again
+        let _handle = asset_server.load_untyped("root.dep");
+        for _ in 0..50 {
+            tick(&mut world);
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let events = world
+            .get_resource::<Events<AssetLoadedWithDependencies>>()
+            .unwrap();
+        assert_eq!(reader.iter(events).count(), 0);
+        assert!(asset_server
+            .server
+            .pending_recursive_loads
+            .lock()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_asset_settings() {
+        let dir = create_dir_and_file("plain.tex");
+        std::fs::write(dir.path().join("pixel.tex"), []).unwrap();
+        std::fs::write(dir.path().join("pixel.tex.meta"), "(nearest: true)").unwrap();
+        std::fs::write(dir.path().join("broken.tex"), []).unwrap();
+        std::fs::write(dir.path().join("broken.tex.meta"), "(nearest: 4)").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeSettingsLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let get_settings = |path: &str| {
+            let id =
+                futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap();
+            let asset_sources = asset_server.server.asset_sources.read();
+            let meta = asset_sources[&id.source_path_id()].meta.clone().unwrap();
+            meta.assets[0]
+                .settings
+                .as_ref()
+                .map(|settings| settings.downcast_ref::<FakeSettings>().unwrap().nearest)
+        };
+
+        assert_eq!(get_settings("plain.tex"), None);
+        assert_eq!(get_settings("pixel.tex"), Some(true));
+
+        let path: AssetPath = "broken.tex".into();
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetSettingsError(_)));
+        assert_eq!(
+            asset_server.get_load_state(path.get_id()),
+            LoadState::Failed
+        );
+
+        // loaders without settings do not read `.meta` files, so an unreadable one is ignored
+        std::fs::write(dir.path().join("image.png"), []).unwrap();
+        std::fs::create_dir(dir.path().join("image.png.meta")).unwrap();
+        asset_server.add_loader(FakePngLoader);
+        futures_lite::future::block_on(asset_server.load_async("image.png".into(), true)).unwrap();
+    }
+
+    #[test]
+    fn test_asset_settings_created_later() {
+        use crate::{filesystem_watcher_system, EmbeddedAssetIo, EmbeddedAssets};
+
+        let embedded_assets = EmbeddedAssets::default();
+        embedded_assets.insert("pixel.tex", b"" as &'static [u8]);
+        let asset_server = setup_io(Box::new(EmbeddedAssetIo::new(embedded_assets.clone())));
+        asset_server.add_loader(FakeSettingsLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let path: AssetPath = "pixel.tex".into();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), false)).unwrap();
+        let get_settings = || {
+            let asset_sources = asset_server.server.asset_sources.read();
+            let meta = asset_sources[&path.get_id().source_path_id()]
+                .meta
+                .clone()?;
+            meta.assets[0]
+                .settings
+                .as_ref()
+                .map(|settings| settings.downcast_ref::<FakeSettings>().unwrap().nearest)
+        };
+        assert_eq!(get_settings(), None);
+
+        // creating a `.meta` file reloads the asset with its settings
+        let mut world = World::new();
+        world.insert_resource(asset_server.clone());
+        let mut filesystem_watcher_system = filesystem_watcher_system.system();
+        filesystem_watcher_system.initialize(&mut world);
+        embedded_assets.insert("pixel.tex.meta", b"(nearest: true)" as &'static [u8]);
+        filesystem_watcher_system.run((), &mut world);
+        for _ in 0..500 {
+            if get_settings().is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        assert_eq!(get_settings(), Some(true));
+    }
+
+    #[test]
+    fn test_processed_asset_cache() {
+        let dir = create_dir_and_file("image.svg");
+        std::fs::write(dir.path().join("palette.txt"), "red").unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        asset_server.add_processor(FakeSvgProcessor { runs: runs.clone() });
+        asset_server.set_processed_asset_cache(Some(ProcessedAssetCache::new(cache_dir.path())));
+
+        let load = |asset_server: &AssetServer| {
+            futures_lite::future::block_on(asset_server.load_async("image.svg".into(), true))
+                .unwrap()
+        };
+        let process_count = || runs.load(std::sync::atomic::Ordering::SeqCst);
+
+        // the first load processes the asset and populates the cache
+        let id = load(&asset_server);
+        assert_eq!(process_count(), 1);
+        assert_eq!(asset_server.get_load_state(id), LoadState::Loading);
+
+        // unchanged sources are served from the cache
+        load(&asset_server);
+        assert_eq!(process_count(), 1);
+
+        // files read by the processor are watched like loader dependencies, also for cached
+        // artifacts
+        assert_eq!(
+            asset_server.get_loader_dependants(Path::new("palette.txt")),
+            vec![PathBuf::from("image.svg")]
+        );
+
+        // modified sources are processed again, replacing the previous artifact
+        std::fs::write(dir.path().join("image.svg"), b"<svg/>").unwrap();
+        load(&asset_server);
+        assert_eq!(process_count(), 2);
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 2);
+
+        std::fs::write(dir.path().join("palette.txt"), "blue").unwrap();
+        load(&asset_server);
+        assert_eq!(process_count(), 3);
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_asset_sources() {
+        use crate::{FileAssetIo, LayeredAssetIo, NamespacedAssetIo};
+
+        let base = create_dir_and_file("base.png");
+        std::fs::write(base.path().join("shadowed.png"), "base").unwrap();
+        let overlay = create_dir_and_file("shadowed.png");
+        let user = create_dir_and_file("settings.png");
+
+        let asset_server = setup_io(Box::new(
+            NamespacedAssetIo::new(Box::new(
+                LayeredAssetIo::new(Box::new(FileAssetIo::new(base.path())))
+                    .with_layer(Box::new(FileAssetIo::new(overlay.path()))),
+            ))
+            .with_source("user", Box::new(FileAssetIo::new(user.path()))),
+        ));
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_server.server.asset_io.load_path(Path::new(path)))
+        };
+        assert!(load("base.png").is_ok());
+        assert_eq!(load("shadowed.png").unwrap(), b"");
+        assert!(load("user://settings.png").is_ok());
+        assert!(matches!(
+            load("user://base.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(matches!(
+            load("mods://base.png"),
+            Err(AssetIoError::UnknownSource(_))
+        ));
+        assert_eq!(
+            AssetPath::from("user://settings.png").source(),
+            Some("user")
+        );
+
+        let mut paths = asset_server
+            .load_folder(".")
+            .unwrap()
+            .iter()
+            .map(|handle| asset_server.get_handle_path(handle).unwrap().to_owned())
+            .map(|path| path.path().to_owned())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![Path::new("base.png"), Path::new("shadowed.png")]
+        );
+
+        let paths = asset_server
+            .load_folder("user://")
+            .unwrap()
+            .iter()
+            .map(|handle| asset_server.get_handle_path(handle).unwrap().to_owned())
+            .map(|path| path.path().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec![Path::new("user://settings.png")]);
+    }
+
+    #[test]
+    fn test_embedded_assets() {
+        use crate::{
+            EmbeddedAssetIo, EmbeddedAssets, FileAssetIo, NamespacedAssetIo, EMBEDDED_SOURCE,
+        };
+
+        let dir = create_dir_and_file("fake.png");
+        let embedded_assets = EmbeddedAssets::default();
+        embedded_assets.insert("shaders/sprite.png", b"sprite" as &'static [u8]);
+        embedded_assets.insert("shaders/mesh.png", b"mesh" as &'static [u8]);
+
+        let asset_server = setup_io(Box::new(
+            NamespacedAssetIo::new(Box::new(FileAssetIo::new(dir.path()))).with_source(
+                EMBEDDED_SOURCE,
+                Box::new(EmbeddedAssetIo::new(embedded_assets.clone())),
+            ),
+        ));
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let asset_io = &asset_server.server.asset_io;
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("embedded://shaders/sprite.png").unwrap(), b"sprite");
+        assert!(matches!(
+            load("embedded://shaders/missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(asset_io.is_directory(Path::new("embedded://shaders")));
+        assert!(!asset_io.is_directory(Path::new("embedded://shaders/mesh.png")));
+
+        let mut paths = asset_server
+            .load_folder("embedded://shaders")
+            .unwrap()
+            .iter()
+            .map(|handle| asset_server.get_handle_path(handle).unwrap().to_owned())
+            .map(|path| path.path().to_owned())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("embedded://shaders/mesh.png"),
+                Path::new("embedded://shaders/sprite.png")
+            ]
+        );
+
+        // overriding a watched embedded asset reports it as changed
+        asset_io
+            .watch_path_for_changes(Path::new("embedded://shaders/sprite.png"))
+            .unwrap();
+        embedded_assets.insert("shaders/sprite.png", b"custom".to_vec());
+        assert_eq!(
+            asset_io.changed_paths(),
+            vec![Path::new("embedded://shaders/sprite.png")]
+        );
+        assert_eq!(load("embedded://shaders/sprite.png").unwrap(), b"custom");
+    }
+
+    #[test]
+    fn test_loader_dependencies() {
+        let dir = create_dir_and_file("stone.mat");
+        std::fs::write(dir.path().join("raw.bin"), "raw").unwrap();
+        std::fs::write(dir.path().join("texture.png"), "").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeMaterialLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let path: AssetPath = "stone.mat".into();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), false)).unwrap();
+
+        let loader_dependencies = asset_server.server.asset_sources.read()
+            [&path.get_id().source_path_id()]
+            .meta
+            .as_ref()
+            .unwrap()
+            .loader_dependencies
+            .clone();
+        assert_eq!(
+            loader_dependencies,
+            vec![PathBuf::from("raw.bin"), PathBuf::from("texture.png")]
+        );
+
+        // changes to either file reload the material
+        for dependency in loader_dependencies.iter() {
+            assert_eq!(
+                asset_server.get_loader_dependants(dependency),
+                vec![PathBuf::from("stone.mat")]
+            );
+        }
+        assert!(asset_server
+            .get_loader_dependants(Path::new("stone.mat"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cyclic_load_direct() {
+        let dir = create_dir_and_file("texture.png");
+        std::fs::write(dir.path().join("self.link"), "self.link").unwrap();
+        std::fs::write(dir.path().join("a.link"), "b.link").unwrap();
+        std::fs::write(dir.path().join("b.link"), "a.link").unwrap();
+        std::fs::write(dir.path().join("chain.link"), "a.link").unwrap();
+        std::fs::write(dir.path().join("c.link"), "texture.png").unwrap();
+        std::fs::write(dir.path().join("d.link"), "c.link").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeLinkLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        // finds the error of the innermost loader in the nested loader errors
+        fn cyclic_path(err: &AssetServerError) -> Option<PathBuf> {
+            match err {
+                AssetServerError::CyclicLoad(path) => Some(path.clone()),
+                AssetServerError::AssetLoaderError(err) => {
+                    err.downcast_ref::<AssetServerError>().and_then(cyclic_path)
+                }
+                _ => None,
+            }
+        }
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_server.load_async(path.into(), false))
+        };
+
+        assert_eq!(
+            cyclic_path(&load("self.link").unwrap_err()),
+            Some(PathBuf::from("self.link"))
+        );
+        assert_eq!(
+            cyclic_path(&load("a.link").unwrap_err()),
+            Some(PathBuf::from("a.link"))
+        );
+        assert_eq!(
+            cyclic_path(&load("chain.link").unwrap_err()),
+            Some(PathBuf::from("a.link"))
+        );
+
+        // chains without a cycle still load
+        load("d.link").unwrap();
+    }
+
+    #[test]
+    fn test_save_asset() {
+        use crate::filesystem_watcher_system;
+
+        let dir = create_dir_and_file("fake.png");
+        let asset_server = setup(dir.path());
+        asset_server.watch_for_changes().unwrap();
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_saver(FakePngSaver);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut world = World::new();
+        world.insert_resource(assets);
+        world.insert_resource(asset_server.clone());
+        let mut tick = {
+            let mut filesystem_watcher_system = filesystem_watcher_system.system();
+            filesystem_watcher_system.initialize(&mut world);
+            let mut update_asset_storage_system = update_asset_storage_system::<PngAsset>.system();
+            update_asset_storage_system.initialize(&mut world);
+
+            move |world: &mut World| {
+                filesystem_watcher_system.run((), world);
+                update_asset_storage_system.run((), world);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+        };
+        let version = |asset_server: &AssetServer| {
+            let source_path_id = SourcePathId::from(Path::new("fake.png"));
+            asset_server.server.asset_sources.read()[&source_path_id].version
+        };
+
+        let handle: Handle<PngAsset> = asset_server.load("fake.png");
+        for _ in 0..100 {
+            if asset_server.get_load_state(&handle) == LoadState::Loaded {
+                break;
+            }
+            tick(&mut world);
+        }
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+        let loaded_version = version(&asset_server);
+
+        assert!(matches!(
+            asset_server.save(&handle, "saved.txt"),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+        asset_server.save(&handle, "fake.png").unwrap();
+
+        // the asset is written on the task pool, and the change it causes is consumed without
+        // reloading the asset
+        let saved_path = dir.path().join("fake.png");
+        for _ in 0..100 {
+            tick(&mut world);
+            let is_written =
+                matches!(std::fs::read(&saved_path), Ok(bytes) if bytes == b"saved png");
+            if is_written && asset_server.server.saved_asset_hashes.lock().is_empty() {
+                break;
+            }
+        }
+        assert_eq!(std::fs::read(&saved_path).unwrap(), b"saved png");
+        assert!(asset_server.server.saved_asset_hashes.lock().is_empty());
+        for _ in 0..10 {
+            tick(&mut world);
+        }
+        assert_eq!(version(&asset_server), loaded_version);
+
+        // later changes reload the asset
+        std::fs::write(&saved_path, b"edited png").unwrap();
+        for _ in 0..100 {
+            if version(&asset_server) != loaded_version {
+                break;
+            }
+            tick(&mut world);
+        }
+        assert_ne!(version(&asset_server), loaded_version);
+    }
+
+    #[test]
+    fn test_live_folder() {
+        use crate::{
+            filesystem_watcher_system, EmbeddedAssetIo, EmbeddedAssets, FileAssetIo,
+            NamespacedAssetIo, EMBEDDED_SOURCE,
+        };
+
+        let dir = create_dir_and_file("fake.png");
+        let embedded_assets = EmbeddedAssets::default();
+        embedded_assets.insert("levels/a.png", b"" as &'static [u8]);
+        let asset_server = setup_io(Box::new(
+            NamespacedAssetIo::new(Box::new(FileAssetIo::new(dir.path()))).with_source(
+                EMBEDDED_SOURCE,
+                Box::new(EmbeddedAssetIo::new(embedded_assets.clone())),
+            ),
+        ));
+        asset_server.add_loader(FakePngLoader);
+        let png_assets = asset_server.register_asset_type::<PngAsset>();
+        let folder_assets = asset_server.register_asset_type::<AssetFolder>();
+
+        let mut world = World::new();
+        world.insert_resource(png_assets);
+        world.insert_resource(folder_assets);
+        world.insert_resource(asset_server.clone());
+        world.insert_resource(Events::<AssetFolderEvent>::default());
+
+        let mut tick = {
+            let mut filesystem_watcher_system = filesystem_watcher_system.system();
+            filesystem_watcher_system.initialize(&mut world);
+            let mut update_asset_storage_system =
+                update_asset_storage_system::<AssetFolder>.system();
+            update_asset_storage_system.initialize(&mut world);
+            let mut live_folder_event_system = live_folder_event_system.system();
+            live_folder_event_system.initialize(&mut world);
+
+            move |world: &mut World| {
+                filesystem_watcher_system.run((), world);
+                update_asset_storage_system.run((), world);
+                live_folder_event_system.run((), world);
+            }
+        };
+        let mut reader = world
+            .get_resource::<Events<AssetFolderEvent>>()
+            .unwrap()
+            .get_reader();
+        let mut folder_events = move |world: &World| {
+            let events = world.get_resource::<Events<AssetFolderEvent>>().unwrap();
+            reader.iter(events).cloned().collect::<Vec<_>>()
+        };
+        let folder_paths = |handle: &Handle<AssetFolder>, world: &World| {
+            world
+                .get_resource::<Assets<AssetFolder>>()
+                .unwrap()
+                .get(handle)
+                .unwrap()
+                .paths()
+                .map(|path| path.to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(asset_server.load_live_folder("embedded://missing").is_err());
+        let folder = asset_server.load_live_folder("embedded://levels").unwrap();
+        tick(&mut world);
+        assert_eq!(asset_server.get_load_state(&folder), LoadState::Loaded);
+        assert_eq!(
+            folder_paths(&folder, &world),
+            vec![PathBuf::from("embedded://levels/a.png")]
+        );
+        assert_eq!(
+            folder_events(&world),
+            vec![AssetFolderEvent::Created {
+                folder: folder.clone_weak(),
+                path: PathBuf::from("embedded://levels/a.png")
+            }]
+        );
+
+        // added files are loaded and added to the folder
+        embedded_assets.insert("levels/b.png", b"" as &'static [u8]);
+        tick(&mut world);
+        assert_eq!(
+            folder_paths(&folder, &world),
+            vec![
+                PathBuf::from("embedded://levels/a.png"),
+                PathBuf::from("embedded://levels/b.png")
+            ]
+        );
+        assert_eq!(
+            folder_events(&world),
+            vec![AssetFolderEvent::Created {
+                folder: folder.clone_weak(),
+                path: PathBuf::from("embedded://levels/b.png")
+            }]
+        );
+
+        // removed files are removed from the folder
+        embedded_assets.remove(Path::new("levels/a.png"));
+        tick(&mut world);
+        assert_eq!(
+            folder_paths(&folder, &world),
+            vec![PathBuf::from("embedded://levels/b.png")]
+        );
+        assert_eq!(
+            folder_events(&world),
+            vec![AssetFolderEvent::Removed {
+                folder: folder.clone_weak(),
+                path: PathBuf::from("embedded://levels/a.png")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_handle_paths_in_reflection() {
+        use crate::{handle_ids_to_paths, load_handle_paths, make_handles_strong};
+        use bevy_reflect::{GetField, Reflect, ReflectRef, TypeRegistry};
+
+        #[derive(Reflect, Default)]
+        struct Sprite {
+            texture: Handle<PngAsset>,
+            generated: Handle<PngAsset>,
+        }
+
+        let dir = create_dir_and_file("fake.png");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Handle<PngAsset>>();
+        type_registry.register::<HandleId>();
+        type_registry.register::<AssetPath<'static>>();
+
+        let texture: Handle<PngAsset> = asset_server.load("fake.png");
+        let sprite = Sprite {
+            texture: texture.clone(),
+            generated: Handle::weak(HandleId::random::<PngAsset>()),
+        };
+
+        // only handles that were loaded from a path are replaced
+        let mut value = sprite.clone_value();
+        handle_ids_to_paths(&mut *value, &type_registry, &asset_server);
+        let field = |value: &dyn Reflect, name: &str| match value.reflect_ref() {
+            ReflectRef::Struct(value) => value.field(name).unwrap().clone_value(),
+            _ => panic!("expected a struct"),
+        };
+        let dynamic_texture = field(&*value, "texture");
+        let dynamic_texture = match dynamic_texture.reflect_ref() {
+            ReflectRef::Struct(dynamic_texture) => dynamic_texture,
+            _ => panic!("expected a struct"),
+        };
+        assert_eq!(
+            dynamic_texture.get_field::<AssetPath<'static>>("path"),
+            Some(&AssetPath::from("fake.png"))
+        );
+        assert!(dynamic_texture.field("id").is_none());
+        let dynamic_generated = field(&*value, "generated");
+        let generated_id = match dynamic_generated.reflect_ref() {
+            ReflectRef::Struct(generated) => generated.get_field::<HandleId>("id").copied(),
+            _ => None,
+        };
+        assert_eq!(generated_id, Some(sprite.generated.id));
+
+        // loading the paths restores the ids, and the applied handles can be made strong
+        let loaded_handles = load_handle_paths(&mut *value, &type_registry, &asset_server);
+        assert_eq!(loaded_handles.len(), 1);
+        assert_eq!(loaded_handles[0].id, texture.id);
+
+        let mut restored = Sprite::default();
+        restored.apply(&*value);
+        assert_eq!(restored.texture.id, texture.id);
+        assert!(restored.texture.is_weak());
+        make_handles_strong(
+            &mut restored,
+            &type_registry,
+            &asset_server,
+            &loaded_handles.iter().map(|handle| handle.id).collect(),
+        );
+        assert!(restored.texture.is_strong());
+        assert!(restored.generated.is_weak());
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("fake.png"), "fake").unwrap();
+        std::fs::write(dir.path().join("fake.txt"), "text").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        assert_eq!(
+            asset_server.get_total_load_progress(),
+            LoadProgress::default()
+        );
+        assert_eq!(asset_server.get_total_load_progress().fraction(), 1.0);
+
+        let png: AssetPath = "fake.png".into();
+        futures_lite::future::block_on(asset_server.load_async(png.clone(), false)).unwrap();
+        let progress = asset_server.get_load_progress(png.get_id());
+        assert_eq!(progress.loading, 1);
+        assert_eq!((progress.bytes_read, progress.total_bytes), (4, 4));
+        assert_eq!(progress.bytes_fraction(), 1.0);
+
+        // sources without a loader fail to load
+        let txt: AssetPath = "fake.txt".into();
+        assert!(
+            futures_lite::future::block_on(asset_server.load_async(txt.clone(), false)).is_err()
+        );
+
+        // sources count as queued until their load starts
+        let queued: AssetPath = "queued.png".into();
+        asset_server
+            .server
+            .queued_loads
+            .lock()
+            .insert(queued.get_id().source_path_id());
+
+        let progress = asset_server.get_group_load_progress(vec![
+            png.get_id().into(),
+            txt.get_id().into(),
+            queued.get_id().into(),
+        ]);
+        assert_eq!(
+            (progress.queued, progress.loading, progress.failed),
+            (1, 1, 1)
+        );
+        assert_eq!(progress.total(), 3);
+        assert_eq!(progress.finished(), 1);
+        assert_eq!(asset_server.get_total_load_progress(), progress);
+    }
+
     #[test]
     fn test_asset_lifecycle() {
         let dir = create_dir_and_file("fake.png");
@@ -842,6 +2492,156 @@ mod test {
         assert!(get_asset(&handle, &world).is_some());
     }
 
+    #[test]
+    fn test_asset_budget() {
+        let dir = create_dir_and_file("a.png");
+        std::fs::write(dir.path().join("b.png"), "").unwrap();
+        std::fs::write(dir.path().join("c.png"), "").unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let mut assets = asset_server.register_asset_type::<PngAsset>();
+        assets.set_count_budget(1);
+
+        let mut world = World::new();
+        world.insert_resource(assets);
+        world.insert_resource(asset_server);
+
+        let mut tick = {
+            let mut free_unused_assets_system = free_unused_assets_system.system();
+            free_unused_assets_system.initialize(&mut world);
+            let mut update_asset_storage_system = update_asset_storage_system::<PngAsset>.system();
+            update_asset_storage_system.initialize(&mut world);
+
+            move |world: &mut World| {
+                free_unused_assets_system.run((), world);
+                update_asset_storage_system.run((), world);
+            }
+        };
+
+        let load = |path: &str, world: &World| -> Handle<PngAsset> {
+            let asset_server = world.get_resource::<AssetServer>().unwrap();
+            let id = futures_lite::future::block_on(asset_server.load_async(path.into(), false))
+                .unwrap();
+            asset_server.get_handle(id)
+        };
+        let is_loaded = |path: &str, world: &World| {
+            world
+                .get_resource::<Assets<PngAsset>>()
+                .unwrap()
+                .contains(AssetPath::from(path).get_id())
+        };
+
+        let a = load("a.png", &world);
+        let b = load("b.png", &world);
+        let c = load("c.png", &world);
+        tick(&mut world);
+        assert!(is_loaded("a.png", &world) && is_loaded("b.png", &world));
+
+        // unused assets are kept within the budget, evicting the least recently used first
+        drop(a);
+        drop(b);
+        tick(&mut world);
+        tick(&mut world);
+        assert!(!is_loaded("a.png", &world));
+        assert!(is_loaded("b.png", &world));
+        assert!(is_loaded("c.png", &world));
+        let assets = world.get_resource::<Assets<PngAsset>>().unwrap();
+        assert_eq!(assets.unused_len(), 1);
+
+        // loading a kept asset again reuses it
+        let b = load("b.png", &world);
+        assert_eq!(
+            world
+                .get_resource::<AssetServer>()
+                .unwrap()
+                .get_load_state(&b),
+            LoadState::Loaded
+        );
+        drop(c);
+        tick(&mut world);
+        tick(&mut world);
+        assert!(is_loaded("b.png", &world));
+        assert!(is_loaded("c.png", &world));
+        let assets = world.get_resource::<Assets<PngAsset>>().unwrap();
+        assert_eq!(assets.unused_len(), 1);
+
+        // removing the budget frees all unused assets
+        world
+            .get_resource_mut::<Assets<PngAsset>>()
+            .unwrap()
+            .remove_budget();
+        tick(&mut world);
+        assert!(is_loaded("b.png", &world));
+        assert!(!is_loaded("c.png", &world));
+    }
+
+    #[test]
+    fn test_asset_memory_usage() {
+        let asset_server = setup(".");
+        let mut assets = asset_server.register_asset_type::<PngAsset>();
+        assert_eq!(assets.memory_usage(), None);
+
+        assets.set_byte_budget(15);
+        let _handle = assets.add(PngAsset);
+        let unused = HandleId::from(AssetPath::from("unused.png"));
+        assets.set_untracked(unused, PngAsset);
+        assert!(assets.keep_unused(unused));
+        assert_eq!(assets.memory_usage(), Some(20));
+        assert_eq!(assets.unused_memory_usage(), Some(10));
+        assert!(!assets.needs_eviction(|_| false));
+
+        let other = HandleId::from(AssetPath::from("other.png"));
+        assets.set_untracked(other, PngAsset);
+        assert!(assets.keep_unused(other));
+        assert!(assets.needs_eviction(|_| false));
+        assert_eq!(assets.evict_unused(|_| false), vec![unused]);
+    }
+
+    #[test]
+    fn test_asset_memory_diagnostics() {
+        use crate::diagnostic::AssetCountDiagnosticsPlugin;
+        use bevy_diagnostic::Diagnostics;
+
+        type Plugin = AssetCountDiagnosticsPlugin<PngAsset>;
+        let ids = [
+            Plugin::diagnostic_id(),
+            Plugin::memory_diagnostic_id(),
+            Plugin::unused_memory_diagnostic_id(),
+        ];
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(
+            Plugin::memory_diagnostic_id(),
+            Plugin::memory_diagnostic_id()
+        );
+
+        let asset_server = setup(".");
+        let mut assets = asset_server.register_asset_type::<PngAsset>();
+        let _handle = assets.add(PngAsset);
+        let mut world = World::new();
+        world.insert_resource(Diagnostics::default());
+        world.insert_resource(assets);
+        let mut setup_system = Plugin::setup_system.system();
+        setup_system.initialize(&mut world);
+        setup_system.run((), &mut world);
+        let mut diagnostic_system = Plugin::diagnostic_system.system();
+        diagnostic_system.initialize(&mut world);
+
+        // memory diagnostics are only added for asset types that track their memory usage
+        diagnostic_system.run((), &mut world);
+        let diagnostics = world.get_resource::<Diagnostics>().unwrap();
+        assert_eq!(diagnostics.get(ids[0]).unwrap().value(), Some(1.0));
+        assert!(diagnostics.get(ids[1]).is_none());
+
+        world
+            .get_resource_mut::<Assets<PngAsset>>()
+            .unwrap()
+            .track_memory();
+        diagnostic_system.run((), &mut world);
+        let diagnostics = world.get_resource::<Diagnostics>().unwrap();
+        assert_eq!(diagnostics.get(ids[1]).unwrap().value(), Some(10.0));
+        assert_eq!(diagnostics.get(ids[2]).unwrap().value(), Some(0.0));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,12 +1,12 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    update_asset_storage_system, Asset, AssetLoader, AssetProcessor, AssetSaver, AssetServer,
+    AssetStage, Handle, HandleId, RefChange,
 };
 use bevy_app::{App, EventWriter, Events};
 use bevy_ecs::{system::ResMut, world::FromWorld};
 use bevy_utils::HashMap;
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{collections::VecDeque, fmt::Debug};
 
 /// Events that happen on assets of type `T`
 pub enum AssetEvent<T: Asset> {
@@ -43,11 +43,34 @@ impl<T: Asset> Debug for AssetEvent<T> {
     }
 }
 
+/// Reports the memory used by an asset, which enables byte budgets and memory diagnostics for its
+/// asset type. See [`Assets::track_memory`].
+pub trait AssetSize {
+    /// The approximate number of bytes used by this asset, including its heap allocations
+    fn asset_size(&self) -> usize;
+}
+
+/// Limits how many loaded assets of a type are kept after their last strong handle is dropped.
+///
+/// Unused assets within the budget stay loaded, so loading them again is free. When the budget is
+/// exceeded, the assets that became unused the longest time ago are freed first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetBudget {
+    /// Keep at most this many unused assets
+    Count(usize),
+    /// Keep unused assets up to this many bytes, as reported by [`AssetSize`]
+    Bytes(usize),
+}
+
 /// Stores Assets of a given type and tracks changes to them.
 #[derive(Debug)]
 pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
+    budget: Option<AssetBudget>,
+    size_of: Option<fn(&T) -> usize>,
+    // unused assets kept within the budget, from the least to the most recently used
+    unused: VecDeque<HandleId>,
     pub(crate) ref_change_sender: Sender<RefChange>,
 }
 
@@ -56,6 +79,9 @@ impl<T: Asset> Assets<T> {
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
+            budget: None,
+            size_of: None,
+            unused: VecDeque::new(),
             ref_change_sender,
         }
     }
@@ -185,6 +211,125 @@ impl<T: Asset> Assets<T> {
         self.assets.len()
     }
 
+    /// Measures the assets with [`AssetSize`], which enables [`Assets::memory_usage`].
+    pub fn track_memory(&mut self)
+    where
+        T: AssetSize,
+    {
+        self.size_of = Some(T::asset_size);
+    }
+
+    /// Keeps up to `count` assets loaded after their last strong handle is dropped.
+    pub fn set_count_budget(&mut self, count: usize) {
+        self.budget = Some(AssetBudget::Count(count));
+    }
+
+    /// Keeps assets loaded after their last strong handle is dropped, as long as they use at most
+    /// `bytes` in total. This also enables [`Assets::track_memory`].
+    pub fn set_byte_budget(&mut self, bytes: usize)
+    where
+        T: AssetSize,
+    {
+        self.track_memory();
+        self.budget = Some(AssetBudget::Bytes(bytes));
+    }
+
+    /// Frees assets as soon as their last strong handle is dropped, which is the default.
+    ///
+    /// Unused assets that are currently kept are freed the next time the storage is updated.
+    pub fn remove_budget(&mut self) {
+        self.budget = None;
+    }
+
+    pub fn budget(&self) -> Option<AssetBudget> {
+        self.budget
+    }
+
+    /// The number of assets that are kept loaded without a strong handle
+    pub fn unused_len(&self) -> usize {
+        self.unused.len()
+    }
+
+    /// The memory used by all assets, if it is tracked with [`Assets::track_memory`]
+    pub fn memory_usage(&self) -> Option<usize> {
+        let size_of = self.size_of?;
+        Some(self.assets.values().map(size_of).sum())
+    }
+
+    /// The memory used by the assets that are kept loaded without a strong handle, if it is
+    /// tracked with [`Assets::track_memory`]
+    pub fn unused_memory_usage(&self) -> Option<usize> {
+        let size_of = self.size_of?;
+        Some(self.unused_size(size_of))
+    }
+
+    fn unused_size(&self, size_of: fn(&T) -> usize) -> usize {
+        self.unused
+            .iter()
+            .filter_map(|id| self.assets.get(id))
+            .map(size_of)
+            .sum()
+    }
+
+    /// Keeps an asset that is no longer used if this asset type has a budget. Returns false if the
+    /// asset should be freed right away.
+    pub(crate) fn keep_unused(&mut self, id: HandleId) -> bool {
+        if self.budget.is_none() || !self.assets.contains_key(&id) {
+            return false;
+        }
+        self.unused.retain(|unused| *unused != id);
+        self.unused.push_back(id);
+        true
+    }
+
+    /// Returns true if [`Assets::evict_unused`] would change the kept assets.
+    pub(crate) fn needs_eviction(&self, is_used: impl Fn(HandleId) -> bool) -> bool {
+        if self
+            .unused
+            .iter()
+            .any(|id| is_used(*id) || !self.assets.contains_key(id))
+        {
+            return true;
+        }
+        match (self.budget, self.size_of) {
+            (None, _) => !self.unused.is_empty(),
+            (Some(AssetBudget::Count(count)), _) => self.unused.len() > count,
+            (Some(AssetBudget::Bytes(bytes)), Some(size_of)) => self.unused_size(size_of) > bytes,
+            (Some(AssetBudget::Bytes(_)), None) => false,
+        }
+    }
+
+    /// Stops keeping assets that are used again and returns the least recently used assets that
+    /// exceed the budget, which should be freed.
+    pub(crate) fn evict_unused(&mut self, is_used: impl Fn(HandleId) -> bool) -> Vec<HandleId> {
+        let assets = &self.assets;
+        self.unused
+            .retain(|id| !is_used(*id) && assets.contains_key(id));
+
+        let mut evicted = Vec::new();
+        match (self.budget, self.size_of) {
+            (None, _) => evicted.extend(self.unused.drain(..)),
+            (Some(AssetBudget::Count(count)), _) => {
+                while self.unused.len() > count {
+                    evicted.extend(self.unused.pop_front());
+                }
+            }
+            (Some(AssetBudget::Bytes(bytes)), Some(size_of)) => {
+                let mut size = self.unused_size(size_of);
+                while size > bytes {
+                    let id = match self.unused.pop_front() {
+                        Some(id) => id,
+                        None => break,
+                    };
+                    size -= size_of(&self.assets[&id]);
+                    evicted.push(id);
+                }
+            }
+            (Some(AssetBudget::Bytes(_)), None) => {}
+        }
+        evicted
+    }
+
     pub fn is_empty(&self) -> bool {
         self.assets.is_empty()
     }
@@ -201,6 +346,18 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
 }
 
 impl AddAsset for App {
@@ -238,4 +395,42 @@ impl AddAsset for App {
             .add_loader(loader);
         self
     }
+
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_processor(result)
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .get_resource_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_processor(processor);
+        self
+    }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world
+            .get_resource_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_saver(saver);
+        self
+    }
 }
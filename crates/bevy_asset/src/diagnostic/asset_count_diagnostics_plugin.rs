@@ -2,8 +2,16 @@ use crate::{Asset, Assets};
 use bevy_app::prelude::*;
 use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
 use bevy_ecs::system::{Res, ResMut};
+use bevy_utils::{FixedState, Uuid};
+use std::hash::BuildHasher;
 
-/// Adds "asset count" diagnostic to an App
+/// The namespace that the ids of the per-type memory diagnostics are derived in
+const ASSET_DIAGNOSTICS_NAMESPACE: Uuid = Uuid::from_u128(0x3e6ba5c1_54f2_4d7a_9b0e_8f1c2d4a6b79);
+
+/// Adds "asset count" diagnostic to an App, along with "asset memory" and "unused asset memory"
+/// diagnostics for asset types that track their memory usage with
+/// [`Assets::track_memory`](crate::Assets::track_memory). The memory diagnostics are only added
+/// once the memory usage is tracked.
 pub struct AssetCountDiagnosticsPlugin<T: Asset> {
     marker: std::marker::PhantomData<T>,
 }
@@ -28,26 +36,77 @@ impl<T: Asset> AssetCountDiagnosticsPlugin<T> {
         DiagnosticId(T::TYPE_UUID)
     }
 
+    pub fn memory_diagnostic_id() -> DiagnosticId {
+        Self::derived_diagnostic_id("asset_memory")
+    }
+
+    pub fn unused_memory_diagnostic_id() -> DiagnosticId {
+        Self::derived_diagnostic_id("unused_asset_memory")
+    }
+
+    /// Derives the id of a diagnostic of this asset type by hashing its name together with the
+    /// type uuid in a namespace, so it does not collide with the uuid of another type.
+    fn derived_diagnostic_id(name: &str) -> DiagnosticId {
+        let hash = |half: u8| {
+            FixedState.hash_one((ASSET_DIAGNOSTICS_NAMESPACE, T::TYPE_UUID, name, half)) as u128
+        };
+        DiagnosticId(Uuid::from_u128(hash(0) << 64 | hash(1)))
+    }
+
     pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
-        let asset_type_name = std::any::type_name::<T>();
-        let max_length = MAX_DIAGNOSTIC_NAME_WIDTH - "asset_count ".len();
         diagnostics.add(Diagnostic::new(
             Self::diagnostic_id(),
-            format!(
-                "asset_count {}",
-                if asset_type_name.len() > max_length {
-                    asset_type_name
-                        .split_at(asset_type_name.len() - max_length + 1)
-                        .1
-                } else {
-                    asset_type_name
-                }
-            ),
+            Self::diagnostic_name("asset_count"),
             20,
         ));
     }
 
+    fn diagnostic_name(prefix: &str) -> String {
+        let asset_type_name = std::any::type_name::<T>();
+        let max_length = MAX_DIAGNOSTIC_NAME_WIDTH - prefix.len() - 1;
+        format!(
+            "{} {}",
+            prefix,
+            if asset_type_name.len() > max_length {
+                asset_type_name
+                    .split_at(asset_type_name.len() - max_length + 1)
+                    .1
+            } else {
+                asset_type_name
+            }
+        )
+    }
+
     pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, assets: Res<Assets<T>>) {
         diagnostics.add_measurement(Self::diagnostic_id(), assets.len() as f64);
+        if let Some(memory_usage) = assets.memory_usage() {
+            Self::add_memory_measurement(
+                &mut diagnostics,
+                Self::memory_diagnostic_id(),
+                "asset_memory",
+                memory_usage,
+            );
+        }
+        if let Some(unused_memory_usage) = assets.unused_memory_usage() {
+            Self::add_memory_measurement(
+                &mut diagnostics,
+                Self::unused_memory_diagnostic_id(),
+                "unused_asset_memory",
+                unused_memory_usage,
+            );
+        }
+    }
+
+    fn add_memory_measurement(
+        diagnostics: &mut Diagnostics,
+        id: DiagnosticId,
+        prefix: &str,
+        bytes: usize,
+    ) {
+        if diagnostics.get(id).is_none() {
+            diagnostics
+                .add(Diagnostic::new(id, Self::diagnostic_name(prefix), 20).with_suffix("B"));
+        }
+        diagnostics.add_measurement(id, bytes as f64);
     }
 }
@@ -1,12 +1,16 @@
+use bevy_utils::HashSet;
 use crossbeam_channel::Receiver;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Result, Watcher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Watches for changes to assets on the filesystem. This is used by the `AssetServer` to reload
 /// them
 pub struct FilesystemWatcher {
     pub watcher: RecommendedWatcher,
     pub receiver: Receiver<Result<Event>>,
+    watched_paths: HashSet<PathBuf>,
+    // files that did not exist when they were watched, which are watched through their folder
+    missing_files: HashSet<PathBuf>,
 }
 
 impl Default for FilesystemWatcher {
@@ -16,12 +20,47 @@ impl Default for FilesystemWatcher {
             sender.send(res).expect("Watch event send failure.");
         })
         .expect("Failed to create filesystem watcher.");
-        FilesystemWatcher { watcher, receiver }
+        FilesystemWatcher {
+            watcher,
+            receiver,
+            watched_paths: Default::default(),
+            missing_files: Default::default(),
+        }
     }
 }
 
 impl FilesystemWatcher {
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
-        self.watcher.watch(path.as_ref(), RecursiveMode::Recursive)
+        self.watcher
+            .watch(path.as_ref(), RecursiveMode::Recursive)?;
+        self.watched_paths.insert(path.as_ref().to_owned());
+        Ok(())
+    }
+
+    /// Watches a file that does not exist yet, so its creation is reported. The folder of the
+    /// file is watched for this, but changes to its other files are not reported by
+    /// [`FilesystemWatcher::is_watched`].
+    pub fn watch_missing_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !self
+                .missing_files
+                .iter()
+                .any(|file| file.parent() == Some(parent))
+            {
+                self.watcher.watch(parent, RecursiveMode::NonRecursive)?;
+            }
+        }
+        self.missing_files.insert(path.to_owned());
+        Ok(())
+    }
+
+    /// Returns `true` if changes to `path` were asked for, and not only reported because its
+    /// folder is watched for a missing file.
+    pub fn is_watched(&self, path: &Path) -> bool {
+        self.missing_files.contains(path)
+            || path
+                .ancestors()
+                .any(|ancestor| self.watched_paths.contains(ancestor))
     }
 }
@@ -0,0 +1,68 @@
+use crate::{Handle, HandleUntyped};
+use bevy_reflect::TypeUuid;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The assets in a folder, as loaded by [`AssetServer::load_live_folder`](crate::AssetServer::load_live_folder).
+///
+/// The folder is updated when files are created, renamed or deleted in it, which emits an
+/// [`AssetEvent::Modified`](crate::AssetEvent::Modified) for the folder and an [`AssetFolderEvent`]
+/// for each changed file. Added files are loaded like any other asset, and the handles of removed
+/// files are dropped, so their assets are eventually removed.
+#[derive(Debug, Default, TypeUuid)]
+#[uuid = "4e6f3a1b-5f0e-4a8c-9f64-8d2b2c1e7a90"]
+pub struct AssetFolder {
+    handles: BTreeMap<PathBuf, HandleUntyped>,
+}
+
+impl AssetFolder {
+    pub(crate) fn new(handles: BTreeMap<PathBuf, HandleUntyped>) -> Self {
+        AssetFolder { handles }
+    }
+
+    /// Returns the handle of the asset at `path`, which includes the folder path.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&HandleUntyped> {
+        self.handles.get(path.as_ref())
+    }
+
+    /// The paths of the assets in this folder and its subfolders, in sorted order
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.handles.keys().map(|path| path.as_path())
+    }
+
+    /// The handles of the assets in this folder and its subfolders, sorted by path
+    pub fn handles(&self) -> impl Iterator<Item = &HandleUntyped> {
+        self.handles.values()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &HandleUntyped)> {
+        self.handles
+            .iter()
+            .map(|(path, handle)| (path.as_path(), handle))
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+/// An event that is sent for each file that is created or removed in a live folder, including the
+/// files found when the folder is first loaded. Renamed files are reported as removed at their old
+/// path and created at their new path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetFolderEvent {
+    Created {
+        folder: Handle<AssetFolder>,
+        path: PathBuf,
+    },
+    Removed {
+        folder: Handle<AssetFolder>,
+        path: PathBuf,
+    },
+}
@@ -7,7 +7,7 @@ use std::{
 
 use crate::{
     path::{AssetPath, AssetPathId},
-    Asset, Assets,
+    Asset, Assets, ReflectHandle,
 };
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_reflect::{Reflect, ReflectDeserialize};
@@ -59,7 +59,7 @@ impl HandleId {
 /// Handles contain a unique id that corresponds to a specific asset in the [Assets](crate::Assets)
 /// collection.
 #[derive(Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Handle)]
 pub struct Handle<T>
 where
     T: Asset,
@@ -1,11 +1,15 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{path::AssetPath, AssetLoaderSettings, LabelId};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceMeta {
     pub assets: Vec<AssetMeta>,
+    /// The paths read or loaded directly by the loader, see
+    /// [`LoadContext::read_asset_bytes`](crate::LoadContext::read_asset_bytes)
+    #[serde(default)]
+    pub loader_dependencies: Vec<PathBuf>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -13,6 +17,9 @@ pub struct AssetMeta {
     pub label: Option<String>,
     pub dependencies: Vec<AssetPath<'static>>,
     pub type_uuid: Uuid,
+    /// The loader settings read from the asset's `.meta` file
+    #[serde(skip)]
+    pub settings: Option<Arc<dyn AssetLoaderSettings>>,
 }
 
 /// Info about a specific asset, such as its path and its current load state
@@ -24,6 +31,10 @@ pub struct SourceInfo {
     pub load_state: LoadState,
     pub committed_assets: HashSet<LabelId>,
     pub version: usize,
+    /// The number of bytes read from the asset source so far
+    pub bytes_read: u64,
+    /// The size of the asset source, if it is known
+    pub total_bytes: Option<u64>,
 }
 
 impl SourceInfo {
@@ -53,3 +64,49 @@ pub enum LoadState {
     /// the asset was removed from the [`Assets`](crate::Assets) collection.
     Unloaded,
 }
+
+/// The progress of loading a group of assets, or all assets of an
+/// [`AssetServer`](crate::AssetServer).
+///
+/// Counts are per source file. The byte totals only include files whose size is known, which is
+/// the case once they are read, or before that if their [`AssetIo`](crate::AssetIo) reports
+/// file sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Files that were requested, but did not start loading yet
+    pub queued: usize,
+    pub loading: usize,
+    pub loaded: usize,
+    pub failed: usize,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+}
+
+impl LoadProgress {
+    /// The number of files that are done loading, successfully or not
+    pub fn finished(&self) -> usize {
+        self.loaded + self.failed
+    }
+
+    /// The number of files that were requested
+    pub fn total(&self) -> usize {
+        self.queued + self.loading + self.finished()
+    }
+
+    /// The fraction of files that are done loading, between 0 and 1. This is 1 if no files
+    /// were requested.
+    pub fn fraction(&self) -> f32 {
+        match self.total() {
+            0 => 1.0,
+            total => self.finished() as f32 / total as f32,
+        }
+    }
+
+    /// The fraction of the known bytes that were read, between 0 and 1
+    pub fn bytes_fraction(&self) -> f32 {
+        match self.total_bytes {
+            0 => 1.0,
+            total_bytes => self.bytes_read as f32 / total_bytes as f32,
+        }
+    }
+}
@@ -0,0 +1,567 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeSet,
+    convert::TryFrom,
+    fs::{self, File},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// A read-only collection of files packed into a single archive
+pub trait AssetArchive: Send + Sync + 'static {
+    /// The paths of all files in the archive, relative to the archive root.
+    fn paths(&self) -> Box<dyn Iterator<Item = &Path> + '_>;
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetIoError>;
+}
+
+const PAK_MAGIC: &[u8; 4] = b"BPAK";
+const PAK_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy)]
+struct PakEntry {
+    offset: u64,
+    size: u64,
+}
+
+/// An uncompressed archive with an index of all files at its start.
+///
+/// The layout is the magic bytes `BPAK`, a little-endian `u32` version, a `u32` entry count and
+/// then for each entry a `u32` path length, the UTF-8 path with `/` separators, and the `u64`
+/// offset and size of the file data. Use [`PakArchive::build`] to create one from a directory.
+pub struct PakArchive {
+    file: Mutex<File>,
+    entries: HashMap<PathBuf, PakEntry>,
+}
+
+impl PakArchive {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let file = File::open(path.as_ref())?;
+        let file_len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != PAK_MAGIC || read_u32(&mut reader)? != PAK_VERSION {
+            return Err(invalid_data("not a supported pak archive").into());
+        }
+
+        let entry_count = read_u32(&mut reader)?;
+        let mut entries = HashMap::default();
+        // lengths from the index are checked against the archive before anything is allocated
+        // for them, so a corrupt archive fails to open instead of exhausting memory
+        let mut position = PAK_MAGIC.len() as u64 + 8;
+        for _ in 0..entry_count {
+            let path_len = u64::from(read_u32(&mut reader)?);
+            position += 4;
+            if path_len > file_len.saturating_sub(position) {
+                return Err(invalid_data("entry path exceeds the pak archive").into());
+            }
+            let mut path = vec![0; path_len as usize];
+            reader.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| invalid_data("invalid entry path"))?;
+            let offset = read_u64(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            position += path_len + 16;
+            if offset > file_len || size > file_len - offset {
+                return Err(invalid_data("entry data exceeds the pak archive").into());
+            }
+            entries.insert(PathBuf::from(path), PakEntry { offset, size });
+        }
+
+        Ok(PakArchive {
+            file: Mutex::new(reader.into_inner()),
+            entries,
+        })
+    }
+
+    /// Packs every file in `directory` and its subdirectories into a pak archive at `output`.
+    pub fn build<P: AsRef<Path>, Q: AsRef<Path>>(directory: P, output: Q) -> Result<(), io::Error> {
+        let directory = directory.as_ref();
+        let mut files = Vec::new();
+        collect_files(directory, directory, &mut files)?;
+        files.sort();
+
+        let mut paths = Vec::with_capacity(files.len());
+        for file in files.iter() {
+            let path =
+                archive_path(file).ok_or_else(|| invalid_data("file path is not valid UTF-8"))?;
+            paths.push(path);
+        }
+
+        let mut sizes = Vec::with_capacity(files.len());
+        for file in files.iter() {
+            sizes.push(fs::metadata(directory.join(file))?.len());
+        }
+
+        let index_len: u64 = paths.iter().map(|path| 4 + path.len() as u64 + 16).sum();
+        let mut offset = PAK_MAGIC.len() as u64 + 8 + index_len;
+
+        let mut writer = io::BufWriter::new(File::create(output.as_ref())?);
+        writer.write_all(PAK_MAGIC)?;
+        writer.write_all(&PAK_VERSION.to_le_bytes())?;
+        writer.write_all(&len_u32(files.len())?.to_le_bytes())?;
+        for (path, size) in paths.iter().zip(sizes.iter()) {
+            writer.write_all(&len_u32(path.len())?.to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&size.to_le_bytes())?;
+            offset += size;
+        }
+        for (file, &size) in files.iter().zip(sizes.iter()) {
+            let copied = io::copy(&mut File::open(directory.join(file))?, &mut writer)?;
+            if copied != size {
+                return Err(invalid_data("file changed while building the pak archive"));
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+impl AssetArchive for PakArchive {
+    fn paths(&self) -> Box<dyn Iterator<Item = &Path> + '_> {
+        Box::new(self.entries.keys().map(|path| path.as_path()))
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let entry = archive_path(path)
+            .and_then(|path| self.entries.get(Path::new(&path)))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        let size = usize::try_from(entry.size).map_err(|_| invalid_data("entry is too large"))?;
+        let mut bytes = vec![0; size];
+        let mut file = self.file.lock();
+        file.seek(SeekFrom::Start(entry.offset))?;
+        file.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// A zip archive. Compressed entries are decompressed when they are read.
+#[cfg(feature = "zip")]
+pub struct ZipArchive {
+    archive: Mutex<zip::ZipArchive<File>>,
+    paths: Vec<PathBuf>,
+}
+
+#[cfg(feature = "zip")]
+impl ZipArchive {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let archive = zip::ZipArchive::new(File::open(path.as_ref())?).map_err(zip_error)?;
+        let paths = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .map(PathBuf::from)
+            .collect();
+        Ok(ZipArchive {
+            archive: Mutex::new(archive),
+            paths,
+        })
+    }
+}
+
+#[cfg(feature = "zip")]
+impl AssetArchive for ZipArchive {
+    fn paths(&self) -> Box<dyn Iterator<Item = &Path> + '_> {
+        Box::new(self.paths.iter().map(|path| path.as_path()))
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let name = archive_path(path).ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        let mut archive = self.archive.lock();
+        let mut file = match archive.by_name(&name) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => {
+                return Err(AssetIoError::NotFound(path.to_owned()))
+            }
+            Err(err) => return Err(zip_error(err)),
+        };
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "zip")]
+fn zip_error(err: zip::result::ZipError) -> AssetIoError {
+    match err {
+        zip::result::ZipError::Io(err) => err.into(),
+        err => invalid_data(&err.to_string()).into(),
+    }
+}
+
+/// Serves assets from one or more [`AssetArchive`]s.
+///
+/// Archives are searched in the order they were added, so files in earlier archives shadow files
+/// with the same path in later ones. Paths that are not in any archive are loaded from the
+/// optional fallback `AssetIo`, which makes it possible to ship packed assets while still loading
+/// loose files from a [`FileAssetIo`](crate::FileAssetIo) during development. Watching for changes
+/// and writes are delegated to the fallback.
+#[derive(Default)]
+pub struct ArchiveAssetIo {
+    archives: Vec<Box<dyn AssetArchive>>,
+    files: HashMap<PathBuf, usize>,
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    fallback: Option<Box<dyn AssetIo>>,
+}
+
+impl ArchiveAssetIo {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_fallback(fallback: Box<dyn AssetIo>) -> Self {
+        ArchiveAssetIo {
+            fallback: Some(fallback),
+            ..Default::default()
+        }
+    }
+
+    pub fn add_archive<A: AssetArchive>(&mut self, archive: A) {
+        let archive_index = self.archives.len();
+        for path in archive.paths().filter_map(normalize) {
+            if self.files.contains_key(&path) {
+                continue;
+            }
+            self.files.insert(path.clone(), archive_index);
+
+            // register the file with each of its ancestor directories
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                self.directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                child = parent;
+            }
+        }
+        self.archives.push(Box::new(archive));
+    }
+
+    pub fn with_archive<A: AssetArchive>(mut self, archive: A) -> Self {
+        self.add_archive(archive);
+        self
+    }
+
+    /// Returns the index of the archive that serves `path`, if any archive contains it.
+    fn archive_index(&self, path: &Path) -> Option<usize> {
+        normalize(path).and_then(|path| self.files.get(&path).copied())
+    }
+}
+
+impl AssetIo for ArchiveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            match self.archive_index(path) {
+                Some(archive_index) => self.archives[archive_index].read(path),
+                None => match &self.fallback {
+                    Some(fallback) => fallback.load_path(path).await,
+                    None => Err(AssetIoError::NotFound(path.to_owned())),
+                },
+            }
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children: BTreeSet<PathBuf> = normalize(path)
+            .and_then(|path| self.directories.get(&path))
+            .cloned()
+            .unwrap_or_default();
+        let fallback_children = match &self.fallback {
+            Some(fallback) if fallback.is_directory(path) => Some(fallback.read_directory(path)?),
+            _ => None,
+        };
+        if fallback_children.is_none() && children.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        let known: HashSet<PathBuf> = children.iter().cloned().collect();
+        for child in fallback_children.into_iter().flatten() {
+            if !matches!(normalize(&child), Some(normalized) if known.contains(&normalized)) {
+                children.insert(child);
+            }
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        matches!(normalize(path), Some(path) if self.directories.contains_key(&path))
+            || matches!(&self.fallback, Some(fallback) if fallback.is_directory(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        match &self.fallback {
+            Some(fallback) if self.archive_index(path).is_none() => {
+                fallback.watch_path_for_changes(path)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        match &self.fallback {
+            Some(fallback) => fallback.watch_for_changes(),
+            None => Ok(()),
+        }
+    }
+
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        match &self.fallback {
+            Some(fallback) if self.archive_index(path).is_none() => fallback.file_size(path),
+            _ => None,
+        }
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            // archives are read-only, and a written loose file would be shadowed by an archived one
+            match &self.fallback {
+                Some(fallback) if self.archive_index(path).is_none() => {
+                    fallback.write_path(path, bytes).await
+                }
+                _ => Err(AssetIoError::ReadOnly(path.to_owned())),
+            }
+        })
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        match &self.fallback {
+            Some(fallback) => fallback.changed_paths(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Converts a relative path into the form used for archive lookups, resolving `.` components.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            Component::Prefix(_) | Component::RootDir => return None,
+        }
+    }
+    Some(normalized)
+}
+
+/// Converts a relative path into a `/` separated archive entry name.
+fn archive_path(path: &Path) -> Option<String> {
+    let path = normalize(path)?;
+    let parts = path
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("/"))
+}
+
+fn collect_files(root: &Path, directory: &Path, files: &mut Vec<PathBuf>) -> Result<(), io::Error> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            files.push(path.strip_prefix(root).unwrap().to_owned());
+        }
+    }
+    Ok(())
+}
+
+fn len_u32(len: usize) -> Result<u32, io::Error> {
+    u32::try_from(len).map_err(|_| invalid_data("pak archives are limited to u32::MAX entries"))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, io::Error> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, io::Error> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pak_round_trip() {
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir_all(source.path().join("textures/ui")).unwrap();
+        fs::write(source.path().join("scene.scn"), b"scene").unwrap();
+        fs::write(source.path().join("textures/grass.png"), b"grass").unwrap();
+        fs::write(source.path().join("textures/ui/button.png"), b"button").unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        let pak_path = output.path().join("assets.pak");
+        PakArchive::build(source.path(), &pak_path).unwrap();
+
+        let asset_io = ArchiveAssetIo::new().with_archive(PakArchive::open(&pak_path).unwrap());
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("scene.scn").unwrap(), b"scene");
+        assert_eq!(load("textures/grass.png").unwrap(), b"grass");
+        assert_eq!(load("./textures/ui/button.png").unwrap(), b"button");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_directory(Path::new("textures")));
+        assert!(asset_io.is_directory(Path::new("textures/ui")));
+        assert!(!asset_io.is_directory(Path::new("scene.scn")));
+        let children = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+    }
+
+    #[test]
+    fn pak_corrupt_lengths() {
+        let output = tempfile::tempdir().unwrap();
+        let pak_path = output.path().join("corrupt.pak");
+        let header = |entry: &[u8]| {
+            let mut bytes = PAK_MAGIC.to_vec();
+            bytes.extend_from_slice(&PAK_VERSION.to_le_bytes());
+            bytes.extend_from_slice(&1u32.to_le_bytes());
+            bytes.extend_from_slice(entry);
+            bytes
+        };
+        let is_invalid_data = |result: Result<PakArchive, AssetIoError>| matches!(result, Err(AssetIoError::Io(err)) if err.kind() == io::ErrorKind::InvalidData);
+
+        // a path length far beyond the end of the file
+        fs::write(&pak_path, header(&u32::MAX.to_le_bytes())).unwrap();
+        assert!(is_invalid_data(PakArchive::open(&pak_path)));
+
+        // an entry whose data would end past the end of the file
+        let mut entry = 1u32.to_le_bytes().to_vec();
+        entry.push(b'a');
+        entry.extend_from_slice(&0u64.to_le_bytes());
+        entry.extend_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(&pak_path, header(&entry)).unwrap();
+        assert!(is_invalid_data(PakArchive::open(&pak_path)));
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn zip_archive() {
+        let output = tempfile::tempdir().unwrap();
+        let zip_path = output.path().join("assets.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer
+            .add_directory("textures/", zip::write::FileOptions::default())
+            .unwrap();
+        writer
+            .start_file("textures/grass.png", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"grass").unwrap();
+        writer
+            .start_file(
+                "scene.scn",
+                zip::write::FileOptions::default()
+                    .compression_method(zip::CompressionMethod::Stored),
+            )
+            .unwrap();
+        writer.write_all(b"scene").unwrap();
+        writer.finish().unwrap();
+
+        let archive = ZipArchive::open(&zip_path).unwrap();
+        let mut paths = archive.paths().map(Path::to_owned).collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("scene.scn"),
+                PathBuf::from("textures/grass.png")
+            ]
+        );
+
+        let asset_io = ArchiveAssetIo::new().with_archive(archive);
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("textures/grass.png").unwrap(), b"grass");
+        assert_eq!(load("scene.scn").unwrap(), b"scene");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(asset_io.is_directory(Path::new("textures")));
+    }
+
+    #[test]
+    fn fallback_to_loose_files() {
+        use crate::FileAssetIo;
+
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir_all(source.path().join("textures")).unwrap();
+        fs::write(source.path().join("textures/grass.png"), b"packed grass").unwrap();
+        let output = tempfile::tempdir().unwrap();
+        let pak_path = output.path().join("assets.pak");
+        PakArchive::build(source.path(), &pak_path).unwrap();
+
+        let loose = tempfile::tempdir().unwrap();
+        fs::create_dir_all(loose.path().join("textures")).unwrap();
+        fs::write(loose.path().join("textures/grass.png"), b"loose grass").unwrap();
+        fs::write(loose.path().join("textures/dirt.png"), b"loose dirt").unwrap();
+
+        let asset_io = ArchiveAssetIo::with_fallback(Box::new(FileAssetIo::new(loose.path())))
+            .with_archive(PakArchive::open(&pak_path).unwrap());
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+
+        // archived files shadow loose ones, other paths are loaded from the fallback
+        assert_eq!(load("textures/grass.png").unwrap(), b"packed grass");
+        assert_eq!(load("textures/dirt.png").unwrap(), b"loose dirt");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        let children = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/dirt.png"),
+                PathBuf::from("textures/grass.png")
+            ]
+        );
+
+        // writes go to the fallback, except for paths served by an archive
+        futures_lite::future::block_on(asset_io.write_path(Path::new("textures/new.png"), b"new"))
+            .unwrap();
+        assert_eq!(load("textures/new.png").unwrap(), b"new");
+        assert!(matches!(
+            futures_lite::future::block_on(
+                asset_io.write_path(Path::new("textures/grass.png"), b"new"),
+            ),
+            Err(AssetIoError::ReadOnly(_))
+        ));
+    }
+}
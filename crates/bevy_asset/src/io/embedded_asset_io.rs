@@ -0,0 +1,153 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the source that serves [`EmbeddedAssets`], as in `embedded://shaders/sprite.vert`.
+pub const EMBEDDED_SOURCE: &str = "embedded";
+
+/// A registry of assets that are compiled into the binary, usually with [`embedded_asset!`].
+///
+/// The registry is shared between all of its clones, so assets can be registered after the
+/// [`EmbeddedAssetIo`] serving them was created. Registering a path again replaces the previous
+/// bytes, which allows overriding embedded engine assets, and reloads the asset if it is watched.
+/// Inserting and removing paths also updates the live folders that contain them.
+#[derive(Clone, Default)]
+pub struct EmbeddedAssets {
+    assets: Arc<RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>>,
+    watched: Arc<Mutex<HashSet<PathBuf>>>,
+    changed: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl EmbeddedAssets {
+    pub fn insert<P: Into<PathBuf>, B: Into<Cow<'static, [u8]>>>(&self, path: P, bytes: B) {
+        let path = path.into();
+        self.assets.write().insert(path.clone(), bytes.into());
+        self.record_change(path);
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<Cow<'static, [u8]>> {
+        let bytes = self.assets.write().remove(path);
+        if bytes.is_some() {
+            self.record_change(path.to_owned());
+        }
+        bytes
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.assets.read().contains_key(path)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Cow<'static, [u8]>> {
+        self.assets.read().get(path).cloned()
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.assets.read().keys().cloned().collect()
+    }
+
+    // changes are reported for watched paths and for paths in watched folders
+    fn record_change(&self, path: PathBuf) {
+        let is_watched = self
+            .watched
+            .lock()
+            .iter()
+            .any(|watched| path.starts_with(watched));
+        if is_watched {
+            self.changed.lock().push(path);
+        }
+    }
+}
+
+/// Embeds a file into the binary with `include_bytes!` and registers it with the
+/// [`EmbeddedAssets`] of an `App`, so it can be loaded from `embedded://<path>`.
+///
+/// The file path is relative to the current source file. By default it is also used as the
+/// embedded asset path, but a different asset path can be passed before it:
+///
+/// ```ignore
+/// embedded_asset!(app, "shaders/outline.frag");
+/// embedded_asset!(app, "ui/fonts/mono.ttf", "../assets/FiraMono-Medium.ttf");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: expr, $path: expr) => {
+        $crate::embedded_asset!($app, $path, $path)
+    };
+    ($app: expr, $asset_path: expr, $file_path: expr) => {
+        $app.world
+            .get_resource_or_insert_with($crate::EmbeddedAssets::default)
+            .insert($asset_path, include_bytes!($file_path) as &'static [u8])
+    };
+}
+
+/// Serves the assets registered in [`EmbeddedAssets`]
+pub struct EmbeddedAssetIo {
+    assets: EmbeddedAssets,
+}
+
+impl EmbeddedAssetIo {
+    pub fn new(assets: EmbeddedAssets) -> Self {
+        EmbeddedAssetIo { assets }
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.assets
+                .get(path)
+                .map(|bytes| bytes.into_owned())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = HashSet::default();
+        for asset_path in self.assets.assets.read().keys() {
+            if let Ok(relative_path) = asset_path.strip_prefix(path) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(path.join(child));
+                }
+            }
+        }
+
+        if children.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.assets
+            .assets
+            .read()
+            .keys()
+            .any(|asset_path| asset_path != path && asset_path.starts_with(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.assets.watched.lock().insert(path.to_owned());
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        self.assets.get(path).map(|bytes| bytes.len() as u64)
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        std::mem::take(&mut *self.assets.changed.lock())
+    }
+}
@@ -1,7 +1,6 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError};
 use anyhow::Result;
-use bevy_ecs::system::Res;
-use bevy_utils::{BoxedFuture, HashSet};
+use bevy_utils::BoxedFuture;
 use crossbeam_channel::TryRecvError;
 use fs::File;
 use io::Read;
@@ -81,9 +80,14 @@ impl AssetIo for FileAssetIo {
             let path = self.root_path.join(path);
             let mut watcher = self.filesystem_watcher.write();
             if let Some(ref mut watcher) = *watcher {
-                watcher
-                    .watch(&path)
-                    .map_err(|_error| AssetIoError::PathWatchError(path))?;
+                // files that do not exist yet are watched through their folder, which reports
+                // their creation
+                let result = if path.exists() {
+                    watcher.watch(&path)
+                } else {
+                    watcher.watch_missing_file(&path)
+                };
+                result.map_err(|_error| AssetIoError::PathWatchError(path))?;
             }
         }
 
@@ -102,42 +106,63 @@ impl AssetIo for FileAssetIo {
     fn is_directory(&self, path: &Path) -> bool {
         self.root_path.join(path).is_dir()
     }
-}
 
-#[cfg(all(
-    feature = "filesystem_watcher",
-    all(not(target_arch = "wasm32"), not(target_os = "android"))
-))]
-pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
-    let watcher = asset_io.filesystem_watcher.read();
-    if let Some(ref watcher) = *watcher {
-        loop {
-            let event = match watcher.receiver.try_recv() {
-                Ok(result) => result.unwrap(),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
-            };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in paths.iter() {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        fs::metadata(self.root_path.join(path))
+            .ok()
+            .map(|metadata| metadata.len())
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            let watcher = self.filesystem_watcher.read();
+            if let Some(ref watcher) = *watcher {
+                loop {
+                    let event = match watcher.receiver.try_recv() {
+                        Ok(result) => result.unwrap(),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            panic!("FilesystemWatcher disconnected.")
+                        }
+                    };
+                    // created and removed files change the watched folders that contain them
+                    if let notify::event::Event {
+                        kind:
+                            notify::event::EventKind::Create(_)
+                            | notify::event::EventKind::Modify(_)
+                            | notify::event::EventKind::Remove(_),
+                        paths,
+                        ..
+                    } = event
+                    {
+                        for path in paths.iter().filter(|path| watcher.is_watched(path)) {
+                            let relative_path = path.strip_prefix(&self.root_path).unwrap();
+                            if !changed.iter().any(|changed| changed == relative_path) {
+                                changed.push(relative_path.to_owned());
+                            }
+                        }
                     }
                 }
-                changed.extend(paths);
             }
         }
+
+        changed
     }
 }
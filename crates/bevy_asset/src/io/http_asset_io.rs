@@ -0,0 +1,535 @@
+use crate::{processor::hash_bytes, AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_log::warn;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::Mutex;
+use std::{
+    fs,
+    io::{self, Read},
+    panic::{self, AssertUnwindSafe},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Fetches assets over HTTP from a base URL, like `https://cdn.example.com/assets`.
+///
+/// Responses are revalidated with their `ETag` and `Last-Modified` headers. With a cache folder,
+/// downloaded files are kept on disk, so unchanged files are not downloaded again across runs and
+/// cached files are served when the server is unreachable. HTTP has no change notifications, so
+/// watching for changes polls the watched files in a background thread.
+///
+/// Directories cannot be listed over HTTP, so [`AssetServer::load_folder`](crate::AssetServer::load_folder)
+/// is not supported.
+pub struct HttpAssetIo {
+    state: Arc<HttpState>,
+    requests: RequestPool,
+    poll_interval: Duration,
+}
+
+struct HttpState {
+    base_url: String,
+    agent: ureq::Agent,
+    cache_folder: Option<PathBuf>,
+    // the validators of the last response for each path
+    validators: Mutex<HashMap<PathBuf, Validators>>,
+    watched: Mutex<HashSet<PathBuf>>,
+    changed: Mutex<Vec<PathBuf>>,
+    polling: AtomicBool,
+}
+
+/// What identifies a version of a remote file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    hash: u64,
+}
+
+enum Fetched {
+    Modified(Vec<u8>, Validators),
+    NotModified,
+}
+
+impl HttpAssetIo {
+    pub fn new<S: Into<String>>(base_url: S) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        HttpAssetIo {
+            state: Arc::new(HttpState {
+                base_url,
+                agent: ureq::AgentBuilder::new()
+                    .timeout(Duration::from_secs(30))
+                    .build(),
+                cache_folder: None,
+                validators: Default::default(),
+                watched: Default::default(),
+                changed: Default::default(),
+                polling: AtomicBool::new(false),
+            }),
+            requests: RequestPool::new(DEFAULT_REQUEST_THREADS),
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+
+    /// Keeps downloaded files in `cache_folder`, which is created if it does not exist.
+    pub fn with_cache_folder<P: Into<PathBuf>>(mut self, cache_folder: P) -> Self {
+        Arc::get_mut(&mut self.state)
+            .expect("`HttpAssetIo` should be configured before watching for changes.")
+            .cache_folder = Some(cache_folder.into());
+        self
+    }
+
+    /// Sets how often watched files are checked for changes. The default is two seconds.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how many requests are made at the same time. The default is four.
+    pub fn with_request_threads(mut self, request_threads: usize) -> Self {
+        self.requests = RequestPool::new(request_threads.max(1));
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.state.base_url
+    }
+
+    pub fn cache_folder(&self) -> Option<&Path> {
+        self.state.cache_folder.as_deref()
+    }
+}
+
+impl HttpState {
+    fn url(&self, path: &Path) -> String {
+        let path = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{}/{}", self.base_url, path)
+    }
+
+    /// Returns the paths of the cached body and validators of `path`. Absolute paths and paths
+    /// with `..` components could point outside of the cache folder, so they are never cached.
+    fn cache_paths(&self, path: &Path) -> Option<(PathBuf, PathBuf)> {
+        let cache_folder = self.cache_folder.as_ref()?;
+        let is_contained = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !is_contained {
+            return None;
+        }
+        let body_path = cache_folder.join(path);
+        let mut meta_path = body_path.as_os_str().to_owned();
+        meta_path.push(".http");
+        Some((body_path, PathBuf::from(meta_path)))
+    }
+
+    fn read_cache(&self, path: &Path) -> Option<(Vec<u8>, Validators)> {
+        let (body_path, meta_path) = self.cache_paths(path)?;
+        let bytes = fs::read(body_path).ok()?;
+        let meta = fs::read_to_string(meta_path).ok()?;
+        let mut validators = Validators {
+            hash: hash_bytes(&bytes),
+            ..Default::default()
+        };
+        for line in meta.lines() {
+            if let Some(etag) = line.strip_prefix("etag: ") {
+                validators.etag = Some(etag.to_string());
+            } else if let Some(last_modified) = line.strip_prefix("last-modified: ") {
+                validators.last_modified = Some(last_modified.to_string());
+            }
+        }
+        Some((bytes, validators))
+    }
+
+    /// Reads the cached body of `path` that was validated with `known`, which are kept in memory.
+    fn read_cached_body(
+        &self,
+        path: &Path,
+        known: Option<Validators>,
+    ) -> Option<(Vec<u8>, Validators)> {
+        let (body_path, _) = self.cache_paths(path)?;
+        Some((fs::read(body_path).ok()?, known?))
+    }
+
+    fn write_cache(&self, path: &Path, bytes: &[u8], validators: &Validators) -> io::Result<()> {
+        let (body_path, meta_path) = match self.cache_paths(path) {
+            Some(paths) => paths,
+            None => return Ok(()),
+        };
+        if let Some(parent) = body_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut meta = String::new();
+        if let Some(etag) = &validators.etag {
+            meta.push_str(&format!("etag: {}\n", etag));
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            meta.push_str(&format!("last-modified: {}\n", last_modified));
+        }
+        fs::write(body_path, bytes)?;
+        fs::write(meta_path, meta)
+    }
+
+    /// Requests the file at `path`, only downloading it if it differs from `known`.
+    fn fetch(&self, path: &Path, known: Option<&Validators>) -> Result<Fetched, AssetIoError> {
+        let mut request = self.agent.get(&self.url(path));
+        if let Some(known) = known {
+            if let Some(etag) = &known.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &known.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) | Err(ureq::Error::Status(410, _)) => {
+                return Err(AssetIoError::NotFound(path.to_owned()))
+            }
+            Err(err) => return Err(AssetIoError::RemoteRequestFailed(err.to_string())),
+        };
+        if response.status() == 304 {
+            return Ok(Fetched::NotModified);
+        }
+
+        let etag = response.header("ETag").map(|etag| etag.to_string());
+        let last_modified = response
+            .header("Last-Modified")
+            .map(|last_modified| last_modified.to_string());
+        let mut bytes = Vec::new();
+        response.into_reader().read_to_end(&mut bytes)?;
+        let validators = Validators {
+            etag,
+            last_modified,
+            hash: hash_bytes(&bytes),
+        };
+
+        // servers without validators always respond with the full file
+        if known.map(|known| known.hash) == Some(validators.hash) {
+            return Ok(Fetched::NotModified);
+        }
+        Ok(Fetched::Modified(bytes, validators))
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        // the validators of earlier responses are kept in memory, so the cache folder is only
+        // read for the first load of a path and when the server has no newer file. Without a
+        // cache folder there is no file to revalidate.
+        let mut cached = None;
+        let known = if self.cache_paths(path).is_none() {
+            None
+        } else {
+            let known = self.validators.lock().get(path).cloned();
+            known.or_else(|| {
+                cached = self.read_cache(path);
+                cached.as_ref().map(|(_, validators)| validators.clone())
+            })
+        };
+
+        match self.fetch(path, known.as_ref()) {
+            Ok(Fetched::Modified(bytes, validators)) => {
+                if let Err(err) = self.write_cache(path, &bytes, &validators) {
+                    warn!("Failed to cache {:?}: {}", path, err);
+                }
+                self.validators.lock().insert(path.to_owned(), validators);
+                Ok(bytes)
+            }
+            Ok(Fetched::NotModified) => match cached.or_else(|| self.read_cached_body(path, known))
+            {
+                Some((bytes, validators)) => {
+                    self.validators.lock().insert(path.to_owned(), validators);
+                    Ok(bytes)
+                }
+                // the cached file was removed since it was validated, so it is downloaded again
+                None => {
+                    self.validators.lock().remove(path);
+                    self.load(path)
+                }
+            },
+            Err(AssetIoError::NotFound(path)) => Err(AssetIoError::NotFound(path)),
+            // fall back to the cached file if the server is unreachable
+            Err(err) => match cached.or_else(|| self.read_cached_body(path, known)) {
+                Some((bytes, validators)) => {
+                    warn!("Failed to fetch {:?}, using the cached file: {}", path, err);
+                    self.validators.lock().insert(path.to_owned(), validators);
+                    Ok(bytes)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Checks the watched files for changes, which are reported by `changed_paths`.
+    fn poll(&self) {
+        let watched = self.watched.lock().iter().cloned().collect::<Vec<_>>();
+        for path in watched {
+            let known = self.validators.lock().get(&path).cloned();
+            match self.fetch(&path, known.as_ref()) {
+                Ok(Fetched::Modified(bytes, validators)) => {
+                    if let Err(err) = self.write_cache(&path, &bytes, &validators) {
+                        warn!("Failed to cache {:?}: {}", path, err);
+                    }
+                    self.validators.lock().insert(path.clone(), validators);
+                    self.changed.lock().push(path);
+                }
+                Ok(Fetched::NotModified) => {}
+                Err(AssetIoError::NotFound(_)) if known.is_some() => {
+                    self.validators.lock().remove(&path);
+                    self.changed.lock().push(path);
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+impl AssetIo for HttpAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        let state = self.state.clone();
+        let path = path.to_owned();
+        Box::pin(self.requests.run(move || state.load(&path)))
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn is_directory(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.state.watched.lock().insert(path.to_owned());
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        if self.state.polling.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        // the thread stops once the `HttpAssetIo` is dropped
+        let state: Weak<HttpState> = Arc::downgrade(&self.state);
+        let poll_interval = self.poll_interval;
+        thread::Builder::new()
+            .name("http asset watcher".to_string())
+            .spawn(move || loop {
+                thread::sleep(poll_interval);
+                match state.upgrade() {
+                    Some(state) => state.poll(),
+                    None => break,
+                }
+            })?;
+        Ok(())
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        std::mem::take(&mut *self.state.changed.lock())
+    }
+}
+
+const DEFAULT_REQUEST_THREADS: usize = 4;
+
+type Request = Box<dyn FnOnce() + Send>;
+
+/// A fixed number of threads that make the blocking requests, so waiting for the server does not
+/// block the IO task pool. Requests beyond the number of threads wait for a free thread.
+struct RequestPool {
+    thread_count: usize,
+    // started by the first request; the threads stop once the sender is dropped
+    sender: Mutex<Option<crossbeam_channel::Sender<Request>>>,
+}
+
+impl RequestPool {
+    fn new(thread_count: usize) -> Self {
+        RequestPool {
+            thread_count,
+            sender: Default::default(),
+        }
+    }
+
+    fn sender(&self) -> Result<crossbeam_channel::Sender<Request>, AssetIoError> {
+        let mut sender = self.sender.lock();
+        if let Some(sender) = &*sender {
+            return Ok(sender.clone());
+        }
+
+        let (new_sender, receiver) = crossbeam_channel::unbounded::<Request>();
+        for index in 0..self.thread_count {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("http asset request {}", index))
+                .spawn(move || {
+                    for request in receiver.iter() {
+                        // a panicking request drops its result sender, which fails only that
+                        // request and keeps the thread
+                        let _ = panic::catch_unwind(AssertUnwindSafe(request));
+                    }
+                })?;
+        }
+        *sender = Some(new_sender.clone());
+        Ok(new_sender)
+    }
+
+    async fn run<T, F>(&self, request: F) -> Result<T, AssetIoError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, AssetIoError> + Send + 'static,
+    {
+        let (result_sender, result_receiver) = async_channel::bounded(1);
+        let request: Request = Box::new(move || {
+            let _ = result_sender.try_send(request());
+        });
+        self.sender()?.send(request).map_err(|_| {
+            AssetIoError::RemoteRequestFailed("the request threads stopped".to_string())
+        })?;
+        result_receiver
+            .recv()
+            .await
+            .map_err(|_| AssetIoError::RemoteRequestFailed("the request panicked".to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// A file served by the test server, along with the number of full responses sent for it
+    #[derive(Default)]
+    struct ServedFile {
+        body: Vec<u8>,
+        etag: String,
+        downloads: usize,
+    }
+
+    /// Serves a single file on a loopback port, answering conditional requests with 304
+    fn serve(file: Arc<Mutex<ServedFile>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut if_none_match = None;
+                let mut reader = BufReader::new(&mut stream);
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(etag) = line.strip_prefix("If-None-Match: ") {
+                        if_none_match = Some(etag.to_string());
+                    }
+                }
+
+                let mut file = file.lock();
+                let response = if !request_line.starts_with("GET /assets/level.txt ") {
+                    b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_vec()
+                } else if if_none_match.as_deref() == Some(file.etag.as_str()) {
+                    format!(
+                        "HTTP/1.1 304 Not Modified\r\nETag: {}\r\nConnection: close\r\n\r\n",
+                        file.etag
+                    )
+                    .into_bytes()
+                } else {
+                    file.downloads += 1;
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nETag: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        file.etag,
+                        file.body.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(&file.body);
+                    response
+                };
+                stream.write_all(&response).unwrap();
+            }
+        });
+        format!("http://{}/assets", address)
+    }
+
+    #[test]
+    fn http_revalidation_and_polling() {
+        let file = Arc::new(Mutex::new(ServedFile {
+            body: b"first".to_vec(),
+            etag: "\"1\"".to_string(),
+            downloads: 0,
+        }));
+        let base_url = serve(file.clone());
+        let cache_folder = tempfile::tempdir().unwrap();
+        let load = |asset_io: &HttpAssetIo, path: &str| {
+            futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+        };
+
+        let asset_io = HttpAssetIo::new(&base_url).with_cache_folder(cache_folder.path());
+        assert_eq!(load(&asset_io, "level.txt").unwrap(), b"first");
+        assert!(matches!(
+            load(&asset_io, "missing.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        // paths that could leave the cache folder are not cached
+        assert!(asset_io
+            .state
+            .cache_paths(Path::new("./level.txt"))
+            .is_some());
+        assert!(asset_io
+            .state
+            .cache_paths(Path::new("../level.txt"))
+            .is_none());
+        assert!(asset_io
+            .state
+            .cache_paths(Path::new("/level.txt"))
+            .is_none());
+
+        // unchanged files are revalidated and served from the cache, even after a restart
+        let asset_io = HttpAssetIo::new(&base_url).with_cache_folder(cache_folder.path());
+        assert_eq!(load(&asset_io, "level.txt").unwrap(), b"first");
+        assert_eq!(file.lock().downloads, 1);
+
+        // later loads revalidate with the validators in memory
+        fs::remove_file(cache_folder.path().join("level.txt.http")).unwrap();
+        assert_eq!(load(&asset_io, "level.txt").unwrap(), b"first");
+        assert_eq!(file.lock().downloads, 1);
+
+        // a removed cached file is downloaded again
+        fs::remove_file(cache_folder.path().join("level.txt")).unwrap();
+        assert_eq!(load(&asset_io, "level.txt").unwrap(), b"first");
+        assert_eq!(file.lock().downloads, 2);
+
+        // polling reports changed files
+        asset_io
+            .watch_path_for_changes(Path::new("level.txt"))
+            .unwrap();
+        asset_io.state.poll();
+        assert!(asset_io.changed_paths().is_empty());
+        {
+            let mut file = file.lock();
+            file.body = b"second".to_vec();
+            file.etag = "\"2\"".to_string();
+        }
+        asset_io.state.poll();
+        assert_eq!(asset_io.changed_paths(), vec![PathBuf::from("level.txt")]);
+        assert_eq!(load(&asset_io, "level.txt").unwrap(), b"second");
+        assert_eq!(file.lock().downloads, 3);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashSet};
+use std::path::{Path, PathBuf};
+
+/// An ordered stack of `AssetIo` layers that behave like a single source.
+///
+/// A file in a higher layer shadows the file with the same path in every layer below it, so a mod
+/// directory can be pushed on top of the base game assets to replace individual files. Directory
+/// listings are merged across all layers.
+#[derive(Default)]
+pub struct LayeredAssetIo {
+    // ordered from the highest to the lowest priority
+    layers: Vec<Box<dyn AssetIo>>,
+}
+
+impl LayeredAssetIo {
+    pub fn new(base: Box<dyn AssetIo>) -> Self {
+        LayeredAssetIo { layers: vec![base] }
+    }
+
+    /// Adds a layer that takes priority over all existing layers.
+    pub fn push_layer(&mut self, layer: Box<dyn AssetIo>) {
+        self.layers.insert(0, layer);
+    }
+
+    pub fn with_layer(mut self, layer: Box<dyn AssetIo>) -> Self {
+        self.push_layer(layer);
+        self
+    }
+
+    /// The layers, ordered from the highest to the lowest priority.
+    pub fn layers(&self) -> &[Box<dyn AssetIo>] {
+        &self.layers
+    }
+}
+
+impl AssetIo for LayeredAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                match layer.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut seen = HashSet::default();
+        let mut children = Vec::new();
+        let mut found = false;
+        for layer in self.layers.iter().filter(|layer| layer.is_directory(path)) {
+            found = true;
+            for child in layer.read_directory(path)? {
+                if seen.insert(child.clone()) {
+                    children.push(child);
+                }
+            }
+        }
+
+        if !found {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.layers.iter().any(|layer| layer.is_directory(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        // watch every layer that has the file, so changes to a shadowed file still trigger a
+        // reload that picks the right layer
+        let mut result = Err(AssetIoError::PathWatchError(path.to_owned()));
+        for layer in self.layers.iter() {
+            if layer.watch_path_for_changes(path).is_ok() {
+                result = Ok(());
+            }
+        }
+        result
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        for layer in self.layers.iter() {
+            layer.watch_for_changes()?;
+        }
+        Ok(())
+    }
+
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        self.layers.iter().find_map(|layer| layer.file_size(path))
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            // write to the highest layer that accepts writes, so the written file shadows the
+            // files of all layers below it
+            for layer in self.layers.iter() {
+                match layer.write_path(path, bytes).await {
+                    Err(AssetIoError::ReadOnly(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::ReadOnly(path.to_owned()))
+        })
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for layer in self.layers.iter() {
+            for path in layer.changed_paths() {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+        changed
+    }
+}
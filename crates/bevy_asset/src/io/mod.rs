@@ -1,18 +1,34 @@
 #[cfg(target_os = "android")]
 mod android_asset_io;
+#[cfg(not(target_arch = "wasm32"))]
+mod archive_asset_io;
+mod embedded_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+mod http_asset_io;
+mod layered_asset_io;
+mod namespaced_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use archive_asset_io::*;
+pub use embedded_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+pub use http_asset_io::*;
+pub use layered_asset_io::*;
+pub use namespaced_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+use crate::{AssetServer, META_EXTENSION};
 use anyhow::Result;
+use bevy_ecs::system::Res;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
 use std::{
@@ -30,6 +46,12 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+    #[error("no asset source named `{0}`")]
+    UnknownSource(String),
+    #[error("asset source is read-only, cannot write: {0}")]
+    ReadOnly(PathBuf),
+    #[error("failed to request remote asset: {0}")]
+    RemoteRequestFailed(String),
 }
 
 /// Handles load requests from an AssetServer
@@ -40,8 +62,51 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
         path: &Path,
     ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError>;
     fn is_directory(&self, path: &Path) -> bool;
+    /// Watches `path` for changes, which are then reported by [`AssetIo::changed_paths`]. Paths
+    /// that do not exist yet, like the `.meta` file of an asset without settings, should report
+    /// their creation.
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+
+    /// Returns the watched paths that changed since the last call, including files that were
+    /// created, renamed or deleted in watched folders. The `AssetServer` reloads the assets at
+    /// these paths and updates the live folders that contain them.
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns the size of the file at `path` in bytes without reading it, if the source can tell.
+    /// This is used to report [`LoadProgress`](crate::LoadProgress).
+    fn file_size(&self, _path: &Path) -> Option<u64> {
+        None
+    }
+
+    /// Writes `bytes` to `path`, replacing the existing file and creating missing parent
+    /// directories. Sources that cannot be written to return [`AssetIoError::ReadOnly`].
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+    }
 }
 
 impl_downcast!(AssetIo);
+
+/// Reloads the assets whose files were reported as changed by the `AssetServer`'s `AssetIo`.
+///
+/// Changes caused by [`AssetServer::save`] do not reload the saved asset.
+pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
+    let changed_paths = asset_server.server.asset_io.changed_paths();
+    for changed_path in changed_paths.iter() {
+        // a modified `.meta` file reloads the asset it belongs to
+        if changed_path.extension() == Some(META_EXTENSION.as_ref()) {
+            asset_server.reload_with_dependants(changed_path.with_extension(""));
+        } else {
+            asset_server.reload_changed_path(changed_path.clone());
+        }
+    }
+    // files that were created, renamed or deleted change the live folders that contain them
+    asset_server.update_live_folders(&changed_paths);
+}
@@ -0,0 +1,150 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use std::path::{Path, PathBuf};
+
+/// The separator between the source name and the path in a namespaced asset path, as in
+/// `mods://textures/grass.png`.
+pub const SOURCE_SEPARATOR: &str = "://";
+
+/// Splits a namespaced path like `mods://textures/grass.png` into the source name and the path
+/// within that source. Paths without a source name belong to the default source.
+pub fn split_source_path(path: &Path) -> (Option<&str>, &Path) {
+    if let Some(path_str) = path.to_str() {
+        if let Some(index) = path_str.find(SOURCE_SEPARATOR) {
+            let (source, rest) = path_str.split_at(index);
+            return (Some(source), Path::new(&rest[SOURCE_SEPARATOR.len()..]));
+        }
+    }
+    (None, path)
+}
+
+/// Routes asset paths to different `AssetIo` sources by the `name://` prefix of the path.
+///
+/// Paths without a prefix are served by the default source. Every source can itself be any
+/// `AssetIo`, for example a [`LayeredAssetIo`](crate::LayeredAssetIo) to overlay mods on top of the
+/// base game assets. Changes are watched per source and reported with the source prefix, so hot
+/// reloading works for all of them. Writes go to the source of the path.
+pub struct NamespacedAssetIo {
+    default_source: Box<dyn AssetIo>,
+    sources: HashMap<String, Box<dyn AssetIo>>,
+}
+
+impl NamespacedAssetIo {
+    pub fn new(default_source: Box<dyn AssetIo>) -> Self {
+        NamespacedAssetIo {
+            default_source,
+            sources: Default::default(),
+        }
+    }
+
+    /// Adds a source that serves the paths prefixed with `name://`, replacing any existing source
+    /// with that name.
+    pub fn add_source<S: Into<String>>(&mut self, name: S, source: Box<dyn AssetIo>) {
+        self.sources.insert(name.into(), source);
+    }
+
+    pub fn with_source<S: Into<String>>(mut self, name: S, source: Box<dyn AssetIo>) -> Self {
+        self.add_source(name, source);
+        self
+    }
+
+    pub fn get_source(&self, name: &str) -> Option<&dyn AssetIo> {
+        self.sources.get(name).map(|source| &**source)
+    }
+
+    pub fn default_source(&self) -> &dyn AssetIo {
+        &*self.default_source
+    }
+
+    fn resolve<'a>(&self, path: &'a Path) -> Result<(&dyn AssetIo, &'a Path), AssetIoError> {
+        match split_source_path(path) {
+            (Some(name), source_path) => self
+                .get_source(name)
+                .map(|source| (source, source_path))
+                .ok_or_else(|| AssetIoError::UnknownSource(name.to_string())),
+            (None, path) => Ok((self.default_source(), path)),
+        }
+    }
+}
+
+fn with_source_prefix(name: Option<&str>, path: PathBuf) -> PathBuf {
+    match name {
+        Some(name) => PathBuf::from(format!(
+            "{}{}{}",
+            name,
+            SOURCE_SEPARATOR,
+            path.to_string_lossy()
+        )),
+        None => path,
+    }
+}
+
+impl AssetIo for NamespacedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let (source, source_path) = self.resolve(path)?;
+            source.load_path(source_path).await
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let (source, source_path) = self.resolve(path)?;
+        let name = split_source_path(path).0.map(|name| name.to_string());
+        Ok(Box::new(source.read_directory(source_path)?.map(
+            move |child| with_source_prefix(name.as_deref(), child),
+        )))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        match self.resolve(path) {
+            Ok((source, source_path)) => source.is_directory(source_path),
+            Err(_) => false,
+        }
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        let (source, source_path) = self.resolve(path)?;
+        source.watch_path_for_changes(source_path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.default_source.watch_for_changes()?;
+        for source in self.sources.values() {
+            source.watch_for_changes()?;
+        }
+        Ok(())
+    }
+
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        let (source, source_path) = self.resolve(path).ok()?;
+        source.file_size(source_path)
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let (source, source_path) = self.resolve(path)?;
+            source.write_path(source_path, bytes).await
+        })
+    }
+
+    fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut changed = self.default_source.changed_paths();
+        for (name, source) in self.sources.iter() {
+            changed.extend(
+                source
+                    .changed_paths()
+                    .into_iter()
+                    .map(|path| with_source_prefix(Some(name), path)),
+            );
+        }
+        changed
+    }
+}
@@ -28,6 +28,11 @@ impl AssetIo for WasmAssetIo {
                 .await
                 .unwrap();
             let resp: Response = resp_value.dyn_into().unwrap();
+            // the server answers missing files, like absent `.meta` files, with an error status
+            // whose body is not the asset
+            if !resp.ok() {
+                return Err(AssetIoError::NotFound(path));
+            }
             let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
             let bytes = Uint8Array::new(&data).to_vec();
             Ok(bytes)
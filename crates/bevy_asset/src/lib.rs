@@ -6,11 +6,15 @@ pub mod diagnostic;
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 mod filesystem_watcher;
+mod folder;
 mod handle;
 mod info;
 mod io;
 mod loader;
 mod path;
+mod processor;
+mod reflect;
+mod saver;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -20,11 +24,15 @@ pub mod prelude {
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use folder::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
+pub use reflect::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
@@ -44,12 +52,16 @@ pub struct AssetPlugin;
 
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// The folder processed assets are cached in. If this is `None`, assets that have an
+    /// [`AssetProcessor`] are processed every time they are loaded.
+    pub processed_asset_folder: Option<String>,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            processed_asset_folder: Some("processed_assets".to_string()),
         }
     }
 }
@@ -58,6 +70,9 @@ impl Default for AssetServerSettings {
 ///
 /// This is useful when providing a custom `AssetIo` instance that needs to
 /// delegate to the default `AssetIo` for the platform.
+///
+/// Besides the asset folder, the returned `AssetIo` serves the [`EmbeddedAssets`] of the app from
+/// `embedded://` paths.
 pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
     let settings = app
         .world
@@ -70,11 +85,22 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
     #[cfg(target_os = "android")]
     let source = AndroidAssetIo::new(&settings.asset_folder);
 
-    Box::new(source)
+    let embedded_assets = app
+        .world
+        .get_resource_or_insert_with(EmbeddedAssets::default)
+        .clone();
+
+    Box::new(NamespacedAssetIo::new(Box::new(source)).with_source(
+        EMBEDDED_SOURCE,
+        Box::new(EmbeddedAssetIo::new(embedded_assets)),
+    ))
 }
 
 impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with(EmbeddedAssets::default);
+
         if app.world.get_resource::<AssetServer>().is_none() {
             let task_pool = app
                 .world
@@ -87,6 +113,16 @@ impl Plugin for AssetPlugin {
 
             let asset_server = AssetServer::with_boxed_io(source, task_pool);
 
+            #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+            {
+                let settings = app.world.get_resource::<AssetServerSettings>().unwrap();
+                asset_server.set_processed_asset_cache(
+                    settings.processed_asset_folder.as_ref().map(|folder| {
+                        ProcessedAssetCache::new(FileAssetIo::get_root_path().join(folder))
+                    }),
+                );
+            }
+
             app.insert_resource(asset_server);
         }
 
@@ -101,15 +137,27 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .register_type::<AssetPath<'static>>()
+        .add_event::<AssetLoadedWithDependencies>()
+        .add_event::<AssetFolderEvent>()
+        .init_resource::<LoadProgress>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
-        );
-
-        #[cfg(all(
-            feature = "filesystem_watcher",
-            all(not(target_arch = "wasm32"), not(target_os = "android"))
-        ))]
-        app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            asset_server::recursive_load_event_system,
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            asset_server::load_progress_system,
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            asset_server::live_folder_event_system,
+        )
+        .add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system)
+        .add_asset::<AssetFolder>();
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, AssetServerError, Assets,
+    Handle, HandleId, RefChangeChannel,
 };
 use anyhow::Result;
 use bevy_ecs::{
@@ -12,7 +12,13 @@ use bevy_tasks::TaskPool;
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
@@ -22,6 +28,51 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+
+    /// Deserializes the contents of an asset's `.meta` file into the settings for this loader,
+    /// which are then available through [`LoadContext::settings`].
+    ///
+    /// Only called if [`AssetLoader::has_settings`] returns `true`. See
+    /// [`deserialize_ron_settings`] for the usual implementation.
+    fn deserialize_settings(
+        &self,
+        _meta: &[u8],
+    ) -> Result<Option<Box<dyn AssetLoaderSettings>>, anyhow::Error> {
+        Ok(None)
+    }
+
+    /// Whether this loader reads settings from `.meta` files. Loaders that implement
+    /// [`AssetLoader::deserialize_settings`] return `true`; for all others the `AssetServer`
+    /// neither reads nor watches `.meta` files.
+    fn has_settings(&self) -> bool {
+        false
+    }
+}
+
+/// Per-asset settings for an [`AssetLoader`]
+pub trait AssetLoaderSettings: Downcast + Debug + Send + Sync + 'static {}
+impl_downcast!(AssetLoaderSettings);
+
+impl<T> AssetLoaderSettings for T where T: Debug + Send + Sync + 'static {}
+
+/// Deserializes [`AssetLoaderSettings`] of type `T` from the RON contents of a `.meta` file.
+pub fn deserialize_ron_settings<T: AssetLoaderSettings + DeserializeOwned>(
+    meta: &[u8],
+) -> Result<Option<Box<dyn AssetLoaderSettings>>, anyhow::Error> {
+    let settings: T = ron::de::from_bytes(meta)?;
+    Ok(Some(Box::new(settings)))
+}
+
+/// The extension of the sidecar files that hold per-asset loader settings
+pub const META_EXTENSION: &str = "meta";
+
+/// Returns the path of the `.meta` file for the asset at `path`, e.g. `textures/grass.png.meta`
+/// for `textures/grass.png`.
+pub fn get_meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".");
+    meta_path.push(META_EXTENSION);
+    PathBuf::from(meta_path)
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -76,27 +127,30 @@ impl<T: Asset> From<LoadedAsset<T>> for BoxedLoadedAsset {
 pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) asset_server: &'a AssetServer,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
+    pub(crate) loader_dependencies: Mutex<Vec<PathBuf>>,
     pub(crate) path: &'a Path,
+    // the paths of the assets whose loaders are loading this asset through `load_direct`
+    pub(crate) load_chain: Vec<PathBuf>,
+    pub(crate) settings: Option<Arc<dyn AssetLoaderSettings>>,
     pub(crate) version: usize,
     pub(crate) task_pool: &'a TaskPool,
 }
 
 impl<'a> LoadContext<'a> {
-    pub(crate) fn new(
-        path: &'a Path,
-        ref_change_channel: &'a RefChangeChannel,
-        asset_io: &'a dyn AssetIo,
-        version: usize,
-        task_pool: &'a TaskPool,
-    ) -> Self {
+    pub(crate) fn new(path: &'a Path, asset_server: &'a AssetServer, version: usize) -> Self {
         Self {
-            ref_change_channel,
-            asset_io,
+            ref_change_channel: &asset_server.server.asset_ref_counter.channel,
+            asset_io: &*asset_server.server.asset_io,
+            asset_server,
             labeled_assets: Default::default(),
+            loader_dependencies: Default::default(),
             version,
             path,
-            task_pool,
+            load_chain: Vec::new(),
+            settings: None,
+            task_pool: &asset_server.server.task_pool,
         }
     }
 
@@ -104,6 +158,14 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Returns the settings read from this asset's `.meta` file, if it has one and they are of
+    /// type `T`.
+    pub fn settings<T: AssetLoaderSettings>(&self) -> Option<&T> {
+        self.settings
+            .as_deref()
+            .and_then(|settings| settings.downcast_ref::<T>())
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -123,8 +185,98 @@ impl<'a> LoadContext<'a> {
         Handle::strong(id.into(), self.ref_change_channel.sender.clone())
     }
 
+    /// Reads the bytes of the file at `path` through the `AssetIo` of the `AssetServer`.
+    ///
+    /// The file becomes a dependency of the asset being loaded, which is reloaded when the file
+    /// changes.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
-        self.asset_io.load_path(path.as_ref()).await
+        let path = path.as_ref();
+        let bytes = self.asset_io.load_path(path).await?;
+        self.add_loader_dependency(path);
+        Ok(bytes)
+    }
+
+    /// Loads the asset at `path` with its `AssetLoader` and returns its value, instead of adding
+    /// it to the `Assets` storage. A label in the path selects a labeled asset of the file.
+    ///
+    /// Like with [`LoadContext::read_asset_bytes`], the asset being loaded is reloaded when the
+    /// loaded file changes. Dependencies of the returned asset are loaded by the `AssetServer`.
+    ///
+    /// Returns [`AssetServerError::CyclicLoad`] if the asset at `path` is already being loaded
+    /// further up the chain of direct loads.
+    pub async fn load_direct<'b, T: Asset, P: Into<AssetPath<'b>>>(
+        &self,
+        path: P,
+    ) -> Result<T, AssetServerError> {
+        let asset_path = path.into();
+        if asset_path.path() == self.path
+            || self
+                .load_chain
+                .iter()
+                .any(|loading| loading == asset_path.path())
+        {
+            return Err(AssetServerError::CyclicLoad(asset_path.path().to_owned()));
+        }
+
+        let source = self
+            .asset_server
+            .read_asset_source(asset_path.path())
+            .await?;
+        self.add_loader_dependency(asset_path.path());
+        if source.settings.is_some() {
+            self.add_loader_dependency(&get_meta_path(asset_path.path()));
+        }
+        for dependency in source.process_dependencies.iter() {
+            self.add_loader_dependency(dependency);
+        }
+
+        let mut load_context = LoadContext::new(asset_path.path(), self.asset_server, self.version);
+        load_context.settings = source.settings;
+        load_context.load_chain = self.load_chain.clone();
+        load_context.load_chain.push(self.path.to_owned());
+        source
+            .loader
+            .load(&source.bytes, &mut load_context)
+            .await
+            .map_err(AssetServerError::AssetLoaderError)?;
+
+        // the nested asset's own dependencies are dependencies of this asset as well
+        for path in load_context.loader_dependencies.into_inner() {
+            self.add_loader_dependency(&path);
+        }
+        for loaded_asset in load_context.labeled_assets.values() {
+            for dependency in loaded_asset.dependencies.iter() {
+                self.asset_server.load_untracked(dependency.clone(), false);
+            }
+        }
+
+        let label = asset_path.label().map(|label| label.to_string());
+        let value = load_context
+            .labeled_assets
+            .remove(&label)
+            .and_then(|loaded_asset| loaded_asset.value)
+            .ok_or(AssetServerError::MissingLabeledAsset {
+                path: asset_path.to_owned(),
+            })?;
+        value
+            .downcast::<T>()
+            .map(|value| *value)
+            .map_err(|_| AssetServerError::IncorrectHandleType)
+    }
+
+    pub(crate) fn add_loader_dependency(&self, path: &Path) {
+        let mut loader_dependencies = self.loader_dependencies.lock();
+        if !loader_dependencies
+            .iter()
+            .any(|dependency| dependency == path)
+        {
+            loader_dependencies.push(path.to_owned());
+        }
+    }
+
+    /// The paths that were read or loaded directly while loading this asset.
+    pub fn loader_dependencies(&self) -> Vec<PathBuf> {
+        self.loader_dependencies.lock().clone()
     }
 
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
@@ -134,6 +286,7 @@ impl<'a> LoadContext<'a> {
                 dependencies: asset.dependencies.clone(),
                 label: label.clone(),
                 type_uuid: asset.value.as_ref().unwrap().type_uuid(),
+                settings: self.settings.clone(),
             });
         }
         asset_metas
@@ -162,6 +315,8 @@ pub struct AssetLifecycleChannel<T: Component> {
 pub enum AssetLifecycleEvent<T: Component> {
     Create(AssetResult<T>),
     Free(HandleId),
+    /// Saves the asset with the given id to a path with the `AssetSaver` for that path
+    Save(HandleId, PathBuf),
 }
 
 pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
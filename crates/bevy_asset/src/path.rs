@@ -1,13 +1,18 @@
-use bevy_reflect::{Reflect, ReflectDeserialize};
+use crate::split_source_path;
+use bevy_reflect::{
+    serde::Serializable, FromType, GetTypeRegistration, Reflect, ReflectDeserialize, ReflectMut,
+    ReflectRef, TypeInfo, TypeRegistration, Typed, ValueInfo,
+};
 use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
+    any::Any,
     borrow::Cow,
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
-#[derive(Debug, Hash, Clone, Serialize, Deserialize)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
@@ -45,6 +50,13 @@ impl<'a> AssetPath<'a> {
         &self.path
     }
 
+    /// Returns the name of the asset source this path belongs to, e.g. `mods` for
+    /// `mods://textures/grass.png`. See [`NamespacedAssetIo`](crate::NamespacedAssetIo).
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        split_source_path(&self.path).0
+    }
+
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
@@ -169,3 +181,78 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
         }
     }
 }
+
+// SAFE: any and any_mut both return self
+unsafe impl Reflect for AssetPath<'static> {
+    fn type_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        let value = value.any();
+        if let Some(value) = value.downcast_ref::<Self>() {
+            *self = value.clone();
+        } else {
+            panic!("Value is not a {}.", std::any::type_name::<Self>());
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Value(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Value(self)
+    }
+
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone())
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        let mut hasher = AHasher::default();
+        Hash::hash(&Any::type_id(self), &mut hasher);
+        Hash::hash(self, &mut hasher);
+        Some(hasher.finish())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        let value = value.any();
+        if let Some(value) = value.downcast_ref::<Self>() {
+            Some(PartialEq::eq(self, value))
+        } else {
+            Some(false)
+        }
+    }
+
+    fn serializable(&self) -> Option<Serializable<'_>> {
+        Some(Serializable::Borrowed(self))
+    }
+}
+
+impl Typed for AssetPath<'static> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::Value(ValueInfo::of::<Self>())
+    }
+}
+
+impl GetTypeRegistration for AssetPath<'static> {
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<AssetPath<'static>>();
+        registration.insert::<ReflectDeserialize>(FromType::<AssetPath<'static>>::from_type());
+        registration
+    }
+}
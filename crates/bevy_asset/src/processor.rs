@@ -0,0 +1,271 @@
+use crate::{path::get_hasher, AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_log::warn;
+use bevy_utils::BoxedFuture;
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Transforms the bytes of a source asset into an optimized runtime format before they are
+/// handed to an [`AssetLoader`](crate::AssetLoader).
+///
+/// Processed artifacts are stored in a [`ProcessedAssetCache`] and are only regenerated when the
+/// source file, any file read through the [`ProcessContext`] or the processor version changes.
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+
+    /// The source extensions this processor handles.
+    fn extensions(&self) -> &[&str];
+
+    /// The extension of the processed artifact. This selects the `AssetLoader` that loads it.
+    fn processed_extension(&self) -> &str;
+
+    /// The version of the processed format. Bumping it invalidates all cached artifacts produced
+    /// by this processor.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// A source file read while processing an asset, along with the hash of its contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessDependency {
+    pub path: PathBuf,
+    pub hash: u64,
+}
+
+pub struct ProcessContext<'a> {
+    pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) path: &'a Path,
+    pub(crate) dependencies: Vec<ProcessDependency>,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub(crate) fn new(path: &'a Path, asset_io: &'a dyn AssetIo) -> Self {
+        Self {
+            asset_io,
+            path,
+            dependencies: Vec::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the bytes of another source file. The file is recorded as an input of the processed
+    /// artifact, so changes to it cause the asset to be processed again.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let path = path.as_ref();
+        let bytes = self.asset_io.load_path(path).await?;
+        self.dependencies.push(ProcessDependency {
+            path: path.to_owned(),
+            hash: hash_bytes(&bytes),
+        });
+        Ok(bytes)
+    }
+
+    pub fn dependencies(&self) -> &[ProcessDependency] {
+        &self.dependencies
+    }
+}
+
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = get_hasher();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Identifies a processed artifact by the source path, the source content hash and the processor
+/// that produced it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessedAssetKey {
+    /// The hash of the source path and the processor, which is shared by all the artifacts of
+    /// the same asset
+    slot: u64,
+    source: u64,
+}
+
+impl ProcessedAssetKey {
+    pub fn new(path: &Path, source_bytes: &[u8], processor: &dyn AssetProcessor) -> Self {
+        let mut hasher = get_hasher();
+        path.hash(&mut hasher);
+        processor.extensions().hash(&mut hasher);
+        processor.processed_extension().hash(&mut hasher);
+        processor.version().hash(&mut hasher);
+        ProcessedAssetKey {
+            slot: hasher.finish(),
+            source: hash_bytes(source_bytes),
+        }
+    }
+
+    fn slot_prefix(&self) -> String {
+        format!("{:016x}-", self.slot)
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}{:016x}", self.slot_prefix(), self.source)
+    }
+}
+
+/// A directory of processed asset artifacts keyed by [`ProcessedAssetKey`].
+///
+/// Each artifact is stored next to a small manifest listing the extra source files read while
+/// processing it, which are checked for modifications before the artifact is reused. Only the
+/// latest artifact of each asset is kept.
+pub struct ProcessedAssetCache {
+    root_path: PathBuf,
+}
+
+impl ProcessedAssetCache {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        ProcessedAssetCache {
+            root_path: path.as_ref().to_owned(),
+        }
+    }
+
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    fn artifact_path(&self, key: ProcessedAssetKey) -> PathBuf {
+        self.root_path.join(key.file_name())
+    }
+
+    fn manifest_path(&self, key: ProcessedAssetKey) -> PathBuf {
+        self.root_path.join(format!("{}.deps", key.file_name()))
+    }
+
+    /// Returns the cached artifact for `key` and the dependencies it was processed with, if one
+    /// exists and none of its dependencies changed.
+    pub async fn get(
+        &self,
+        key: ProcessedAssetKey,
+        asset_io: &dyn AssetIo,
+    ) -> Option<(Vec<u8>, Vec<ProcessDependency>)> {
+        let manifest = fs::read_to_string(self.manifest_path(key)).ok()?;
+        let dependencies = parse_manifest(&manifest)?;
+        for dependency in dependencies.iter() {
+            let bytes = asset_io.load_path(&dependency.path).await.ok()?;
+            if hash_bytes(&bytes) != dependency.hash {
+                return None;
+            }
+        }
+
+        Some((fs::read(self.artifact_path(key)).ok()?, dependencies))
+    }
+
+    /// Stores the artifact for `key`, replacing the artifacts of previous versions of the same
+    /// source file.
+    pub fn insert(
+        &self,
+        key: ProcessedAssetKey,
+        bytes: &[u8],
+        dependencies: &[ProcessDependency],
+    ) -> Result<(), io::Error> {
+        fs::create_dir_all(&self.root_path)?;
+        self.remove_stale(key)?;
+        fs::write(self.artifact_path(key), bytes)?;
+        // the manifest is written last so a partially written artifact is never considered valid
+        fs::write(self.manifest_path(key), write_manifest(dependencies))
+    }
+
+    /// Removes the artifacts and manifests of the same asset and processor as `key`, but of other
+    /// source contents.
+    fn remove_stale(&self, key: ProcessedAssetKey) -> Result<(), io::Error> {
+        let slot_prefix = key.slot_prefix();
+        let file_name = key.file_name();
+        for entry in fs::read_dir(&self.root_path)? {
+            let entry = entry?;
+            let entry_name = entry.file_name();
+            let entry_name = entry_name.to_string_lossy();
+            let stale = entry_name.starts_with(&slot_prefix)
+                && entry_name.trim_end_matches(".deps") != file_name;
+            if stale {
+                match fs::remove_file(entry.path()) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes every artifact from the cache.
+    pub fn clear(&self) -> Result<(), io::Error> {
+        match fs::remove_dir_all(&self.root_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn write_manifest(dependencies: &[ProcessDependency]) -> String {
+    let mut manifest = String::new();
+    for dependency in dependencies {
+        manifest.push_str(&format!(
+            "{:016x} {}\n",
+            dependency.hash,
+            dependency.path.to_string_lossy()
+        ));
+    }
+    manifest
+}
+
+fn parse_manifest(manifest: &str) -> Option<Vec<ProcessDependency>> {
+    let mut dependencies = Vec::new();
+    for line in manifest.lines() {
+        let mut parts = line.splitn(2, ' ');
+        let hash = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let path = PathBuf::from(parts.next()?);
+        dependencies.push(ProcessDependency { path, hash });
+    }
+    Some(dependencies)
+}
+
+/// Runs `processor` on `bytes`, reusing the artifact in `cache` when it is up to date. Returns the
+/// processed bytes and the paths of the other source files they were processed from.
+pub(crate) async fn process_asset(
+    processor: &dyn AssetProcessor,
+    cache: Option<&ProcessedAssetCache>,
+    path: &Path,
+    bytes: &[u8],
+    asset_io: &dyn AssetIo,
+) -> Result<(Vec<u8>, Vec<PathBuf>), anyhow::Error> {
+    let key = ProcessedAssetKey::new(path, bytes, processor);
+    if let Some(cache) = cache {
+        if let Some((processed, dependencies)) = cache.get(key, asset_io).await {
+            return Ok((processed, dependency_paths(dependencies)));
+        }
+    }
+
+    let mut process_context = ProcessContext::new(path, asset_io);
+    let processed = processor.process(bytes, &mut process_context).await?;
+    if let Some(cache) = cache {
+        if let Err(err) = cache.insert(key, &processed, process_context.dependencies()) {
+            warn!(
+                "failed to write processed asset {:?} to the cache: {}",
+                path, err
+            );
+        }
+    }
+
+    Ok((processed, dependency_paths(process_context.dependencies)))
+}
+
+fn dependency_paths(dependencies: Vec<ProcessDependency>) -> Vec<PathBuf> {
+    dependencies
+        .into_iter()
+        .map(|dependency| dependency.path)
+        .collect()
+}
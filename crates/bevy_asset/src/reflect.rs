@@ -0,0 +1,160 @@
+use crate::{Asset, AssetPath, AssetServer, Handle, HandleId, HandleUntyped};
+use bevy_reflect::{
+    DynamicStruct, FromType, GetField, Reflect, ReflectMut, ReflectRef, TypeRegistry,
+};
+use bevy_utils::HashSet;
+
+/// The name of the field that stores the [`AssetPath`] of a handle that was converted with
+/// [`handle_ids_to_paths`].
+pub const HANDLE_FIELD_PATH: &str = "path";
+const HANDLE_FIELD_ID: &str = "id";
+
+/// Type data for [`Handle`] types, which is used to find handles in reflected values.
+#[derive(Clone)]
+pub struct ReflectHandle {
+    make_strong: fn(&mut dyn Reflect, &AssetServer),
+}
+
+impl ReflectHandle {
+    /// Replaces the handle with a strong handle to the same asset, if it is a weak handle.
+    pub fn make_strong(&self, handle: &mut dyn Reflect, asset_server: &AssetServer) {
+        (self.make_strong)(handle, asset_server);
+    }
+}
+
+impl<T: Asset> FromType<Handle<T>> for ReflectHandle {
+    fn from_type() -> Self {
+        ReflectHandle {
+            make_strong: |reflect, asset_server| {
+                if let Some(handle) = reflect.downcast_mut::<Handle<T>>() {
+                    if handle.is_weak() {
+                        *handle = asset_server.get_handle(handle.id);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Replaces the handles in a dynamic value, as returned by [`Reflect::clone_value`], with their
+/// [`AssetPath`] if they were loaded from a path. The handles then serialize as their path and
+/// label instead of an id, which is only meaningful while the app is running.
+pub fn handle_ids_to_paths(
+    value: &mut dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: &AssetServer,
+) {
+    visit_handles(value, type_registry, &mut |handle, _| {
+        let path = match handle_id(handle).and_then(|id| asset_server.get_handle_path(id)) {
+            Some(path) => path.to_owned(),
+            None => return,
+        };
+        let mut dynamic_handle = DynamicStruct::default();
+        dynamic_handle.set_name(handle.type_name().to_string());
+        dynamic_handle.insert(HANDLE_FIELD_PATH, path);
+        // handles that are not dynamic cannot be replaced, and keep their id
+        let _ = handle.set(Box::new(dynamic_handle));
+    });
+}
+
+/// Loads the [`AssetPath`]s of the handles in a dynamic value that were converted with
+/// [`handle_ids_to_paths`] and replaces them with the ids of the loaded assets, so the value can
+/// be applied to a real value again. The returned handles keep the assets alive until the handles
+/// in the real value are made strong with [`make_handles_strong`].
+pub fn load_handle_paths(
+    value: &mut dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: &AssetServer,
+) -> Vec<HandleUntyped> {
+    let mut handles = Vec::new();
+    visit_handles(value, type_registry, &mut |handle, _| {
+        let path = match handle.reflect_ref() {
+            ReflectRef::Struct(handle) => handle
+                .get_field::<AssetPath<'static>>(HANDLE_FIELD_PATH)
+                .cloned(),
+            _ => None,
+        };
+        if let Some(path) = path {
+            let loaded_handle = asset_server.load_untyped(path);
+            let mut dynamic_handle = DynamicStruct::default();
+            dynamic_handle.set_name(handle.type_name().to_string());
+            dynamic_handle.insert(HANDLE_FIELD_ID, loaded_handle.id);
+            let _ = handle.set(Box::new(dynamic_handle));
+            handles.push(loaded_handle);
+        }
+    });
+    handles
+}
+
+/// Replaces the weak handles to the given assets in a value with strong handles.
+pub fn make_handles_strong(
+    value: &mut dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: &AssetServer,
+    handle_ids: &HashSet<HandleId>,
+) {
+    visit_handles(value, type_registry, &mut |handle, reflect_handle| {
+        if matches!(handle_id(handle), Some(id) if handle_ids.contains(&id)) {
+            reflect_handle.make_strong(handle, asset_server);
+        }
+    });
+}
+
+fn handle_id(handle: &dyn Reflect) -> Option<HandleId> {
+    match handle.reflect_ref() {
+        ReflectRef::Struct(handle) => handle.get_field::<HandleId>(HANDLE_FIELD_ID).copied(),
+        _ => None,
+    }
+}
+
+/// Calls `visitor` for every value in `value` whose type has [`ReflectHandle`] type data.
+fn visit_handles(
+    value: &mut dyn Reflect,
+    type_registry: &TypeRegistry,
+    visitor: &mut dyn FnMut(&mut dyn Reflect, &ReflectHandle),
+) {
+    if let Some(reflect_handle) = type_registry
+        .get_with_name(value.type_name())
+        .and_then(|registration| registration.data::<ReflectHandle>())
+    {
+        visitor(value, reflect_handle);
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for i in 0..value.field_len() {
+                visit_handles(value.field_at_mut(i).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for i in 0..value.field_len() {
+                visit_handles(value.field_mut(i).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for i in 0..value.field_len() {
+                visit_handles(value.field_mut(i).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::List(value) => {
+            for i in 0..value.len() {
+                visit_handles(value.get_mut(i).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys = (0..value.len())
+                .map(|i| value.get_at(i).unwrap().0.clone_value())
+                .collect::<Vec<_>>();
+            for key in keys {
+                visit_handles(value.get_mut(&*key).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for i in 0..value.field_len() {
+                visit_handles(value.field_at_mut(i).unwrap(), type_registry, visitor);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Result;
+use bevy_reflect::TypeUuid;
+use bevy_utils::Uuid;
+use std::path::Path;
+
+/// Serializes assets of type [`AssetSaver::Asset`] so they can be written back to an asset source
+/// with [`AssetServer::save`](crate::AssetServer::save). This is the counterpart of an
+/// [`AssetLoader`](crate::AssetLoader).
+///
+/// Assets are serialized while the `Assets` collection is borrowed, so `save` should only convert
+/// the asset to bytes. Writing the bytes happens on the IO task pool.
+pub trait AssetSaver: Send + Sync + 'static {
+    type Asset: Asset;
+
+    fn save(&self, asset: &Self::Asset, path: &Path) -> Result<Vec<u8>, anyhow::Error>;
+
+    /// The extensions of the paths this saver writes.
+    fn extensions(&self) -> &[&str];
+}
+
+pub(crate) trait ErasedAssetSaver: Send + Sync + 'static {
+    fn asset_type_uuid(&self) -> Uuid;
+    fn extensions(&self) -> &[&str];
+    fn save(&self, asset: &dyn AssetDynamic, path: &Path) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+impl<T: AssetSaver> ErasedAssetSaver for T {
+    fn asset_type_uuid(&self) -> Uuid {
+        T::Asset::TYPE_UUID
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetSaver::extensions(self)
+    }
+
+    fn save(&self, asset: &dyn AssetDynamic, path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+        let asset = asset
+            .downcast_ref::<T::Asset>()
+            .expect("Asset type should match the type of the `AssetSaver`.");
+        AssetSaver::save(self, asset, path)
+    }
+}
@@ -45,4 +45,8 @@ impl EntityMap {
     pub fn values(&self) -> impl Iterator<Item = Entity> + '_ {
         self.map.values().cloned()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.map.iter().map(|(from, to)| (*from, *to))
+    }
 }
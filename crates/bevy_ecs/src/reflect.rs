@@ -4,21 +4,47 @@ use crate::{
     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
     world::{FromWorld, World},
 };
-use bevy_reflect::{impl_reflect_value, FromType, Reflect, ReflectDeserialize};
+use bevy_reflect::{
+    impl_from_reflect_value, impl_reflect_value, FromReflect, FromType, Reflect, ReflectDeserialize,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReflectComponentError {
+    #[error("failed to construct the component `{type_name}` from `{value_type_name}`. consider adding the missing fields")]
+    FromReflect {
+        type_name: &'static str,
+        value_type_name: String,
+    },
+}
 
 #[derive(Clone)]
 pub struct ReflectComponent {
-    add_component: fn(&mut World, Entity, &dyn Reflect),
+    add_component: fn(&mut World, Entity, &dyn Reflect) -> Result<(), ReflectComponentError>,
     apply_component: fn(&mut World, Entity, &dyn Reflect),
     remove_component: fn(&mut World, Entity),
     reflect_component: fn(&World, Entity) -> Option<&dyn Reflect>,
     reflect_component_mut: unsafe fn(&World, Entity) -> Option<ReflectMut>,
-    copy_component: fn(&World, &mut World, Entity, Entity),
+    copy_component: fn(&World, &mut World, Entity, Entity) -> Result<(), ReflectComponentError>,
 }
 
 impl ReflectComponent {
+    /// # Panics
+    /// Panics if the component cannot be constructed, see [`ReflectComponent::try_add_component`].
     pub fn add_component(&self, world: &mut World, entity: Entity, component: &dyn Reflect) {
-        (self.add_component)(world, entity, component);
+        self.try_add_component(world, entity, component)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Adds `component` to `entity`. Fails if the component is constructed with [`FromReflect`],
+    /// see [`ReflectComponent::from_reflect`], and `component` is missing any of its fields.
+    pub fn try_add_component(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        component: &dyn Reflect,
+    ) -> Result<(), ReflectComponentError> {
+        (self.add_component)(world, entity, component)
     }
 
     pub fn apply_component(&self, world: &mut World, entity: Entity, component: &dyn Reflect) {
@@ -60,6 +86,8 @@ impl ReflectComponent {
         (self.reflect_component_mut)(world, entity)
     }
 
+    /// # Panics
+    /// Panics if the component cannot be constructed, see [`ReflectComponent::try_copy_component`].
     pub fn copy_component(
         &self,
         source_world: &World,
@@ -67,23 +95,64 @@ impl ReflectComponent {
         source_entity: Entity,
         destination_entity: Entity,
     ) {
+        self.try_copy_component(
+            source_world,
+            destination_world,
+            source_entity,
+            destination_entity,
+        )
+        .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Copies the component of `source_entity` to `destination_entity`. Fails like
+    /// [`ReflectComponent::try_add_component`].
+    pub fn try_copy_component(
+        &self,
+        source_world: &World,
+        destination_world: &mut World,
+        source_entity: Entity,
+        destination_entity: Entity,
+    ) -> Result<(), ReflectComponentError> {
         (self.copy_component)(
             source_world,
             destination_world,
             source_entity,
             destination_entity,
-        );
+        )
     }
 }
 
-impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
-    fn from_type() -> Self {
-        ReflectComponent {
-            add_component: |world, entity, reflected_component| {
-                let mut component = C::from_world(world);
-                component.apply(reflected_component);
+impl ReflectComponent {
+    /// Creates the [`ReflectComponent`] of a component that does not implement [`FromWorld`], by
+    /// constructing the component with [`FromReflect`] instead. Register it with
+    /// `#[reflect(Component(from_reflect))]` in place of `#[reflect(Component)]`.
+    ///
+    /// Adding or copying the component fails if the reflected component is missing any of its
+    /// fields.
+    pub fn from_reflect<C: Component + Reflect + FromReflect>() -> Self {
+        ReflectComponent::new::<C>(
+            |world, entity, reflected_component| {
+                let component = from_reflect_component::<C>(reflected_component)?;
                 world.entity_mut(entity).insert(component);
+                Ok(())
             },
+            |source_world, destination_world, source_entity, destination_entity| {
+                let source_component = source_world.get::<C>(source_entity).unwrap();
+                let destination_component = from_reflect_component::<C>(source_component)?;
+                destination_world
+                    .entity_mut(destination_entity)
+                    .insert(destination_component);
+                Ok(())
+            },
+        )
+    }
+
+    fn new<C: Component + Reflect>(
+        add_component: fn(&mut World, Entity, &dyn Reflect) -> Result<(), ReflectComponentError>,
+        copy_component: fn(&World, &mut World, Entity, Entity) -> Result<(), ReflectComponentError>,
+    ) -> Self {
+        ReflectComponent {
+            add_component,
             apply_component: |world, entity, reflected_component| {
                 let mut component = world.get_mut::<C>(entity).unwrap();
                 component.apply(reflected_component);
@@ -91,14 +160,7 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
             remove_component: |world, entity| {
                 world.entity_mut(entity).remove::<C>();
             },
-            copy_component: |source_world, destination_world, source_entity, destination_entity| {
-                let source_component = source_world.get::<C>(source_entity).unwrap();
-                let mut destination_component = C::from_world(destination_world);
-                destination_component.apply(source_component);
-                destination_world
-                    .entity_mut(destination_entity)
-                    .insert(destination_component);
-            },
+            copy_component,
             reflect_component: |world, entity| {
                 world
                     .get_entity(entity)?
@@ -118,7 +180,116 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
     }
 }
 
+fn from_reflect_component<C: FromReflect>(
+    reflected_component: &dyn Reflect,
+) -> Result<C, ReflectComponentError> {
+    C::from_reflect(reflected_component).ok_or_else(|| ReflectComponentError::FromReflect {
+        type_name: std::any::type_name::<C>(),
+        value_type_name: reflected_component.type_name().to_string(),
+    })
+}
+
+impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
+    fn from_type() -> Self {
+        ReflectComponent::new::<C>(
+            |world, entity, reflected_component| {
+                let mut component = C::from_world(world);
+                component.apply(reflected_component);
+                world.entity_mut(entity).insert(component);
+                Ok(())
+            },
+            |source_world, destination_world, source_entity, destination_entity| {
+                let source_component = source_world.get::<C>(source_entity).unwrap();
+                let mut destination_component = C::from_world(destination_world);
+                destination_component.apply(source_component);
+                destination_world
+                    .entity_mut(destination_entity)
+                    .insert(destination_component);
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ReflectResource {
+    insert_resource: fn(&mut World, &dyn Reflect),
+    apply_resource: fn(&mut World, &dyn Reflect),
+    remove_resource: fn(&mut World),
+    reflect_resource: fn(&World) -> Option<&dyn Reflect>,
+    reflect_resource_mut: fn(&mut World) -> Option<ReflectMut>,
+    copy_resource: fn(&World, &mut World),
+}
+
+impl ReflectResource {
+    /// Inserts the resource into `world`, replacing the existing resource of the same type.
+    pub fn insert_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.insert_resource)(world, resource);
+    }
+
+    /// Applies `resource` to the existing resource of the same type.
+    ///
+    /// # Panics
+    /// Panics if `world` does not have a resource of this type.
+    pub fn apply_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.apply_resource)(world, resource);
+    }
+
+    pub fn remove_resource(&self, world: &mut World) {
+        (self.remove_resource)(world);
+    }
+
+    pub fn reflect_resource<'a>(&self, world: &'a World) -> Option<&'a dyn Reflect> {
+        (self.reflect_resource)(world)
+    }
+
+    pub fn reflect_resource_mut<'a>(&self, world: &'a mut World) -> Option<ReflectMut<'a>> {
+        (self.reflect_resource_mut)(world)
+    }
+
+    /// Copies the resource of `source_world` into `destination_world`.
+    ///
+    /// # Panics
+    /// Panics if `source_world` does not have a resource of this type.
+    pub fn copy_resource(&self, source_world: &World, destination_world: &mut World) {
+        (self.copy_resource)(source_world, destination_world);
+    }
+}
+
+impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectResource {
+    fn from_type() -> Self {
+        ReflectResource {
+            insert_resource: |world, reflected_resource| {
+                let mut resource = C::from_world(world);
+                resource.apply(reflected_resource);
+                world.insert_resource(resource);
+            },
+            apply_resource: |world, reflected_resource| {
+                let mut resource = world.get_resource_mut::<C>().unwrap();
+                resource.apply(reflected_resource);
+            },
+            remove_resource: |world| {
+                world.remove_resource::<C>();
+            },
+            reflect_resource: |world| world.get_resource::<C>().map(|r| r as &dyn Reflect),
+            reflect_resource_mut: |world| {
+                world.get_resource_mut::<C>().map(|r| ReflectMut {
+                    value: r.value as &mut dyn Reflect,
+                    ticks: r.ticks,
+                })
+            },
+            copy_resource: |source_world, destination_world| {
+                let source_resource = source_world.get_resource::<C>().unwrap();
+                let mut destination_resource = C::from_world(destination_world);
+                destination_resource.apply(source_resource);
+                destination_world.insert_resource(destination_resource);
+            },
+        }
+    }
+}
+
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
+impl_from_reflect_value!(Entity);
 
 #[derive(Clone)]
 pub struct ReflectMapEntities {
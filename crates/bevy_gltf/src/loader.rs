@@ -1,6 +1,7 @@
 use anyhow::Result;
 use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    deserialize_ron_settings, AssetIoError, AssetLoader, AssetLoaderSettings, AssetPath,
+    BoxedFuture, Handle, LoadContext, LoadedAsset,
 };
 use bevy_core::Name;
 use bevy_ecs::world::World;
@@ -27,6 +28,7 @@ use gltf::{
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
@@ -60,6 +62,15 @@ pub enum GltfError {
 #[derive(Default)]
 pub struct GltfLoader;
 
+/// Settings for [`GltfLoader`], read from the `.meta` file next to a GLTF file, e.g.
+/// `(skip_cameras: true)`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GltfLoaderSettings {
+    /// Does not add the cameras of the GLTF file to its scenes.
+    pub skip_cameras: bool,
+}
+
 impl AssetLoader for GltfLoader {
     fn load<'a>(
         &'a self,
@@ -72,6 +83,14 @@ impl AssetLoader for GltfLoader {
     fn extensions(&self) -> &[&str] {
         &["gltf", "glb"]
     }
+
+    fn deserialize_settings(&self, meta: &[u8]) -> Result<Option<Box<dyn AssetLoaderSettings>>> {
+        deserialize_ron_settings::<GltfLoaderSettings>(meta)
+    }
+
+    fn has_settings(&self) -> bool {
+        true
+    }
 }
 
 async fn load_gltf<'a, 'b>(
@@ -79,6 +98,10 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
+    let settings = load_context
+        .settings::<GltfLoaderSettings>()
+        .cloned()
+        .unwrap_or_default();
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
     let mut materials = vec![];
@@ -283,7 +306,7 @@ async fn load_gltf<'a, 'b>(
             .insert_bundle((Transform::identity(), GlobalTransform::identity()))
             .with_children(|parent| {
                 for node in scene.nodes() {
-                    let result = load_node(&node, parent, load_context, &buffer_data);
+                    let result = load_node(&node, parent, load_context, &buffer_data, &settings);
                     if result.is_err() {
                         err = Some(result);
                         return;
@@ -447,6 +470,7 @@ fn load_node(
     world_builder: &mut WorldChildBuilder,
     load_context: &mut LoadContext,
     buffer_data: &[Vec<u8>],
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -460,7 +484,7 @@ fn load_node(
     }
 
     // create camera node
-    if let Some(camera) = gltf_node.camera() {
+    if let Some(camera) = gltf_node.camera().filter(|_| !settings.skip_cameras) {
         node.insert(VisibleEntities {
             ..Default::default()
         });
@@ -538,7 +562,7 @@ fn load_node(
 
         // append other nodes
         for child in gltf_node.children() {
-            if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
+            if let Err(err) = load_node(&child, parent, load_context, buffer_data, settings) {
                 gltf_error = Some(err);
                 return;
             }
@@ -661,7 +685,6 @@ async fn load_buffers(
                     Ok(data_uri) if data_uri.mime_type == OCTET_STREAM_URI => data_uri.decode()?,
                     Ok(_) => return Err(GltfError::BufferFormatUnsupported),
                     Err(()) => {
-                        // TODO: Remove this and add dep
                         let buffer_path = asset_path.parent().unwrap().join(uri);
                         let buffer_bytes = load_context.read_asset_bytes(buffer_path).await?;
                         buffer_bytes
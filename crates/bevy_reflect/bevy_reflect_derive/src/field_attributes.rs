@@ -0,0 +1,135 @@
+use crate::REFLECT_ATTRIBUTE_NAME;
+use quote::quote;
+use syn::{Field, Lit, Meta, NestedMeta, Path};
+
+static DOC_ATTRIBUTE_NAME: &str = "doc";
+
+/// The `#[reflect(...)]` attributes and doc comments of a field, like
+/// `#[reflect(range(0.0, 1.0), step = 0.1, units = "m", hidden, read_only)]`.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    pub ignore: bool,
+    pub range: Option<(f64, f64)>,
+    pub step: Option<f64>,
+    pub docs: Option<String>,
+    pub hidden: bool,
+    pub read_only: bool,
+    pub units: Option<String>,
+}
+
+impl FieldAttrs {
+    pub fn from_field(field: &Field) -> Self {
+        let mut attrs = FieldAttrs::default();
+        let mut doc_lines = Vec::new();
+        for attribute in field.attrs.iter() {
+            if attribute.path.is_ident(DOC_ATTRIBUTE_NAME) {
+                if let Ok(Meta::NameValue(name_value)) = attribute.parse_meta() {
+                    if let Lit::Str(doc) = name_value.lit {
+                        let doc = doc.value();
+                        doc_lines.push(doc.strip_prefix(' ').unwrap_or(&doc).to_string());
+                    }
+                }
+            } else if attribute.path.is_ident(REFLECT_ATTRIBUTE_NAME) {
+                let meta_list = match attribute.parse_meta() {
+                    Ok(Meta::List(meta_list)) => meta_list,
+                    _ => panic!("Invalid 'reflect' attribute format."),
+                };
+                for nested_meta in meta_list.nested.iter() {
+                    match nested_meta {
+                        NestedMeta::Meta(meta) => attrs.parse_meta(meta),
+                        NestedMeta::Lit(_) => panic!("Invalid 'reflect' attribute format."),
+                    }
+                }
+            }
+        }
+
+        let docs = doc_lines.join("\n");
+        if !docs.trim().is_empty() {
+            attrs.docs = Some(docs.trim().to_string());
+        }
+        attrs
+    }
+
+    fn parse_meta(&mut self, meta: &Meta) {
+        let ident = meta
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        match (ident.as_str(), meta) {
+            ("ignore", Meta::Path(_)) => self.ignore = true,
+            ("hidden", Meta::Path(_)) => self.hidden = true,
+            ("read_only", Meta::Path(_)) => self.read_only = true,
+            ("range", Meta::List(meta_list)) if meta_list.nested.len() == 2 => {
+                let mut bounds = meta_list
+                    .nested
+                    .iter()
+                    .map(|nested_meta| match nested_meta {
+                        NestedMeta::Lit(lit) => lit_to_f64(lit),
+                        _ => None,
+                    });
+                match (bounds.next().flatten(), bounds.next().flatten()) {
+                    (Some(min), Some(max)) => self.range = Some((min, max)),
+                    _ => panic!(
+                        "`range` attribute must take the form `#[reflect(range(min, max))]`."
+                    ),
+                }
+            }
+            ("step", Meta::NameValue(name_value)) => match lit_to_f64(&name_value.lit) {
+                Some(step) => self.step = Some(step),
+                None => panic!("`step` attribute must take the form `#[reflect(step = 0.1)]`."),
+            },
+            ("units", Meta::NameValue(name_value)) => match &name_value.lit {
+                Lit::Str(units) => self.units = Some(units.value()),
+                _ => panic!("`units` attribute must take the form `#[reflect(units = \"m\")]`."),
+            },
+            _ => panic!("Unknown 'reflect' field attribute `{}`.", ident),
+        }
+    }
+
+    /// Returns the `FieldAttributes` expression of the field, or `None` if the field has no
+    /// metadata.
+    pub fn to_attributes(&self, bevy_reflect_path: &Path) -> Option<proc_macro2::TokenStream> {
+        if self.range.is_none()
+            && self.step.is_none()
+            && self.docs.is_none()
+            && !self.hidden
+            && !self.read_only
+            && self.units.is_none()
+        {
+            return None;
+        }
+
+        let range = option_tokens(self.range.map(|(min, max)| quote!((#min, #max))));
+        let step = option_tokens(self.step.map(|step| quote!(#step)));
+        let docs = option_tokens(self.docs.as_ref().map(|docs| quote!(#docs)));
+        let units = option_tokens(self.units.as_ref().map(|units| quote!(#units)));
+        let hidden = self.hidden;
+        let read_only = self.read_only;
+        Some(quote! {
+            #bevy_reflect_path::FieldAttributes {
+                range: #range,
+                step: #step,
+                docs: #docs,
+                hidden: #hidden,
+                read_only: #read_only,
+                units: #units,
+            }
+        })
+    }
+}
+
+fn lit_to_f64(lit: &Lit) -> Option<f64> {
+    match lit {
+        Lit::Float(float) => float.base10_parse().ok(),
+        Lit::Int(int) => int.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+fn option_tokens(value: Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
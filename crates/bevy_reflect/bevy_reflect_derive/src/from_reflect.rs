@@ -0,0 +1,175 @@
+use crate::{active_fields, ReflectDef, REFLECT_VALUE_ATTRIBUTE_NAME};
+use bevy_macro_utils::BevyManifest;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, token::Comma, Data, DeriveInput, Field, Fields,
+    Generics, Ident, Index, Member, Path,
+};
+
+pub fn derive_from_reflect(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
+    let type_name = &ast.ident;
+
+    let is_value = ast
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident(REFLECT_VALUE_ATTRIBUTE_NAME));
+    if is_value {
+        return impl_value(type_name, &ast.generics, &bevy_reflect_path);
+    }
+
+    match &ast.data {
+        Data::Struct(data_struct) => impl_struct(
+            type_name,
+            &ast.generics,
+            &bevy_reflect_path,
+            &data_struct.fields,
+        ),
+        Data::Enum(data_enum) => {
+            let variant_arms = data_enum
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let variant_name = variant_ident.to_string();
+                    let constructor = construct_fields(
+                        quote!(#type_name::#variant_ident),
+                        &bevy_reflect_path,
+                        &variant.fields,
+                        |name| quote!(ref_enum.field(#name)),
+                        |index| quote!(ref_enum.field_at(#index)),
+                    );
+                    quote!(#variant_name => Some(#constructor),)
+                })
+                .collect::<Vec<_>>();
+
+            impl_from_reflect(
+                type_name,
+                &ast.generics,
+                &bevy_reflect_path,
+                quote! {
+                    if let #bevy_reflect_path::ReflectRef::Enum(ref_enum) = reflect.reflect_ref() {
+                        match ref_enum.variant_name() {
+                            #(#variant_arms)*
+                            _ => None,
+                        }
+                    } else {
+                        None
+                    }
+                },
+            )
+        }
+        Data::Union(_) => impl_value(type_name, &ast.generics, &bevy_reflect_path),
+    }
+}
+
+pub fn impl_from_reflect_value(input: TokenStream) -> TokenStream {
+    let reflect_value_def = parse_macro_input!(input as ReflectDef);
+    let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
+    impl_value(
+        &reflect_value_def.type_name,
+        &reflect_value_def.generics,
+        &bevy_reflect_path,
+    )
+}
+
+fn impl_struct(
+    struct_name: &Ident,
+    generics: &Generics,
+    bevy_reflect_path: &Path,
+    fields: &Fields,
+) -> TokenStream {
+    let constructor = construct_fields(
+        quote!(#struct_name),
+        bevy_reflect_path,
+        fields,
+        |name| quote!(ref_struct.field(#name)),
+        |index| quote!(ref_struct.field(#index)),
+    );
+    let reflect_ref_variant = match fields {
+        Fields::Unnamed(_) => quote!(TupleStruct),
+        _ => quote!(Struct),
+    };
+
+    impl_from_reflect(
+        struct_name,
+        generics,
+        bevy_reflect_path,
+        quote! {
+            if let #bevy_reflect_path::ReflectRef::#reflect_ref_variant(ref_struct) = reflect.reflect_ref() {
+                Some(#constructor)
+            } else {
+                None
+            }
+        },
+    )
+}
+
+fn impl_value(type_name: &Ident, generics: &Generics, bevy_reflect_path: &Path) -> TokenStream {
+    impl_from_reflect(
+        type_name,
+        generics,
+        bevy_reflect_path,
+        quote! {
+            Some(reflect.any().downcast_ref::<Self>()?.clone())
+        },
+    )
+}
+
+fn impl_from_reflect(
+    type_name: &Ident,
+    generics: &Generics,
+    bevy_reflect_path: &Path,
+    body: proc_macro2::TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    TokenStream::from(quote! {
+        impl #impl_generics #bevy_reflect_path::FromReflect for #type_name#ty_generics #where_clause {
+            fn from_reflect(reflect: &dyn #bevy_reflect_path::Reflect) -> Option<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// Constructs a struct or enum variant with `path`, reading the active fields by name for named
+/// fields, or by their index among the active fields for unnamed fields.
+fn construct_fields(
+    path: proc_macro2::TokenStream,
+    bevy_reflect_path: &Path,
+    fields: &Fields,
+    get_named: impl Fn(&str) -> proc_macro2::TokenStream,
+    get_unnamed: impl Fn(usize) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let empty = Punctuated::<Field, Comma>::new();
+    let fields = match fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) => &fields.unnamed,
+        Fields::Unit => &empty,
+    };
+    let active_fields = active_fields(fields);
+
+    let mut active_index = 0;
+    let field_values = fields.iter().enumerate().map(|(index, field)| {
+        let member = field
+            .ident
+            .as_ref()
+            .map(|ident| Member::Named(ident.clone()))
+            .unwrap_or_else(|| Member::Unnamed(Index::from(index)));
+        if !active_fields.iter().any(|(_field, i)| *i == index) {
+            return quote!(#member: ::std::default::Default::default());
+        }
+
+        let field_type = &field.ty;
+        let get_field = match &field.ident {
+            Some(ident) => get_named(&ident.to_string()),
+            None => get_unnamed(active_index),
+        };
+        active_index += 1;
+        quote!(#member: <#field_type as #bevy_reflect_path::FromReflect>::from_reflect(#get_field?)?)
+    });
+
+    quote!(#path { #(#field_values,)* })
+}
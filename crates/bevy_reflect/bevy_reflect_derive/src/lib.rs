@@ -1,27 +1,25 @@
 extern crate proc_macro;
 
+mod field_attributes;
+mod from_reflect;
 mod reflect_trait;
 mod type_uuid;
 
 use bevy_macro_utils::BevyManifest;
+use field_attributes::FieldAttrs;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
     token::{Comma, Paren, Where},
-    Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Meta, NestedMeta,
-    Path,
+    Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Meta,
+    NestedMeta, Path,
 };
 
-#[derive(Default)]
-struct PropAttributeArgs {
-    pub ignore: Option<bool>,
-}
-
 #[derive(Clone)]
 enum TraitImpl {
     NotImplemented,
@@ -39,6 +37,7 @@ enum DeriveType {
     Struct,
     TupleStruct,
     UnitStruct,
+    Enum,
     Value,
 }
 
@@ -62,47 +61,11 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             fields: Fields::Unit,
             ..
         }) => (&unit_struct_punctuated, DeriveType::UnitStruct),
+        Data::Enum(_) => (&unit_struct_punctuated, DeriveType::Enum),
         _ => (&unit_struct_punctuated, DeriveType::Value),
     };
 
-    let fields_and_args = fields
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            (
-                f,
-                f.attrs
-                    .iter()
-                    .find(|a| *a.path.get_ident().as_ref().unwrap() == REFLECT_ATTRIBUTE_NAME)
-                    .map(|a| {
-                        syn::custom_keyword!(ignore);
-                        let mut attribute_args = PropAttributeArgs { ignore: None };
-                        a.parse_args_with(|input: ParseStream| {
-                            if input.parse::<Option<ignore>>()?.is_some() {
-                                attribute_args.ignore = Some(true);
-                                return Ok(());
-                            }
-                            Ok(())
-                        })
-                        .expect("Invalid 'property' attribute format.");
-
-                        attribute_args
-                    }),
-                i,
-            )
-        })
-        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
-    let active_fields = fields_and_args
-        .iter()
-        .filter(|(_field, attrs, _i)| {
-            attrs.is_none()
-                || match attrs.as_ref().unwrap().ignore {
-                    Some(ignore) => !ignore,
-                    None => true,
-                }
-        })
-        .map(|(f, _attr, i)| (*f, *i))
-        .collect::<Vec<(&Field, usize)>>();
+    let active_fields = active_fields(fields);
 
     let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
     let type_name = &ast.ident;
@@ -125,12 +88,17 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
         }
     }
 
-    let registration_data = &reflect_attrs.data;
+    let data_enum = match &ast.data {
+        Data::Enum(data_enum) => Some(data_enum),
+        _ => None,
+    };
+    let type_info = impl_type_info(&bevy_reflect_path, &derive_type, &active_fields, data_enum);
     let get_type_registration_impl = impl_get_type_registration(
         type_name,
         &bevy_reflect_path,
-        registration_data,
+        &reflect_attrs,
         &ast.generics,
+        type_info,
     );
 
     match derive_type {
@@ -150,6 +118,17 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             &reflect_attrs,
             &active_fields,
         ),
+        DeriveType::Enum => match &ast.data {
+            Data::Enum(data_enum) => impl_enum(
+                type_name,
+                &ast.generics,
+                get_type_registration_impl,
+                &bevy_reflect_path,
+                &reflect_attrs,
+                data_enum,
+            ),
+            _ => unreachable!(),
+        },
         DeriveType::Value => impl_value(
             type_name,
             &ast.generics,
@@ -160,6 +139,16 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Returns the fields without `#[reflect(ignore)]`, with their index in `fields`.
+fn active_fields(fields: &Punctuated<Field, Comma>) -> Vec<(&Field, usize)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_i, field)| !FieldAttrs::from_field(field).ignore)
+        .map(|(i, field)| (field, i))
+        .collect()
+}
+
 fn impl_struct(
     struct_name: &Ident,
     generics: &Generics,
@@ -252,7 +241,7 @@ fn impl_struct(
 
             fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicStruct {
                 let mut dynamic = #bevy_reflect_path::DynamicStruct::default();
-                dynamic.set_name(self.type_name().to_string());
+                dynamic.set_name(#bevy_reflect_path::Reflect::type_name(self).to_string());
                 #(dynamic.insert_boxed(#field_names, self.#field_idents.clone_value());)*
                 dynamic
             }
@@ -374,7 +363,7 @@ fn impl_tuple_struct(
 
             fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicTupleStruct {
                 let mut dynamic = #bevy_reflect_path::DynamicTupleStruct::default();
-                dynamic.set_name(self.type_name().to_string());
+                dynamic.set_name(#bevy_reflect_path::Reflect::type_name(self).to_string());
                 #(dynamic.insert_boxed(self.#field_idents.clone_value());)*
                 dynamic
             }
@@ -441,6 +430,324 @@ fn impl_tuple_struct(
     })
 }
 
+fn impl_enum(
+    enum_name: &Ident,
+    generics: &Generics,
+    get_type_registration_impl: proc_macro2::TokenStream,
+    bevy_reflect_path: &Path,
+    reflect_attrs: &ReflectAttrs,
+    data_enum: &DataEnum,
+) -> TokenStream {
+    let mut variant_arms = Vec::new();
+    let mut field_arms = Vec::new();
+    let mut field_mut_arms = Vec::new();
+    let mut field_at_arms = Vec::new();
+    let mut field_at_mut_arms = Vec::new();
+    let mut name_at_arms = Vec::new();
+    let mut clone_dynamic_arms = Vec::new();
+    let mut variant_constructors = Vec::new();
+    let mut variant_names = Vec::new();
+    let mut variant_indices = Vec::new();
+    let mut variant_types = Vec::new();
+    let mut field_counts = Vec::new();
+
+    for (variant_index, variant) in data_enum.variants.iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let variant_pattern = quote!(#enum_name::#variant_ident);
+        let all_members = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                field
+                    .ident
+                    .as_ref()
+                    .map(|ident| Member::Named(ident.clone()))
+                    .unwrap_or_else(|| Member::Unnamed(Index::from(index)))
+            })
+            .collect::<Vec<_>>();
+        let active_fields = match &variant.fields {
+            Fields::Named(fields) => active_fields(&fields.named),
+            Fields::Unnamed(fields) => active_fields(&fields.unnamed),
+            Fields::Unit => Vec::new(),
+        };
+        let field_members = active_fields
+            .iter()
+            .map(|(_field, index)| all_members[*index].clone())
+            .collect::<Vec<_>>();
+        let field_names = active_fields
+            .iter()
+            .map(|(field, _index)| field.ident.as_ref().map(|ident| ident.to_string()))
+            .collect::<Vec<_>>();
+        let field_bindings = (0..active_fields.len())
+            .map(|index| format_ident!("__field_{}", index))
+            .collect::<Vec<_>>();
+
+        let (variant_type, dynamic_variant) = match &variant.fields {
+            Fields::Named(_) => {
+                let names = field_names.iter().flatten().collect::<Vec<_>>();
+                (
+                    quote!(Struct),
+                    quote! {{
+                        let mut fields = #bevy_reflect_path::DynamicStruct::default();
+                        #(fields.insert_boxed(#names, #bevy_reflect_path::Reflect::clone_value(#field_bindings));)*
+                        #bevy_reflect_path::DynamicVariant::Struct(fields)
+                    }},
+                )
+            }
+            Fields::Unnamed(_) => (
+                quote!(Tuple),
+                quote! {{
+                    let mut fields = #bevy_reflect_path::DynamicTuple::default();
+                    #(fields.insert_boxed(#bevy_reflect_path::Reflect::clone_value(#field_bindings));)*
+                    #bevy_reflect_path::DynamicVariant::Tuple(fields)
+                }},
+            ),
+            Fields::Unit => (
+                quote!(Unit),
+                quote!(#bevy_reflect_path::DynamicVariant::Unit),
+            ),
+        };
+
+        for (field_index, (member, name)) in field_members.iter().zip(&field_names).enumerate() {
+            if let Some(name) = name {
+                field_arms
+                    .push(quote!((#variant_pattern { #member: value, .. }, #name) => Some(value),));
+                field_mut_arms
+                    .push(quote!((#variant_pattern { #member: value, .. }, #name) => Some(value),));
+                name_at_arms.push(quote!((#variant_pattern { .. }, #field_index) => Some(#name),));
+            }
+            field_at_arms.push(
+                quote!((#variant_pattern { #member: value, .. }, #field_index) => Some(value),),
+            );
+            field_at_mut_arms.push(
+                quote!((#variant_pattern { #member: value, .. }, #field_index) => Some(value),),
+            );
+        }
+
+        clone_dynamic_arms.push(quote! {
+            #variant_pattern { #(#field_members: #field_bindings,)* .. } => {
+                dynamic.set_variant_with_index(#variant_index, #variant_name, #dynamic_variant);
+            }
+        });
+        // the fields of the new variant are constructed with their `Default` or `FromReflect`
+        // implementations, and the variant is not switched to if one of them has neither
+        let field_types = variant.fields.iter().map(|field| &field.ty);
+        let constructed_fields = (0..all_members.len())
+            .map(|index| format_ident!("__constructed_{}", index))
+            .collect::<Vec<_>>();
+        let applied_fields = (0..all_members.len()).map(|index| {
+            match active_fields
+                .iter()
+                .position(|(_field, active_index)| *active_index == index)
+            {
+                Some(active_position) => match &field_names[active_position] {
+                    Some(name) => quote!(enum_value.field(#name)),
+                    None => quote!(enum_value.field_at(#active_position)),
+                },
+                None => quote!(None),
+            }
+        });
+        variant_constructors.push(quote! {
+            #variant_name => {
+                #(
+                    let #constructed_fields = match (&&#bevy_reflect_path::VariantField::<#field_types>::new())
+                        .construct_field(#applied_fields)
+                    {
+                        Some(value) => value,
+                        None => return,
+                    };
+                )*
+                #variant_pattern { #(#all_members: #constructed_fields,)* }
+            }
+        });
+        variant_arms.push(quote!(#variant_pattern { .. }));
+        variant_names.push(variant_name);
+        variant_indices.push(variant_index);
+        variant_types.push(variant_type);
+        field_counts.push(active_fields.len());
+    }
+
+    // the value of an enum without variants cannot exist, which the match on it proves without
+    // leaving the rest of the function unreachable
+    let (clone_dynamic_body, apply_body) = if data_enum.variants.is_empty() {
+        (
+            quote!(match *self {}),
+            quote! {
+                let _ = value;
+                match *self {}
+            },
+        )
+    } else {
+        (
+            quote! {
+                let mut dynamic = #bevy_reflect_path::DynamicEnum::default();
+                dynamic.set_name(#bevy_reflect_path::Reflect::type_name(self).to_string());
+                match self {
+                    #(#clone_dynamic_arms)*
+                }
+                dynamic
+            },
+            quote! {
+                #[allow(unused_imports)]
+                use #bevy_reflect_path::{ConstructFieldFromReflect, ConstructFieldWithDefault, ConstructNoField};
+                if let #bevy_reflect_path::ReflectRef::Enum(enum_value) = value.reflect_ref() {
+                    if #bevy_reflect_path::Enum::variant_name(self) != enum_value.variant_name() {
+                        *self = match enum_value.variant_name() {
+                            #(#variant_constructors)*
+                            _ => return,
+                        };
+                    }
+                    #bevy_reflect_path::apply_variant_fields(self, enum_value);
+                } else {
+                    panic!("Attempted to apply non-enum type to enum type.");
+                }
+            },
+        )
+    };
+
+    let hash_fn = reflect_attrs.get_hash_impl(bevy_reflect_path);
+    let serialize_fn = reflect_attrs.get_serialize_impl(bevy_reflect_path);
+    let partial_eq_fn = match reflect_attrs.reflect_partial_eq {
+        TraitImpl::NotImplemented => quote! {
+            #bevy_reflect_path::enum_partial_eq(self, value)
+        },
+        TraitImpl::Implemented | TraitImpl::Custom(_) => reflect_attrs.get_partial_eq_impl(),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #get_type_registration_impl
+
+        impl #impl_generics #bevy_reflect_path::Enum for #enum_name#ty_generics #where_clause {
+            fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match (self, name) {
+                    #(#field_arms)*
+                    _ => None,
+                }
+            }
+
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match (self, name) {
+                    #(#field_mut_arms)*
+                    _ => None,
+                }
+            }
+
+            fn field_at(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match (self, index) {
+                    #(#field_at_arms)*
+                    _ => None,
+                }
+            }
+
+            fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match (self, index) {
+                    #(#field_at_mut_arms)*
+                    _ => None,
+                }
+            }
+
+            fn name_at(&self, index: usize) -> Option<&str> {
+                match (self, index) {
+                    #(#name_at_arms)*
+                    _ => None,
+                }
+            }
+
+            fn field_len(&self) -> usize {
+                match *self {
+                    #(#variant_arms => #field_counts,)*
+                }
+            }
+
+            fn iter_fields(&self) -> #bevy_reflect_path::VariantFieldIter {
+                #bevy_reflect_path::VariantFieldIter::new(self)
+            }
+
+            fn variant_name(&self) -> &str {
+                match *self {
+                    #(#variant_arms => #variant_names,)*
+                }
+            }
+
+            fn variant_index(&self) -> usize {
+                match *self {
+                    #(#variant_arms => #variant_indices,)*
+                }
+            }
+
+            fn variant_type(&self) -> #bevy_reflect_path::VariantType {
+                match *self {
+                    #(#variant_arms => #bevy_reflect_path::VariantType::#variant_types,)*
+                }
+            }
+
+            fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicEnum {
+                #clone_dynamic_body
+            }
+        }
+
+        // SAFE: any and any_mut both return self
+        unsafe impl #impl_generics #bevy_reflect_path::Reflect for #enum_name#ty_generics #where_clause {
+            #[inline]
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            #[inline]
+            fn any(&self) -> &dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn clone_value(&self) -> Box<dyn #bevy_reflect_path::Reflect> {
+                Box::new(#bevy_reflect_path::Enum::clone_dynamic(self))
+            }
+            #[inline]
+            fn set(&mut self, value: Box<dyn #bevy_reflect_path::Reflect>) -> Result<(), Box<dyn #bevy_reflect_path::Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            /// Switching to another variant constructs its fields from their default values, or
+            /// with `FromReflect` from the fields of `value` for fields without a default, before
+            /// the fields of `value` are applied. Variants that do not exist on this enum, or
+            /// whose fields cannot be constructed, are ignored, like the fields that do not exist
+            /// on a struct.
+            #[inline]
+            fn apply(&mut self, value: &dyn #bevy_reflect_path::Reflect) {
+                #apply_body
+            }
+
+            fn reflect_ref(&self) -> #bevy_reflect_path::ReflectRef {
+                #bevy_reflect_path::ReflectRef::Enum(self)
+            }
+
+            fn reflect_mut(&mut self) -> #bevy_reflect_path::ReflectMut {
+                #bevy_reflect_path::ReflectMut::Enum(self)
+            }
+
+            fn serializable(&self) -> Option<#bevy_reflect_path::serde::Serializable> {
+                #serialize_fn
+            }
+
+            fn reflect_hash(&self) -> Option<u64> {
+                #hash_fn
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn #bevy_reflect_path::Reflect) -> Option<bool> {
+                #partial_eq_fn
+            }
+        }
+    })
+}
+
 fn impl_value(
     type_name: &Ident,
     generics: &Generics,
@@ -560,12 +867,13 @@ pub fn impl_reflect_value(input: TokenStream) -> TokenStream {
     let reflect_attrs = reflect_value_def
         .attrs
         .unwrap_or_else(ReflectAttrs::default);
-    let registration_data = &reflect_attrs.data;
+    let type_info = impl_type_info(&bevy_reflect_path, &DeriveType::Value, &[], None);
     let get_type_registration_impl = impl_get_type_registration(
         ty,
         &bevy_reflect_path,
-        registration_data,
+        &reflect_attrs,
         &reflect_value_def.generics,
+        type_info,
     );
     impl_value(
         ty,
@@ -582,6 +890,9 @@ struct ReflectAttrs {
     reflect_partial_eq: TraitImpl,
     serialize: TraitImpl,
     data: Vec<Ident>,
+    /// Type data registered with a constructor instead of `FromType`, like
+    /// `#[reflect(Component(from_reflect))]`
+    constructed_data: Vec<(Ident, Ident)>,
 }
 
 impl ReflectAttrs {
@@ -630,7 +941,13 @@ impl ReflectAttrs {
                                                     attrs.serialize =
                                                         TraitImpl::Custom(segment.ident.clone())
                                                 }
-                                                _ => {}
+                                                _ => attrs.constructed_data.push((
+                                                    Ident::new(
+                                                        &format!("Reflect{}", ident),
+                                                        Span::call_site(),
+                                                    ),
+                                                    segment.ident.clone(),
+                                                )),
                                             }
                                         }
                                     }
@@ -708,25 +1025,127 @@ impl Parse for ReflectAttrs {
     }
 }
 
+/// Builds the [`TypeInfo`] expression of the `Typed` impl. Only active fields are described.
+fn impl_type_info(
+    bevy_reflect_path: &Path,
+    derive_type: &DeriveType,
+    active_fields: &[(&Field, usize)],
+    data_enum: Option<&DataEnum>,
+) -> proc_macro2::TokenStream {
+    match (derive_type, data_enum) {
+        (DeriveType::Struct | DeriveType::UnitStruct, _) => {
+            let fields = fields_info(bevy_reflect_path, active_fields);
+            quote! {
+                #bevy_reflect_path::TypeInfo::Struct(#bevy_reflect_path::StructInfo {
+                    type_name: std::any::type_name::<Self>(),
+                    fields: vec![#(#fields,)*],
+                })
+            }
+        }
+        (DeriveType::TupleStruct, _) => {
+            let fields = fields_info(bevy_reflect_path, active_fields);
+            quote! {
+                #bevy_reflect_path::TypeInfo::TupleStruct(#bevy_reflect_path::TupleStructInfo {
+                    type_name: std::any::type_name::<Self>(),
+                    fields: vec![#(#fields,)*],
+                })
+            }
+        }
+        (DeriveType::Enum, Some(data_enum)) => {
+            let variants = data_enum.variants.iter().map(|variant| {
+                let name = variant.ident.to_string();
+                let (variant_type, variant_fields) = match &variant.fields {
+                    Fields::Named(fields) => (quote!(Struct), self::active_fields(&fields.named)),
+                    Fields::Unnamed(fields) => {
+                        (quote!(Tuple), self::active_fields(&fields.unnamed))
+                    }
+                    Fields::Unit => (quote!(Unit), Vec::new()),
+                };
+                let fields = fields_info(bevy_reflect_path, &variant_fields);
+                quote! {
+                    #bevy_reflect_path::VariantInfo {
+                        name: #name,
+                        variant_type: #bevy_reflect_path::VariantType::#variant_type,
+                        fields: vec![#(#fields,)*],
+                    }
+                }
+            });
+            quote! {
+                #bevy_reflect_path::TypeInfo::Enum(#bevy_reflect_path::EnumInfo {
+                    type_name: std::any::type_name::<Self>(),
+                    variants: vec![#(#variants,)*],
+                })
+            }
+        }
+        _ => quote! {
+            #bevy_reflect_path::TypeInfo::Value(#bevy_reflect_path::ValueInfo::of::<Self>())
+        },
+    }
+}
+
+fn fields_info(
+    bevy_reflect_path: &Path,
+    active_fields: &[(&Field, usize)],
+) -> Vec<proc_macro2::TokenStream> {
+    active_fields
+        .iter()
+        .map(|(field, _index)| {
+            let ty = &field.ty;
+            let field_info = match &field.ident {
+                Some(ident) => {
+                    let name = ident.to_string();
+                    quote!(#bevy_reflect_path::FieldInfo::named::<#ty>(#name))
+                }
+                None => quote!(#bevy_reflect_path::FieldInfo::unnamed::<#ty>()),
+            };
+            match FieldAttrs::from_field(field).to_attributes(bevy_reflect_path) {
+                Some(attributes) => quote!(#field_info.with_attributes(#attributes)),
+                None => field_info,
+            }
+        })
+        .collect()
+}
+
 fn impl_get_type_registration(
     type_name: &Ident,
     bevy_reflect_path: &Path,
-    registration_data: &[Ident],
+    reflect_attrs: &ReflectAttrs,
     generics: &Generics,
+    type_info: proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
+    let registration_data = &reflect_attrs.data;
+    let (constructed_data, data_constructors): (Vec<_>, Vec<_>) =
+        reflect_attrs.constructed_data.iter().cloned().unzip();
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote! {
+        impl #impl_generics #bevy_reflect_path::Typed for #type_name#ty_generics #where_clause {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #type_info
+            }
+        }
+
         #[allow(unused_mut)]
         impl #impl_generics #bevy_reflect_path::GetTypeRegistration for #type_name#ty_generics #where_clause {
             fn get_type_registration() -> #bevy_reflect_path::TypeRegistration {
                 let mut registration = #bevy_reflect_path::TypeRegistration::of::<#type_name#ty_generics>();
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<#type_name#ty_generics>::from_type());)*
+                #(registration.insert::<#constructed_data>(#constructed_data::#data_constructors::<#type_name#ty_generics>());)*
                 registration
             }
         }
     }
 }
 
+#[proc_macro_derive(FromReflect, attributes(reflect, reflect_value))]
+pub fn derive_from_reflect(input: TokenStream) -> TokenStream {
+    from_reflect::derive_from_reflect(input)
+}
+
+#[proc_macro]
+pub fn impl_from_reflect_value(input: TokenStream) -> TokenStream {
+    from_reflect::impl_from_reflect_value(input)
+}
+
 // From https://github.com/randomPoison/type-uuid
 #[proc_macro_derive(TypeUuid, attributes(uuid))]
 pub fn type_uuid_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -0,0 +1,491 @@
+use crate::{Reflect, ReflectFromReflect, ReflectMut, ReflectRef, TypeRegistry, VariantType};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// A step of the path from a value to one of the values it contains.
+pub enum PathSegment {
+    /// A field of a struct or struct variant, or the field of a tuple struct, tuple or tuple
+    /// variant with the given index, like `"0"`
+    Field(String),
+    /// An item of a list
+    Index(usize),
+    /// The value of the map entry with the given key
+    Key(Box<dyn Reflect>),
+}
+
+impl Clone for PathSegment {
+    fn clone(&self) -> Self {
+        match self {
+            PathSegment::Field(name) => PathSegment::Field(name.clone()),
+            PathSegment::Index(index) => PathSegment::Index(*index),
+            PathSegment::Key(key) => PathSegment::Key(key.clone_value()),
+        }
+    }
+}
+
+/// Formats a path like the paths of [`GetPath`](crate::GetPath), like `a.b[0]`. Map keys are
+/// written like `["key"]`.
+pub fn display_path(path: &[PathSegment]) -> String {
+    let mut display = String::new();
+    for segment in path.iter() {
+        match segment {
+            PathSegment::Field(name) => {
+                if !display.is_empty() {
+                    display.push('.');
+                }
+                display.push_str(name);
+            }
+            PathSegment::Index(index) => display.push_str(&format!("[{}]", index)),
+            PathSegment::Key(key) => display.push_str(&format!("[{}]", display_key(&**key))),
+        }
+    }
+    display
+}
+
+fn display_key(key: &dyn Reflect) -> String {
+    if let Some(key) = key.downcast_ref::<String>() {
+        return format!("{:?}", key);
+    }
+    macro_rules! display_integer {
+        ($($ty:ty),*) => {
+            $(if let Some(key) = key.downcast_ref::<$ty>() {
+                return key.to_string();
+            })*
+        };
+    }
+    display_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    format!("<{}>", key.type_name())
+}
+
+/// A change found by [`reflect_diff`].
+pub enum ReflectChange {
+    /// The value at `path` changed from `old` to `new`
+    Modify {
+        path: Vec<PathSegment>,
+        old: Box<dyn Reflect>,
+        new: Box<dyn Reflect>,
+    },
+    /// `value` was inserted into the list at `path` at `index`
+    ListInsert {
+        path: Vec<PathSegment>,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// `value` was removed from the list at `path` at `index`
+    ListRemove {
+        path: Vec<PathSegment>,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// The entry of `key` was inserted into the map at `path`
+    MapInsert {
+        path: Vec<PathSegment>,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// The entry of `key` was removed from the map at `path`
+    MapRemove {
+        path: Vec<PathSegment>,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+}
+
+impl ReflectChange {
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            ReflectChange::Modify { path, .. }
+            | ReflectChange::ListInsert { path, .. }
+            | ReflectChange::ListRemove { path, .. }
+            | ReflectChange::MapInsert { path, .. }
+            | ReflectChange::MapRemove { path, .. } => path,
+        }
+    }
+
+    /// Returns the change that undoes this change.
+    pub fn inverse(&self) -> ReflectChange {
+        match self {
+            ReflectChange::Modify { path, old, new } => ReflectChange::Modify {
+                path: path.clone(),
+                old: new.clone_value(),
+                new: old.clone_value(),
+            },
+            ReflectChange::ListInsert { path, index, value } => ReflectChange::ListRemove {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            ReflectChange::ListRemove { path, index, value } => ReflectChange::ListInsert {
+                path: path.clone(),
+                index: *index,
+                value: value.clone_value(),
+            },
+            ReflectChange::MapInsert { path, key, value } => ReflectChange::MapRemove {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+            ReflectChange::MapRemove { path, key, value } => ReflectChange::MapInsert {
+                path: path.clone(),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+        }
+    }
+}
+
+/// The changes that turn one value into another, computed by [`reflect_diff`] and applied by
+/// [`reflect_patch`]. It can be serialized with
+/// [`ReflectPatchSerializer`](crate::serde::ReflectPatchSerializer).
+#[derive(Default)]
+pub struct ReflectPatch {
+    pub changes: Vec<ReflectChange>,
+}
+
+impl ReflectPatch {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the patch that undoes this patch.
+    pub fn inverse(&self) -> ReflectPatch {
+        ReflectPatch {
+            changes: self
+                .changes
+                .iter()
+                .rev()
+                .map(|change| change.inverse())
+                .collect(),
+        }
+    }
+}
+
+/// Computes the patch that turns `old` into `new`.
+///
+/// Fields and items are compared recursively, and values that cannot be compared field by field,
+/// like values of different types, different enum variants or tuples of different lengths, are
+/// modified as a whole. List items are compared by index, so items are only inserted or removed
+/// at the end of a list.
+pub fn reflect_diff(old: &dyn Reflect, new: &dyn Reflect) -> ReflectPatch {
+    let mut patch = ReflectPatch::default();
+    diff_value(old, new, &mut Vec::new(), &mut patch.changes);
+    patch
+}
+
+fn diff_value(
+    old: &dyn Reflect,
+    new: &dyn Reflect,
+    path: &mut Vec<PathSegment>,
+    changes: &mut Vec<ReflectChange>,
+) {
+    let modify = |changes: &mut Vec<ReflectChange>, path: &[PathSegment]| {
+        changes.push(ReflectChange::Modify {
+            path: path.to_vec(),
+            old: old.clone_value(),
+            new: new.clone_value(),
+        })
+    };
+
+    if old.type_name() != new.type_name() {
+        modify(changes, path);
+        return;
+    }
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) => {
+            let same_fields = old_struct.field_len() == new_struct.field_len()
+                && (0..new_struct.field_len())
+                    .all(|i| old_struct.field(new_struct.name_at(i).unwrap()).is_some());
+            if !same_fields {
+                modify(changes, path);
+                return;
+            }
+            for (i, new_field) in new_struct.iter_fields().enumerate() {
+                let name = new_struct.name_at(i).unwrap();
+                path.push(PathSegment::Field(name.to_string()));
+                diff_value(old_struct.field(name).unwrap(), new_field, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::TupleStruct(old_struct), ReflectRef::TupleStruct(new_struct)) => {
+            if old_struct.field_len() != new_struct.field_len() {
+                modify(changes, path);
+                return;
+            }
+            diff_fields(
+                old_struct.iter_fields().zip(new_struct.iter_fields()),
+                path,
+                changes,
+            );
+        }
+        (ReflectRef::Tuple(old_tuple), ReflectRef::Tuple(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                modify(changes, path);
+                return;
+            }
+            diff_fields(
+                old_tuple.iter_fields().zip(new_tuple.iter_fields()),
+                path,
+                changes,
+            );
+        }
+        (ReflectRef::Enum(old_enum), ReflectRef::Enum(new_enum)) => {
+            if old_enum.variant_name() != new_enum.variant_name()
+                || old_enum.variant_type() != new_enum.variant_type()
+                || old_enum.field_len() != new_enum.field_len()
+            {
+                modify(changes, path);
+                return;
+            }
+            if new_enum.variant_type() != VariantType::Struct {
+                diff_fields(
+                    old_enum.iter_fields().zip(new_enum.iter_fields()),
+                    path,
+                    changes,
+                );
+                return;
+            }
+            for (i, new_field) in new_enum.iter_fields().enumerate() {
+                let name = new_enum.name_at(i).unwrap();
+                let old_field = match old_enum.field(name) {
+                    Some(old_field) => old_field,
+                    None => {
+                        modify(changes, path);
+                        return;
+                    }
+                };
+                path.push(PathSegment::Field(name.to_string()));
+                diff_value(old_field, new_field, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::List(old_list), ReflectRef::List(new_list)) => {
+            for (i, (old_item, new_item)) in old_list.iter().zip(new_list.iter()).enumerate() {
+                path.push(PathSegment::Index(i));
+                diff_value(old_item, new_item, path, changes);
+                path.pop();
+            }
+            for i in old_list.len()..new_list.len() {
+                changes.push(ReflectChange::ListInsert {
+                    path: path.clone(),
+                    index: i,
+                    value: new_list.get(i).unwrap().clone_value(),
+                });
+            }
+            // removed from the end, so the indices stay valid while the patch is applied
+            for i in (new_list.len()..old_list.len()).rev() {
+                changes.push(ReflectChange::ListRemove {
+                    path: path.clone(),
+                    index: i,
+                    value: old_list.get(i).unwrap().clone_value(),
+                });
+            }
+        }
+        (ReflectRef::Map(old_map), ReflectRef::Map(new_map)) => {
+            for (key, new_value) in new_map.iter() {
+                match old_map.get(key) {
+                    Some(old_value) => {
+                        path.push(PathSegment::Key(key.clone_value()));
+                        diff_value(old_value, new_value, path, changes);
+                        path.pop();
+                    }
+                    None => changes.push(ReflectChange::MapInsert {
+                        path: path.clone(),
+                        key: key.clone_value(),
+                        value: new_value.clone_value(),
+                    }),
+                }
+            }
+            for (key, old_value) in old_map.iter() {
+                if new_map.get(key).is_none() {
+                    changes.push(ReflectChange::MapRemove {
+                        path: path.clone(),
+                        key: key.clone_value(),
+                        value: old_value.clone_value(),
+                    });
+                }
+            }
+        }
+        (ReflectRef::Value(old_value), ReflectRef::Value(new_value)) => {
+            if old_value.reflect_partial_eq(new_value) != Some(true) {
+                modify(changes, path);
+            }
+        }
+        _ => modify(changes, path),
+    }
+}
+
+/// Diffs the fields of a tuple struct, tuple or tuple variant.
+fn diff_fields<'a>(
+    fields: impl Iterator<Item = (&'a dyn Reflect, &'a dyn Reflect)>,
+    path: &mut Vec<PathSegment>,
+    changes: &mut Vec<ReflectChange>,
+) {
+    for (i, (old_field, new_field)) in fields.enumerate() {
+        path.push(PathSegment::Field(i.to_string()));
+        diff_value(old_field, new_field, path, changes);
+        path.pop();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ReflectPatchError {
+    #[error("the value at `{0}` does not exist")]
+    InvalidPath(String),
+    #[error("the value at `{0}` is not a list")]
+    ExpectedList(String),
+    #[error("the value at `{0}` is not a map")]
+    ExpectedMap(String),
+    #[error("the index {index} is out of bounds of the list at `{path}`")]
+    InvalidIndex { path: String, index: usize },
+    #[error("the map at `{0}` has no entry with the removed key")]
+    MissingKey(String),
+    #[error("the value of type `{type_name}` inserted into `{path}` is not of the item type. consider adding `#[reflect(FromReflect)]` to the type")]
+    InvalidItem { path: String, type_name: String },
+    #[error("the value of type `{type_name}` cannot be applied to the value at `{path}`, which is of another type")]
+    InvalidValue { path: String, type_name: String },
+}
+
+/// Applies the changes of `patch` to `target`, in order. If a change cannot be applied, the
+/// changes before it stay applied.
+///
+/// Values inserted into lists and maps need to be of the item types of the lists and maps, so
+/// dynamic values, like the values in deserialized patches, are converted with the
+/// [`ReflectFromReflect`] of their type in `type_registry`. Inserting a value that cannot be
+/// converted to the item type of a typed list or map fails.
+pub fn reflect_patch(
+    target: &mut dyn Reflect,
+    patch: &ReflectPatch,
+    type_registry: &TypeRegistry,
+) -> Result<(), ReflectPatchError> {
+    for change in patch.changes.iter() {
+        apply_change(target, change, type_registry)?;
+    }
+    Ok(())
+}
+
+fn apply_change(
+    target: &mut dyn Reflect,
+    change: &ReflectChange,
+    type_registry: &TypeRegistry,
+) -> Result<(), ReflectPatchError> {
+    let path = change.path();
+    let value = value_at_mut(target, path)
+        .ok_or_else(|| ReflectPatchError::InvalidPath(display_path(path)))?;
+    match change {
+        ReflectChange::Modify { new, .. } => {
+            if let Err(new) = value.set(from_reflect(&**new, type_registry)) {
+                // applying a value of another type panics
+                if new.type_name() != value.type_name() {
+                    return Err(ReflectPatchError::InvalidValue {
+                        path: display_path(path),
+                        type_name: new.type_name().to_string(),
+                    });
+                }
+                value.apply(&*new);
+            }
+        }
+        ReflectChange::ListInsert {
+            index, value: item, ..
+        } => {
+            let list = match value.reflect_mut() {
+                ReflectMut::List(list) => list,
+                _ => return Err(ReflectPatchError::ExpectedList(display_path(path))),
+            };
+            if *index > list.len() {
+                return Err(ReflectPatchError::InvalidIndex {
+                    path: display_path(path),
+                    index: *index,
+                });
+            }
+            let item = typed_item(&**item, list.item_type_id(), path, type_registry)?;
+            list.insert(*index, item);
+        }
+        ReflectChange::ListRemove { index, .. } => {
+            let list = match value.reflect_mut() {
+                ReflectMut::List(list) => list,
+                _ => return Err(ReflectPatchError::ExpectedList(display_path(path))),
+            };
+            if *index >= list.len() {
+                return Err(ReflectPatchError::InvalidIndex {
+                    path: display_path(path),
+                    index: *index,
+                });
+            }
+            list.remove(*index);
+        }
+        ReflectChange::MapInsert {
+            key, value: entry, ..
+        } => match value.reflect_mut() {
+            ReflectMut::Map(map) => {
+                let key = typed_item(&**key, map.key_type_id(), path, type_registry)?;
+                let entry = typed_item(&**entry, map.value_type_id(), path, type_registry)?;
+                map.insert_boxed(key, entry);
+            }
+            _ => return Err(ReflectPatchError::ExpectedMap(display_path(path))),
+        },
+        ReflectChange::MapRemove { key, .. } => match value.reflect_mut() {
+            ReflectMut::Map(map) => {
+                if map.remove(&**key).is_none() {
+                    return Err(ReflectPatchError::MissingKey(display_path(path)));
+                }
+            }
+            _ => return Err(ReflectPatchError::ExpectedMap(display_path(path))),
+        },
+    }
+    Ok(())
+}
+
+fn value_at_mut<'a>(
+    value: &'a mut dyn Reflect,
+    path: &[PathSegment],
+) -> Option<&'a mut dyn Reflect> {
+    let (segment, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return Some(value),
+    };
+    let next = match (segment, value.reflect_mut()) {
+        (PathSegment::Field(name), ReflectMut::Struct(value)) => value.field_mut(name),
+        (PathSegment::Field(name), ReflectMut::TupleStruct(value)) => {
+            value.field_mut(name.parse().ok()?)
+        }
+        (PathSegment::Field(name), ReflectMut::Tuple(value)) => value.field_mut(name.parse().ok()?),
+        (PathSegment::Field(name), ReflectMut::Enum(value)) => match value.variant_type() {
+            VariantType::Struct => value.field_mut(name),
+            _ => value.field_at_mut(name.parse().ok()?),
+        },
+        (PathSegment::Index(index), ReflectMut::List(value)) => value.get_mut(*index),
+        (PathSegment::Key(key), ReflectMut::Map(value)) => value.get_mut(&**key),
+        _ => None,
+    }?;
+    value_at_mut(next, rest)
+}
+
+/// Converts `value` with [`from_reflect`], and checks that it is of the item type of the list or
+/// map it is inserted into. Dynamic lists and maps, which have no item type, accept any value.
+fn typed_item(
+    value: &dyn Reflect,
+    item_type_id: Option<TypeId>,
+    path: &[PathSegment],
+    type_registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ReflectPatchError> {
+    let value = from_reflect(value, type_registry);
+    match item_type_id {
+        Some(item_type_id) if item_type_id != value.any().type_id() => {
+            Err(ReflectPatchError::InvalidItem {
+                path: display_path(path),
+                type_name: value.type_name().to_string(),
+            })
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Converts a dynamic value to a value of its type, if its type is registered with
+/// [`ReflectFromReflect`].
+fn from_reflect(value: &dyn Reflect, type_registry: &TypeRegistry) -> Box<dyn Reflect> {
+    type_registry
+        .get_with_name(value.type_name())
+        .and_then(|registration| registration.data::<ReflectFromReflect>())
+        .and_then(|reflect_from_reflect| reflect_from_reflect.from_reflect(value))
+        .unwrap_or_else(|| value.clone_value())
+}
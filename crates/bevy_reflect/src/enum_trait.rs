@@ -0,0 +1,392 @@
+use crate::{
+    serde::Serializable, DynamicStruct, DynamicTuple, FromReflect, Reflect, ReflectMut, ReflectRef,
+    Struct, Tuple,
+};
+use std::{any::Any, marker::PhantomData};
+
+/// The kind of fields of an enum variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VariantType {
+    /// A variant with named fields, like `Foo::Bar { x: f32 }`
+    Struct,
+    /// A variant with unnamed fields, like `Foo::Bar(f32)`
+    Tuple,
+    /// A variant without fields, like `Foo::Bar`
+    Unit,
+}
+
+/// A rust "enum" reflection. The fields are the fields of the current variant.
+///
+/// Applying a value of another variant to a derived enum constructs that variant from the default
+/// values of its fields, or with [`FromReflect`] from the applied fields for fields without
+/// `Default`. Applying a variant whose fields can be constructed in neither way, or that does not
+/// exist on the enum, leaves the value unchanged.
+///
+/// Enums that derive `Reflect` used to be reflected as opaque values. Enums that should still be
+/// applied and serialized as a whole can opt out with `#[reflect_value]`, e.g.
+/// `#[reflect_value(PartialEq, Serialize, Deserialize)]`.
+pub trait Enum: Reflect {
+    /// Returns the field with the given name, if the current variant is a struct variant.
+    fn field(&self, name: &str) -> Option<&dyn Reflect>;
+    /// Returns the field with the given name, if the current variant is a struct variant.
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect>;
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect>;
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+    /// Returns the name of the field at the given index, if the current variant is a struct
+    /// variant.
+    fn name_at(&self, index: usize) -> Option<&str>;
+    fn field_len(&self) -> usize;
+    fn iter_fields(&self) -> VariantFieldIter;
+    fn variant_name(&self) -> &str;
+    fn variant_index(&self) -> usize;
+    fn variant_type(&self) -> VariantType;
+    fn clone_dynamic(&self) -> DynamicEnum;
+}
+
+pub struct VariantFieldIter<'a> {
+    pub(crate) value: &'a dyn Enum,
+    pub(crate) index: usize,
+}
+
+impl<'a> VariantFieldIter<'a> {
+    pub fn new(value: &'a dyn Enum) -> Self {
+        VariantFieldIter { value, index: 0 }
+    }
+}
+
+impl<'a> Iterator for VariantFieldIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.value.field_at(self.index);
+        self.index += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.value.field_len();
+        (size, Some(size))
+    }
+}
+
+impl<'a> ExactSizeIterator for VariantFieldIter<'a> {}
+
+/// The fields of the variant of a [`DynamicEnum`]
+pub enum DynamicVariant {
+    Struct(DynamicStruct),
+    Tuple(DynamicTuple),
+    Unit,
+}
+
+impl DynamicVariant {
+    pub fn variant_type(&self) -> VariantType {
+        match self {
+            DynamicVariant::Struct(_) => VariantType::Struct,
+            DynamicVariant::Tuple(_) => VariantType::Tuple,
+            DynamicVariant::Unit => VariantType::Unit,
+        }
+    }
+
+    fn clone_dynamic(&self) -> DynamicVariant {
+        match self {
+            DynamicVariant::Struct(value) => DynamicVariant::Struct(value.clone_dynamic()),
+            DynamicVariant::Tuple(value) => DynamicVariant::Tuple(value.clone_dynamic()),
+            DynamicVariant::Unit => DynamicVariant::Unit,
+        }
+    }
+}
+
+/// A dynamic enum value. The variant index is only known if the value was cloned from a concrete
+/// enum, otherwise it is `0`. Applying an enum to another enum only uses the variant name.
+pub struct DynamicEnum {
+    name: String,
+    variant_name: String,
+    variant_index: usize,
+    variant: DynamicVariant,
+}
+
+impl Default for DynamicEnum {
+    fn default() -> Self {
+        DynamicEnum {
+            name: String::new(),
+            variant_name: String::new(),
+            variant_index: 0,
+            variant: DynamicVariant::Unit,
+        }
+    }
+}
+
+impl DynamicEnum {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_variant(&mut self, name: &str, variant: DynamicVariant) {
+        self.variant_name = name.to_string();
+        self.variant = variant;
+    }
+
+    pub fn set_variant_with_index(&mut self, index: usize, name: &str, variant: DynamicVariant) {
+        self.variant_index = index;
+        self.set_variant(name, variant);
+    }
+
+    pub fn variant(&self) -> &DynamicVariant {
+        &self.variant
+    }
+}
+
+impl Enum for DynamicEnum {
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Struct(value) => Struct::field(value, name),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Struct(value) => Struct::field_mut(value, name),
+            _ => None,
+        }
+    }
+
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Struct(value) => value.field_at(index),
+            DynamicVariant::Tuple(value) => Tuple::field(value, index),
+            DynamicVariant::Unit => None,
+        }
+    }
+
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Struct(value) => value.field_at_mut(index),
+            DynamicVariant::Tuple(value) => Tuple::field_mut(value, index),
+            DynamicVariant::Unit => None,
+        }
+    }
+
+    fn name_at(&self, index: usize) -> Option<&str> {
+        match &self.variant {
+            DynamicVariant::Struct(value) => value.name_at(index),
+            _ => None,
+        }
+    }
+
+    fn field_len(&self) -> usize {
+        match &self.variant {
+            DynamicVariant::Struct(value) => Struct::field_len(value),
+            DynamicVariant::Tuple(value) => Tuple::field_len(value),
+            DynamicVariant::Unit => 0,
+        }
+    }
+
+    #[inline]
+    fn iter_fields(&self) -> VariantFieldIter {
+        VariantFieldIter::new(self)
+    }
+
+    #[inline]
+    fn variant_name(&self) -> &str {
+        &self.variant_name
+    }
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        self.variant_index
+    }
+
+    #[inline]
+    fn variant_type(&self) -> VariantType {
+        self.variant.variant_type()
+    }
+
+    fn clone_dynamic(&self) -> DynamicEnum {
+        DynamicEnum {
+            name: self.name.clone(),
+            variant_name: self.variant_name.clone(),
+            variant_index: self.variant_index,
+            variant: self.variant.clone_dynamic(),
+        }
+    }
+}
+
+// SAFE: any and any_mut both return self
+unsafe impl Reflect for DynamicEnum {
+    #[inline]
+    fn type_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Enum(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Enum(self)
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        if let ReflectRef::Enum(enum_value) = value.reflect_ref() {
+            if self.variant_name == enum_value.variant_name() {
+                apply_variant_fields(self, enum_value);
+            } else {
+                let mut dynamic = enum_value.clone_dynamic();
+                dynamic.name = std::mem::take(&mut self.name);
+                *self = dynamic;
+            }
+        } else {
+            panic!("Attempted to apply non-enum type to enum type.");
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        enum_partial_eq(self, value)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        None
+    }
+}
+
+/// Applies the fields of `value` to the fields of `target`, which is expected to be of the same
+/// variant. Struct variant fields are matched by name, tuple variant fields by index.
+pub fn apply_variant_fields<E: Enum + ?Sized>(target: &mut E, value: &dyn Enum) {
+    for (i, field_value) in value.iter_fields().enumerate() {
+        let field = match value.variant_type() {
+            VariantType::Struct => value
+                .name_at(i)
+                .and_then(|name| Enum::field_mut(target, name)),
+            _ => Enum::field_at_mut(target, i),
+        };
+        if let Some(field) = field {
+            field.apply(field_value);
+        }
+    }
+}
+
+/// Constructs a field of type `T` when a derived enum switches to another variant, using the first
+/// of these that `T` implements:
+///
+/// - [`Default`], after which the applied field value is applied to the field as usual
+/// - [`FromReflect`], from the applied field value
+///
+/// If `T` implements neither, or the value is missing or of another type, no field is
+/// constructed and the enum keeps its variant. The method is picked with the traits
+/// [`ConstructFieldWithDefault`], [`ConstructFieldFromReflect`] and [`ConstructNoField`], which
+/// are implemented for `&&VariantField<T>`, `&VariantField<T>` and `VariantField<T>`, so calling
+/// it on `&&VariantField<T>` uses the most preferred one that applies.
+#[doc(hidden)]
+pub struct VariantField<T>(PhantomData<T>);
+
+impl<T> VariantField<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        VariantField(PhantomData)
+    }
+}
+
+// `Copy` regardless of `T`, so the by-value fallback can be called through the references
+impl<T> Clone for VariantField<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for VariantField<T> {}
+
+#[doc(hidden)]
+pub trait ConstructFieldWithDefault<T> {
+    fn construct_field(self, value: Option<&dyn Reflect>) -> Option<T>;
+}
+
+impl<T: Default> ConstructFieldWithDefault<T> for &&VariantField<T> {
+    fn construct_field(self, _value: Option<&dyn Reflect>) -> Option<T> {
+        Some(T::default())
+    }
+}
+
+#[doc(hidden)]
+pub trait ConstructFieldFromReflect<T> {
+    fn construct_field(self, value: Option<&dyn Reflect>) -> Option<T>;
+}
+
+impl<T: FromReflect> ConstructFieldFromReflect<T> for &VariantField<T> {
+    fn construct_field(self, value: Option<&dyn Reflect>) -> Option<T> {
+        value.and_then(T::from_reflect)
+    }
+}
+
+#[doc(hidden)]
+pub trait ConstructNoField<T> {
+    fn construct_field(self, value: Option<&dyn Reflect>) -> Option<T>;
+}
+
+impl<T> ConstructNoField<T> for VariantField<T> {
+    fn construct_field(self, _value: Option<&dyn Reflect>) -> Option<T> {
+        None
+    }
+}
+
+#[inline]
+pub fn enum_partial_eq<E: Enum>(a: &E, b: &dyn Reflect) -> Option<bool> {
+    let enum_value = if let ReflectRef::Enum(enum_value) = b.reflect_ref() {
+        enum_value
+    } else {
+        return Some(false);
+    };
+
+    if a.variant_name() != enum_value.variant_name()
+        || a.variant_type() != enum_value.variant_type()
+        || a.field_len() != enum_value.field_len()
+    {
+        return Some(false);
+    }
+
+    for (i, value) in enum_value.iter_fields().enumerate() {
+        let field_value = match enum_value.variant_type() {
+            VariantType::Struct => enum_value.name_at(i).and_then(|name| a.field(name)),
+            _ => a.field_at(i),
+        };
+        if let Some(field_value) = field_value {
+            if let Some(false) | None = field_value.reflect_partial_eq(value) {
+                return Some(false);
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
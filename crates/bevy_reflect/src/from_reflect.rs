@@ -0,0 +1,37 @@
+use crate::{FromType, Reflect};
+
+/// A trait for types that can be constructed from a reflected value, like the `Dynamic*` values
+/// produced by the [`ReflectDeserializer`](crate::serde::ReflectDeserializer).
+///
+/// Unlike [`Reflect::apply`], this does not need an existing instance of the type, so every field
+/// of the value has to be present. Fields with `#[reflect(ignore)]` use their `Default` value.
+pub trait FromReflect: Reflect + Sized {
+    /// Constructs a concrete value of this type from `reflect`, or returns `None` if `reflect`
+    /// does not match the type.
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self>;
+}
+
+/// Type data for constructing a type that implements [`FromReflect`], without knowing the type
+/// statically.
+#[derive(Clone)]
+pub struct ReflectFromReflect {
+    from_reflect: fn(&dyn Reflect) -> Option<Box<dyn Reflect>>,
+}
+
+impl ReflectFromReflect {
+    /// Constructs a concrete value of the registered type from `reflect`, or returns `None` if
+    /// `reflect` does not match the type.
+    pub fn from_reflect(&self, reflect: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        (self.from_reflect)(reflect)
+    }
+}
+
+impl<T: FromReflect> FromType<T> for ReflectFromReflect {
+    fn from_type() -> Self {
+        ReflectFromReflect {
+            from_reflect: |reflect| {
+                T::from_reflect(reflect).map(|value| Box::new(value) as Box<dyn Reflect>)
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate as bevy_reflect;
 use crate::ReflectDeserialize;
-use bevy_reflect_derive::impl_reflect_value;
+use bevy_reflect_derive::{impl_from_reflect_value, impl_reflect_value};
 use glam::{IVec2, IVec3, IVec4, Mat3, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
 
 impl_reflect_value!(IVec2(PartialEq, Serialize, Deserialize));
@@ -15,3 +15,16 @@ impl_reflect_value!(Vec4(PartialEq, Serialize, Deserialize));
 impl_reflect_value!(Mat3(PartialEq, Serialize, Deserialize));
 impl_reflect_value!(Mat4(PartialEq, Serialize, Deserialize));
 impl_reflect_value!(Quat(PartialEq, Serialize, Deserialize));
+
+impl_from_reflect_value!(IVec2);
+impl_from_reflect_value!(IVec3);
+impl_from_reflect_value!(IVec4);
+impl_from_reflect_value!(UVec2);
+impl_from_reflect_value!(UVec3);
+impl_from_reflect_value!(UVec4);
+impl_from_reflect_value!(Vec2);
+impl_from_reflect_value!(Vec3);
+impl_from_reflect_value!(Vec4);
+impl_from_reflect_value!(Mat3);
+impl_from_reflect_value!(Mat4);
+impl_from_reflect_value!(Quat);
@@ -1,7 +1,10 @@
 use smallvec::{Array, SmallVec};
-use std::any::Any;
+use std::any::{Any, TypeId};
 
-use crate::{serde::Serializable, List, ListIter, Reflect, ReflectMut, ReflectRef};
+use crate::{
+    serde::Serializable, FromReflect, List, ListInfo, ListIter, Reflect, ReflectMut, ReflectRef,
+    TypeInfo, Typed,
+};
 
 impl<T: Array + Send + Sync + 'static> List for SmallVec<T>
 where
@@ -37,12 +40,30 @@ where
         SmallVec::push(self, value);
     }
 
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) {
+        let value = value.take::<T::Item>().unwrap_or_else(|value| {
+            panic!(
+                "Attempted to insert invalid value of type {}.",
+                value.type_name()
+            )
+        });
+        SmallVec::insert(self, index, value);
+    }
+
+    fn remove(&mut self, index: usize) -> Box<dyn Reflect> {
+        Box::new(SmallVec::remove(self, index))
+    }
+
     fn iter(&self) -> ListIter {
         ListIter {
             list: self,
             index: 0,
         }
     }
+
+    fn item_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<T::Item>())
+    }
 }
 
 // SAFE: any and any_mut both return self
@@ -95,3 +116,28 @@ where
         None
     }
 }
+
+impl<T: Array + Send + Sync + 'static> FromReflect for SmallVec<T>
+where
+    T::Item: FromReflect + Clone,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let ReflectRef::List(ref_list) = reflect.reflect_ref() {
+            ref_list.iter().map(T::Item::from_reflect).collect()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Array + Send + Sync + 'static> Typed for SmallVec<T>
+where
+    T::Item: Reflect + Clone,
+{
+    fn type_info() -> TypeInfo {
+        TypeInfo::List(ListInfo {
+            type_name: std::any::type_name::<Self>(),
+            item_type_name: std::any::type_name::<T::Item>(),
+        })
+    }
+}
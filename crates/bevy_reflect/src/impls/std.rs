@@ -1,14 +1,15 @@
 use crate as bevy_reflect;
 use crate::{
-    map_partial_eq, serde::Serializable, DynamicMap, FromType, GetTypeRegistration, List, ListIter,
-    Map, MapIter, Reflect, ReflectDeserialize, ReflectMut, ReflectRef, TypeRegistration,
+    map_partial_eq, serde::Serializable, DynamicMap, FromReflect, FromType, GetTypeRegistration,
+    List, ListInfo, ListIter, Map, MapInfo, MapIter, Reflect, ReflectDeserialize, ReflectMut,
+    ReflectRef, TypeInfo, TypeRegistration, Typed, ValueInfo,
 };
 
-use bevy_reflect_derive::impl_reflect_value;
+use bevy_reflect_derive::{impl_from_reflect_value, impl_reflect_value};
 use bevy_utils::{Duration, HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     borrow::Cow,
     hash::{Hash, Hasher},
     ops::Range,
@@ -35,6 +36,27 @@ impl_reflect_value!(HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserial
 impl_reflect_value!(Range<T: Serialize + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Duration);
 
+impl_from_reflect_value!(bool);
+impl_from_reflect_value!(u8);
+impl_from_reflect_value!(u16);
+impl_from_reflect_value!(u32);
+impl_from_reflect_value!(u64);
+impl_from_reflect_value!(u128);
+impl_from_reflect_value!(usize);
+impl_from_reflect_value!(i8);
+impl_from_reflect_value!(i16);
+impl_from_reflect_value!(i32);
+impl_from_reflect_value!(i64);
+impl_from_reflect_value!(i128);
+impl_from_reflect_value!(isize);
+impl_from_reflect_value!(f32);
+impl_from_reflect_value!(f64);
+impl_from_reflect_value!(String);
+impl_from_reflect_value!(Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Reflect + 'static>);
+impl_from_reflect_value!(HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>);
+impl_from_reflect_value!(Range<T: Serialize + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>);
+impl_from_reflect_value!(Duration);
+
 impl<T: Reflect> List for Vec<T> {
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
         <[T]>::get(self, index).map(|value| value as &dyn Reflect)
@@ -55,6 +77,10 @@ impl<T: Reflect> List for Vec<T> {
         }
     }
 
+    fn item_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<T>())
+    }
+
     fn push(&mut self, value: Box<dyn Reflect>) {
         let value = value.take::<T>().unwrap_or_else(|value| {
             panic!(
@@ -64,6 +90,20 @@ impl<T: Reflect> List for Vec<T> {
         });
         Vec::push(self, value);
     }
+
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) {
+        let value = value.take::<T>().unwrap_or_else(|value| {
+            panic!(
+                "Attempted to insert invalid value of type {}.",
+                value.type_name()
+            )
+        });
+        Vec::insert(self, index, value);
+    }
+
+    fn remove(&mut self, index: usize) -> Box<dyn Reflect> {
+        Box::new(Vec::remove(self, index))
+    }
 }
 
 // SAFE: any and any_mut both return self
@@ -114,6 +154,25 @@ unsafe impl<T: Reflect> Reflect for Vec<T> {
     }
 }
 
+impl<T: FromReflect> FromReflect for Vec<T> {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let ReflectRef::List(ref_list) = reflect.reflect_ref() {
+            ref_list.iter().map(T::from_reflect).collect()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Reflect> Typed for Vec<T> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::List(ListInfo {
+            type_name: std::any::type_name::<Self>(),
+            item_type_name: std::any::type_name::<T>(),
+        })
+    }
+}
+
 impl<T: Reflect + for<'de> Deserialize<'de>> GetTypeRegistration for Vec<T> {
     fn get_type_registration() -> TypeRegistration {
         let mut registration = TypeRegistration::of::<Vec<T>>();
@@ -141,6 +200,32 @@ impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Map for HashMap<K, V> {
             .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
     }
 
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
+        let key = key.take::<K>().unwrap_or_else(|key| {
+            panic!(
+                "Attempted to insert invalid key of type {}.",
+                key.type_name()
+            )
+        });
+        let value = value.take::<V>().unwrap_or_else(|value| {
+            panic!(
+                "Attempted to insert invalid value of type {}.",
+                value.type_name()
+            )
+        });
+        HashMap::insert(self, key, value).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        key.downcast_ref::<K>()
+            .and_then(|key| HashMap::remove(self, key))
+            .map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
+
     fn len(&self) -> usize {
         HashMap::len(self)
     }
@@ -152,6 +237,14 @@ impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Map for HashMap<K, V> {
         }
     }
 
+    fn key_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<K>())
+    }
+
+    fn value_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<V>())
+    }
+
     fn clone_dynamic(&self) -> DynamicMap {
         let mut dynamic_map = DynamicMap::default();
         dynamic_map.set_name(self.type_name().to_string());
@@ -218,6 +311,33 @@ unsafe impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Reflect for Hash
     }
 }
 
+impl<K, V> FromReflect for HashMap<K, V>
+where
+    K: FromReflect + Clone + Eq + Hash,
+    V: FromReflect + Clone,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
+            ref_map
+                .iter()
+                .map(|(key, value)| Some((K::from_reflect(key)?, V::from_reflect(value)?)))
+                .collect()
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Typed for HashMap<K, V> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::Map(MapInfo {
+            type_name: std::any::type_name::<Self>(),
+            key_type_name: std::any::type_name::<K>(),
+            value_type_name: std::any::type_name::<V>(),
+        })
+    }
+}
+
 impl<K, V> GetTypeRegistration for HashMap<K, V>
 where
     K: Reflect + Clone + Eq + Hash + for<'de> Deserialize<'de>,
@@ -291,6 +411,18 @@ unsafe impl Reflect for Cow<'static, str> {
     }
 }
 
+impl FromReflect for Cow<'static, str> {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        reflect.downcast_ref::<Self>().cloned()
+    }
+}
+
+impl Typed for Cow<'static, str> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::Value(ValueInfo::of::<Self>())
+    }
+}
+
 impl GetTypeRegistration for Cow<'static, str> {
     fn get_type_registration() -> TypeRegistration {
         let mut registration = TypeRegistration::of::<Cow<'static, str>>();
@@ -1,10 +1,15 @@
+mod diff;
+mod enum_trait;
+mod from_reflect;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod schema;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
+mod type_info;
 mod type_registry;
 mod type_uuid;
 mod impls {
@@ -25,19 +30,24 @@ pub mod serde;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        reflect_trait, GetField, GetTupleStructField, Reflect, ReflectDeserialize, Struct,
-        TupleStruct,
+        reflect_trait, Enum, FromReflect, GetField, GetTupleStructField, Reflect,
+        ReflectDeserialize, ReflectFromReflect, Struct, TupleStruct,
     };
 }
 
+pub use diff::*;
+pub use enum_trait::*;
+pub use from_reflect::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use schema::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
+pub use type_info::*;
 pub use type_registry::*;
 pub use type_uuid::*;
 
@@ -56,7 +66,9 @@ mod tests {
 
     use super::*;
     use crate as bevy_reflect;
-    use crate::serde::{ReflectDeserializer, ReflectSerializer};
+    use crate::serde::{
+        ReflectDeserializer, ReflectPatchDeserializer, ReflectPatchSerializer, ReflectSerializer,
+    };
 
     #[test]
     fn reflect_struct() {
@@ -317,6 +329,515 @@ mod tests {
         assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
     }
 
+    #[test]
+    fn reflect_enum() {
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Foo {
+            A,
+            B(u32, #[reflect(ignore)] u32, String),
+            C { x: u64, y: Bar },
+        }
+
+        #[derive(Reflect, Debug, PartialEq, Default)]
+        struct Bar {
+            z: usize,
+        }
+
+        let mut foo = Foo::B(1, 2, "hi".to_string());
+        assert_eq!(foo.variant_name(), "B");
+        assert_eq!(foo.variant_index(), 1);
+        assert_eq!(foo.variant_type(), VariantType::Tuple);
+        assert_eq!(foo.field_len(), 2);
+        assert_eq!(*foo.field_at(0).unwrap().downcast_ref::<u32>().unwrap(), 1);
+        assert_eq!(
+            foo.field_at(1).unwrap().downcast_ref::<String>().unwrap(),
+            "hi"
+        );
+        assert!(foo.field("0").is_none());
+
+        // patch the fields of the current variant
+        let mut tuple = DynamicTuple::default();
+        tuple.insert(3u32);
+        let mut patch = DynamicEnum::default();
+        patch.set_variant("B", DynamicVariant::Tuple(tuple));
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::B(3, 2, "hi".to_string()));
+
+        // switch to another variant, with default values for the missing fields
+        let mut fields = DynamicStruct::default();
+        fields.insert("x", 4u64);
+        patch.set_variant("C", DynamicVariant::Struct(fields));
+        foo.apply(&patch);
+        assert_eq!(
+            foo,
+            Foo::C {
+                x: 4,
+                y: Bar { z: 0 }
+            }
+        );
+        assert_eq!(foo.name_at(1), Some("y"));
+        *foo.field_mut("y").unwrap().downcast_mut::<Bar>().unwrap() = Bar { z: 2 };
+
+        let dynamic = foo.clone_dynamic();
+        assert_eq!(dynamic.type_name(), std::any::type_name::<Foo>());
+        assert_eq!(dynamic.variant_index(), 2);
+        assert!(foo.reflect_partial_eq(&dynamic).unwrap());
+
+        patch.set_variant("A", DynamicVariant::Unit);
+        assert!(!foo.reflect_partial_eq(&patch).unwrap());
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::A);
+        assert_eq!(foo.field_len(), 0);
+
+        // variants that do not exist are ignored
+        patch.set_variant("D", DynamicVariant::Unit);
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::A);
+    }
+
+    #[test]
+    fn reflect_enum_without_default_fields() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Id(u32);
+
+        // implements neither `Default` nor `FromReflect`
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Opaque(u32);
+
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Foo {
+            A,
+            B(Id, u32),
+            C { opaque: Opaque },
+        }
+
+        #[derive(Reflect)]
+        enum Empty {}
+
+        #[derive(Reflect)]
+        enum Generic<T: Reflect> {
+            A,
+            B(T),
+        }
+
+        fn assert_reflect<T: Reflect>() {}
+        assert_reflect::<Empty>();
+        assert_reflect::<Generic<Opaque>>();
+
+        // fields without a default are constructed from the applied fields
+        let mut foo = Foo::A;
+        let mut fields = DynamicTuple::default();
+        fields.insert(Id(1));
+        fields.insert(2u32);
+        let mut patch = DynamicEnum::default();
+        patch.set_variant("B", DynamicVariant::Tuple(fields));
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::B(Id(1), 2));
+
+        // a missing field without a default cannot be constructed, so the variant is kept
+        foo = Foo::A;
+        let mut fields = DynamicTuple::default();
+        fields.insert(2u32);
+        patch.set_variant("B", DynamicVariant::Tuple(fields));
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::A);
+
+        // as are variants with fields that can be constructed in neither way
+        let mut fields = DynamicStruct::default();
+        fields.insert("opaque", Opaque(3));
+        patch.set_variant("C", DynamicVariant::Struct(fields));
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::A);
+
+        // fields of the current variant are still applied
+        foo = Foo::C { opaque: Opaque(0) };
+        foo.apply(&patch);
+        assert_eq!(foo, Foo::C { opaque: Opaque(3) });
+    }
+
+    #[test]
+    fn reflect_serialize_enum() {
+        #[derive(Reflect)]
+        struct Foo {
+            a: Bar,
+            b: Vec<Bar>,
+        }
+
+        #[derive(Reflect)]
+        enum Bar {
+            A,
+            B(u32),
+            C { x: String },
+        }
+
+        let foo = Foo {
+            a: Bar::C {
+                x: "hi".to_string(),
+            },
+            b: vec![Bar::A, Bar::B(2)],
+        };
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        let dynamic_struct = value.take::<DynamicStruct>().unwrap();
+
+        assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
+    }
+
+    #[test]
+    fn from_reflect() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Foo {
+            a: u32,
+            #[reflect(ignore)]
+            _b: u32,
+            c: Vec<Bar>,
+            d: HashMap<usize, Option<String>>,
+            e: (i32, Baz),
+        }
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq, Clone)]
+        struct Bar(u8, #[reflect(ignore)] u8, u16);
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        enum Baz {
+            A,
+            B { x: Vec<u8> },
+        }
+
+        let mut hash_map = HashMap::default();
+        hash_map.insert(1, Some("x".to_string()));
+        hash_map.insert(2, None);
+        let foo = Foo {
+            a: 1,
+            _b: 2,
+            c: vec![Bar(3, 4, 5)],
+            d: hash_map,
+            e: (6, Baz::B { x: vec![7] }),
+        };
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<u8>();
+        registry.register::<u16>();
+        registry.register::<usize>();
+        registry.register::<i32>();
+        registry.register::<Option<String>>();
+        registry.register::<Foo>();
+        registry
+            .get_mut(std::any::TypeId::of::<Foo>())
+            .unwrap()
+            .insert::<ReflectFromReflect>(FromType::<Foo>::from_type());
+
+        let serializer = ReflectSerializer::new(&foo, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let expected = Foo {
+            _b: 0,
+            c: vec![Bar(3, 0, 5)],
+            ..foo
+        };
+        assert_eq!(Foo::from_reflect(&*value), Some(expected));
+
+        let reflect_from_reflect = registry
+            .get_with_name(value.type_name())
+            .unwrap()
+            .data::<ReflectFromReflect>()
+            .unwrap();
+        let constructed = reflect_from_reflect.from_reflect(&*value).unwrap();
+        assert_eq!(constructed.downcast_ref::<Foo>().unwrap().a, 1);
+
+        // every field needs to be present
+        let mut dynamic_struct = DynamicStruct::default();
+        dynamic_struct.insert("a", 1u32);
+        assert_eq!(Foo::from_reflect(&dynamic_struct), None);
+    }
+
+    #[test]
+    fn type_schema() {
+        #[derive(Reflect, TypeUuid)]
+        #[reflect(TypeUuid)]
+        #[uuid = "b2ae1f4c-63f0-4e5c-9a6e-0c8b4e1d2a53"]
+        struct Foo {
+            a: u32,
+            #[reflect(ignore)]
+            _b: u32,
+            c: Vec<Bar>,
+            d: HashMap<String, (i32, u8)>,
+        }
+
+        #[derive(Reflect)]
+        struct Bar(u8);
+
+        #[derive(Reflect)]
+        enum Baz {
+            A,
+            B { x: u64 },
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+        registry.register::<Baz>();
+        registry.register::<u32>();
+
+        let schema = registry.schema();
+        let names = schema
+            .types
+            .iter()
+            .map(|type_schema| type_schema.short_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Bar", "Baz", "Foo", "u32"]);
+
+        let foo = &schema.types[2];
+        assert_eq!(
+            foo.uuid.as_deref(),
+            Some("b2ae1f4c-63f0-4e5c-9a6e-0c8b4e1d2a53")
+        );
+        let fields = match &foo.kind {
+            TypeKindSchema::Struct { fields } => fields,
+            _ => panic!("expected a struct"),
+        };
+        let field_names = fields
+            .iter()
+            .map(|field| field.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(field_names, vec!["a", "c", "d"]);
+        assert_eq!(fields[1].type_name, std::any::type_name::<Vec<Bar>>());
+
+        assert_eq!(
+            <HashMap<String, (i32, u8)> as Typed>::type_info(),
+            TypeInfo::Map(MapInfo {
+                type_name: std::any::type_name::<HashMap<String, (i32, u8)>>(),
+                key_type_name: std::any::type_name::<String>(),
+                value_type_name: std::any::type_name::<(i32, u8)>(),
+            })
+        );
+
+        assert_eq!(
+            schema.types[1].kind,
+            TypeKindSchema::Enum {
+                variants: vec![
+                    VariantSchema {
+                        name: "A".to_string(),
+                        kind: VariantKindSchema::Unit,
+                        fields: Vec::new(),
+                    },
+                    VariantSchema {
+                        name: "B".to_string(),
+                        kind: VariantKindSchema::Struct,
+                        fields: vec![FieldSchema {
+                            name: Some("x".to_string()),
+                            type_name: "u64".to_string(),
+                        }],
+                    },
+                ],
+            }
+        );
+        assert_eq!(schema.types[3].kind, TypeKindSchema::Value);
+
+        let serialized = to_string_pretty(&schema, PrettyConfig::default()).unwrap();
+        let deserialized: RegistrySchema = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, schema);
+    }
+
+    #[test]
+    fn field_attributes() {
+        #[derive(Reflect)]
+        struct Foo {
+            /// The speed of the thing.
+            ///
+            /// Negative speeds go backwards.
+            #[reflect(range(-10.0, 10), step = 0.5, units = "m/s")]
+            speed: f32,
+            #[reflect(ignore)]
+            _cache: u32,
+            #[reflect(hidden, read_only)]
+            id: u32,
+            name: String,
+        }
+
+        #[derive(Reflect)]
+        struct Bar(#[reflect(range(0, 255))] u32, u8);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+
+        let foo = registry.get(std::any::TypeId::of::<Foo>()).unwrap();
+        let speed = foo.field_attributes("speed").unwrap();
+        assert_eq!(
+            speed,
+            &FieldAttributes {
+                range: Some((-10.0, 10.0)),
+                step: Some(0.5),
+                docs: Some("The speed of the thing.\n\nNegative speeds go backwards."),
+                hidden: false,
+                read_only: false,
+                units: Some("m/s"),
+            }
+        );
+        assert!(speed.in_range(-2.5));
+        assert!(!speed.in_range(11.0));
+
+        let id = foo.field_attributes("id").unwrap();
+        assert!(id.hidden && id.read_only);
+        assert_eq!(
+            foo.field_attributes("name"),
+            Some(&FieldAttributes::default())
+        );
+        assert_eq!(foo.field_attributes("_cache"), None);
+
+        let bar = registry.get(std::any::TypeId::of::<Bar>()).unwrap();
+        assert_eq!(bar.field_attributes("0").unwrap().range, Some((0.0, 255.0)));
+        assert_eq!(bar.field_attributes("1"), Some(&FieldAttributes::default()));
+        assert_eq!(bar.field_attributes("2"), None);
+    }
+
+    #[test]
+    fn reflect_diff_patch() {
+        #[derive(Reflect, FromReflect, Clone, Debug, PartialEq)]
+        #[reflect(FromReflect)]
+        struct Foo {
+            a: u32,
+            b: Vec<Bar>,
+            c: HashMap<String, u32>,
+            d: Baz,
+        }
+
+        #[derive(Reflect, FromReflect, Clone, Debug, PartialEq)]
+        #[reflect(FromReflect)]
+        struct Bar(u8);
+
+        #[derive(Reflect, FromReflect, Clone, Debug, PartialEq)]
+        enum Baz {
+            A(u32),
+            B { x: u64 },
+        }
+
+        let mut old_map = HashMap::default();
+        old_map.insert("sword".to_string(), 5);
+        old_map.insert("shield".to_string(), 2);
+        let old = Foo {
+            a: 1,
+            b: vec![Bar(1), Bar(2)],
+            c: old_map,
+            d: Baz::A(1),
+        };
+
+        let mut new_map = HashMap::default();
+        new_map.insert("sword".to_string(), 7);
+        new_map.insert("bow".to_string(), 3);
+        let new = Foo {
+            a: 1,
+            b: vec![Bar(3), Bar(2), Bar(4)],
+            c: new_map,
+            d: Baz::B { x: 2 },
+        };
+
+        let patch = reflect_diff(&old, &new);
+        let mut changes = patch
+            .changes
+            .iter()
+            .map(|change| {
+                let op = match change {
+                    ReflectChange::Modify { .. } => "modify",
+                    ReflectChange::ListInsert { .. } => "list_insert",
+                    ReflectChange::ListRemove { .. } => "list_remove",
+                    ReflectChange::MapInsert { .. } => "map_insert",
+                    ReflectChange::MapRemove { .. } => "map_remove",
+                };
+                format!("{} {}", op, display_path(change.path()))
+            })
+            .collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(
+            changes,
+            vec![
+                "list_insert b",
+                "map_insert c",
+                "map_remove c",
+                "modify b[0].0",
+                "modify c[\"sword\"]",
+                "modify d",
+            ]
+        );
+        assert!(reflect_diff(&old, &old.clone()).is_empty());
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u8>();
+        registry.register::<u32>();
+        registry.register::<u64>();
+        registry.register::<String>();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+        registry.register::<Baz>();
+
+        let serializer = ReflectPatchSerializer::new(&patch, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let patch = ReflectPatchDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = old.clone();
+        reflect_patch(&mut value, &patch, &registry).unwrap();
+        assert_eq!(value, new);
+
+        reflect_patch(&mut value, &patch.inverse(), &registry).unwrap();
+        assert_eq!(value, old);
+
+        let mut missing = DynamicStruct::default();
+        missing.insert("a", 1u32);
+        assert_eq!(
+            reflect_patch(&mut missing, &patch, &registry),
+            Err(ReflectPatchError::InvalidPath("b[0].0".to_string()))
+        );
+
+        // items that cannot be converted to the item type are not inserted
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Qux(u8);
+
+        registry.register::<Qux>();
+        let patch = reflect_diff(&Vec::<Qux>::new(), &vec![Qux(1)]);
+        let mut value = Vec::<Qux>::new();
+        assert_eq!(
+            reflect_patch(&mut value, &patch, &registry),
+            Err(ReflectPatchError::InvalidItem {
+                path: "".to_string(),
+                type_name: std::any::type_name::<Qux>().to_string()
+            })
+        );
+        assert!(value.is_empty());
+
+        let mut dynamic_list = DynamicList::default();
+        reflect_patch(&mut dynamic_list, &patch, &registry).unwrap();
+        assert_eq!(dynamic_list.len(), 1);
+
+        // values are not modified to values of another type
+        let patch = reflect_diff(&Bar(1), &Qux(1));
+        let mut value = Bar(1);
+        assert_eq!(
+            reflect_patch(&mut value, &patch, &registry),
+            Err(ReflectPatchError::InvalidValue {
+                path: "".to_string(),
+                type_name: std::any::type_name::<Qux>().to_string()
+            })
+        );
+        assert_eq!(value, Bar(1));
+    }
+
     #[test]
     fn reflect_take() {
         #[derive(Reflect, Debug, PartialEq)]
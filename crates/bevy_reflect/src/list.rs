@@ -1,4 +1,4 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 use crate::{serde::Serializable, Reflect, ReflectMut, ReflectRef};
 
@@ -7,11 +7,18 @@ pub trait List: Reflect {
     fn get(&self, index: usize) -> Option<&dyn Reflect>;
     fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
     fn push(&mut self, value: Box<dyn Reflect>);
+    /// Inserts a value at `index`, moving the items after it.
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>);
+    /// Removes the item at `index`, moving the items after it.
+    fn remove(&mut self, index: usize) -> Box<dyn Reflect>;
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
     fn iter(&self) -> ListIter;
+    /// Returns the [`TypeId`] of the items, or `None` if the list accepts items of any type, like
+    /// [`DynamicList`].
+    fn item_type_id(&self) -> Option<TypeId>;
     fn clone_dynamic(&self) -> DynamicList {
         DynamicList {
             name: self.type_name().to_string(),
@@ -75,9 +82,21 @@ impl List for DynamicList {
         }
     }
 
+    fn item_type_id(&self) -> Option<TypeId> {
+        None
+    }
+
     fn push(&mut self, value: Box<dyn Reflect>) {
         DynamicList::push_box(self, value);
     }
+
+    fn insert(&mut self, index: usize, value: Box<dyn Reflect>) {
+        self.values.insert(index, value);
+    }
+
+    fn remove(&mut self, index: usize) -> Box<dyn Reflect> {
+        self.values.remove(index)
+    }
 }
 
 // SAFE: any and any_mut both return self
@@ -1,4 +1,7 @@
-use std::{any::Any, collections::hash_map::Entry};
+use std::{
+    any::{Any, TypeId},
+    collections::hash_map::Entry,
+};
 
 use bevy_utils::HashMap;
 
@@ -11,11 +14,25 @@ pub trait Map: Reflect {
     fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect>;
     fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect>;
     fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)>;
+    /// Inserts an entry, returning the previous value of the key.
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>>;
+    /// Removes the entry of the key, returning its value.
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>>;
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
     fn iter(&self) -> MapIter;
+    /// Returns the [`TypeId`] of the keys, or `None` if the map accepts keys of any type, like
+    /// [`DynamicMap`].
+    fn key_type_id(&self) -> Option<TypeId>;
+    /// Returns the [`TypeId`] of the values, or `None` if the map accepts values of any type,
+    /// like [`DynamicMap`].
+    fn value_type_id(&self) -> Option<TypeId>;
     fn clone_dynamic(&self) -> DynamicMap;
 }
 
@@ -41,14 +58,20 @@ impl DynamicMap {
         self.insert_boxed(Box::new(key), Box::new(value));
     }
 
-    pub fn insert_boxed(&mut self, key: Box<dyn Reflect>, value: Box<dyn Reflect>) {
+    pub fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
         match self.indices.entry(key.reflect_hash().expect(HASH_ERROR)) {
             Entry::Occupied(entry) => {
-                self.values[*entry.get()] = (key, value);
+                let (_key, value) = std::mem::replace(&mut self.values[*entry.get()], (key, value));
+                Some(value)
             }
             Entry::Vacant(entry) => {
                 entry.insert(self.values.len());
                 self.values.push((key, value));
+                None
             }
         }
     }
@@ -68,6 +91,35 @@ impl Map for DynamicMap {
             .map(move |index| &mut *self.values.get_mut(index).unwrap().1)
     }
 
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
+        DynamicMap::insert_boxed(self, key, value)
+    }
+
+    fn key_type_id(&self) -> Option<TypeId> {
+        None
+    }
+
+    fn value_type_id(&self) -> Option<TypeId> {
+        None
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        let (_key, value) = self.values.remove(index);
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(value)
+    }
+
     fn len(&self) -> usize {
         self.values.len()
     }
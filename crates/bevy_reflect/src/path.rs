@@ -1,6 +1,6 @@
-use std::num::ParseIntError;
+use std::{any::TypeId, num::ParseIntError};
 
-use crate::{Reflect, ReflectMut, ReflectRef};
+use crate::{Map, Reflect, ReflectDeserialize, ReflectMut, ReflectRef, TypeRegistry, VariantType};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Error)]
@@ -16,20 +16,35 @@ pub enum ReflectPathError<'a> {
     },
     #[error("the current list doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
+    #[error("the current map doesn't have a value with the given key")]
+    InvalidMapKey { index: usize, key: &'a str },
     #[error("encountered an unexpected token")]
     UnexpectedToken { index: usize, token: &'a str },
     #[error("expected a token, but it wasn't there.")]
     ExpectedToken { index: usize, token: &'a str },
     #[error("expected a struct, but found a different reflect value")]
     ExpectedStruct { index: usize },
-    #[error("expected a list, but found a different reflect value")]
+    #[error("expected a list or map, but found a different reflect value")]
     ExpectedList { index: usize },
+    #[error("expected a map, but found a different reflect value")]
+    ExpectedMap { index: usize },
     #[error("failed to parse a usize")]
     IndexParseError(#[from] ParseIntError),
     #[error("failed to downcast to the path result to the given type")]
     InvalidDowncast,
+    #[error("the type `{type_name}` is not registered with `ReflectDeserialize`")]
+    MissingDeserializer { type_name: String },
+    #[error("failed to parse the value: {0}")]
+    InvalidValue(String),
 }
 
+/// Reads and writes the values inside a value with paths like `x.bar.baz`, `y[1]` and
+/// `inventory["sword"].damage`.
+///
+/// Fields of tuple structs and tuple variants are accessed with their index, like `z.0`. List items
+/// are accessed with their index in brackets, and map values with their key in brackets, where
+/// string keys are quoted and integer keys are not, like `["sword"]` or `[1]`. Quotes in keys
+/// cannot be escaped, so quoted keys cannot contain `"`.
 pub trait GetPath {
     fn path<'r, 'p>(&'r self, path: &'p str) -> Result<&'r dyn Reflect, ReflectPathError<'p>>;
     fn path_mut<'r, 'p>(
@@ -56,6 +71,66 @@ pub trait GetPath {
                 .ok_or(ReflectPathError::InvalidDowncast)
         })
     }
+
+    /// Sets the value at `path` to the value read from `deserializer` with the
+    /// [`ReflectDeserialize`] of the type of the value at `path`. Checking that `deserializer`
+    /// has no input left, like with `ron::de::Deserializer::end`, is up to the caller.
+    fn set_path_from_deserializer<'p, 'de, D>(
+        &mut self,
+        path: &'p str,
+        deserializer: D,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), ReflectPathError<'p>>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let target = self.path_mut(path)?;
+        let value = deserialize_value(&*target, deserializer, type_registry)?;
+        target.apply(&*value);
+        Ok(())
+    }
+
+    /// Sets the value at `path` to the value written in RON in `value`, like `42` or `Some(3)`,
+    /// like [`set_path_from_deserializer`](GetPath::set_path_from_deserializer). Fails if `value`
+    /// has input left after the value.
+    #[cfg(feature = "ron")]
+    fn set_path_from_str<'p>(
+        &mut self,
+        path: &'p str,
+        value: &str,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), ReflectPathError<'p>> {
+        let target = self.path_mut(path)?;
+        let mut deserializer = ron::de::Deserializer::from_str(value)
+            .map_err(|err| ReflectPathError::InvalidValue(err.to_string()))?;
+        let value = deserialize_value(&*target, &mut deserializer, type_registry)?;
+        deserializer
+            .end()
+            .map_err(|err| ReflectPathError::InvalidValue(err.to_string()))?;
+        target.apply(&*value);
+        Ok(())
+    }
+}
+
+/// Reads a value of the type of `target` from `deserializer`, with the [`ReflectDeserialize`] of
+/// the type.
+fn deserialize_value<'p, 'de, D>(
+    target: &dyn Reflect,
+    deserializer: D,
+    type_registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ReflectPathError<'p>>
+where
+    D: serde::Deserializer<'de>,
+{
+    let reflect_deserialize = type_registry
+        .get_with_name(target.type_name())
+        .and_then(|registration| registration.data::<ReflectDeserialize>())
+        .ok_or_else(|| ReflectPathError::MissingDeserializer {
+            type_name: target.type_name().to_string(),
+        })?;
+    reflect_deserialize
+        .deserialize(deserializer)
+        .map_err(|err| ReflectPathError::InvalidValue(err.to_string()))
 }
 
 impl<T: Reflect> GetPath for T {
@@ -88,29 +163,25 @@ impl GetPath for dyn Reflect {
                     }
                 }
                 Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_ref() {
-                            ReflectRef::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedList {
-                                    index: current_index,
-                                })
-                            }
+                    current = match next_token(path, &mut index) {
+                        Some(Token::Ident(value)) => {
+                            read_element(current, value, false, current_index)?
                         }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
+                        Some(Token::Key(value)) => {
+                            read_element(current, value, true, current_index)?
+                        }
+                        Some(Token::Quote) => {
+                            return Err(ReflectPathError::ExpectedToken {
+                                index: current_index,
+                                token: "\"",
+                            })
+                        }
+                        _ => {
+                            return Err(ReflectPathError::ExpectedIdent {
+                                index: current_index,
+                            })
+                        }
+                    };
 
                     if let Some(Token::CloseBracket) = next_token(path, &mut index) {
                     } else {
@@ -129,6 +200,12 @@ impl GetPath for dyn Reflect {
                 Token::Ident(value) => {
                     current = read_field(current, value, current_index)?;
                 }
+                Token::Key(_) | Token::Quote => {
+                    return Err(ReflectPathError::UnexpectedToken {
+                        index: current_index,
+                        token: "\"",
+                    })
+                }
             }
         }
 
@@ -154,29 +231,25 @@ impl GetPath for dyn Reflect {
                     }
                 }
                 Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_mut() {
-                            ReflectMut::List(reflect_list) => {
-                                let list_index = value.parse::<usize>()?;
-                                let list_item = reflect_list.get_mut(list_index).ok_or(
-                                    ReflectPathError::InvalidListIndex {
-                                        index: current_index,
-                                        list_index,
-                                    },
-                                )?;
-                                current = list_item;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedStruct {
-                                    index: current_index,
-                                })
-                            }
+                    current = match next_token(path, &mut index) {
+                        Some(Token::Ident(value)) => {
+                            read_element_mut(current, value, false, current_index)?
                         }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
+                        Some(Token::Key(value)) => {
+                            read_element_mut(current, value, true, current_index)?
+                        }
+                        Some(Token::Quote) => {
+                            return Err(ReflectPathError::ExpectedToken {
+                                index: current_index,
+                                token: "\"",
+                            })
+                        }
+                        _ => {
+                            return Err(ReflectPathError::ExpectedIdent {
+                                index: current_index,
+                            })
+                        }
+                    };
 
                     if let Some(Token::CloseBracket) = next_token(path, &mut index) {
                     } else {
@@ -195,6 +268,12 @@ impl GetPath for dyn Reflect {
                 Token::Ident(value) => {
                     current = read_field_mut(current, value, current_index)?;
                 }
+                Token::Key(_) | Token::Quote => {
+                    return Err(ReflectPathError::UnexpectedToken {
+                        index: current_index,
+                        token: "\"",
+                    })
+                }
             }
         }
 
@@ -225,6 +304,23 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Enum(reflect_enum) => match reflect_enum.variant_type() {
+            VariantType::Tuple => {
+                let tuple_index = field.parse::<usize>()?;
+                Ok(reflect_enum.field_at(tuple_index).ok_or(
+                    ReflectPathError::InvalidTupleStructIndex {
+                        index: current_index,
+                        tuple_struct_index: tuple_index,
+                    },
+                )?)
+            }
+            _ => Ok(reflect_enum
+                .field(field)
+                .ok_or(ReflectPathError::InvalidField {
+                    index: current_index,
+                    field,
+                })?),
+        },
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -254,17 +350,131 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Enum(reflect_enum) => match reflect_enum.variant_type() {
+            VariantType::Tuple => {
+                let tuple_index = field.parse::<usize>()?;
+                Ok(reflect_enum.field_at_mut(tuple_index).ok_or(
+                    ReflectPathError::InvalidTupleStructIndex {
+                        index: current_index,
+                        tuple_struct_index: tuple_index,
+                    },
+                )?)
+            }
+            _ => Ok(reflect_enum
+                .field_mut(field)
+                .ok_or(ReflectPathError::InvalidField {
+                    index: current_index,
+                    field,
+                })?),
+        },
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
     }
 }
 
+/// Reads the item of a list or the value of a map, with an index or key written in brackets.
+fn read_element<'r, 'p>(
+    current: &'r dyn Reflect,
+    value: &'p str,
+    quoted: bool,
+    current_index: usize,
+) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
+    match current.reflect_ref() {
+        ReflectRef::List(reflect_list) if !quoted => {
+            let list_index = value.parse::<usize>()?;
+            Ok(reflect_list
+                .get(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
+                    index: current_index,
+                    list_index,
+                })?)
+        }
+        ReflectRef::Map(reflect_map) => Ok(map_key(reflect_map, value, quoted)
+            .and_then(|key| reflect_map.get(&*key))
+            .ok_or(ReflectPathError::InvalidMapKey {
+                index: current_index,
+                key: value,
+            })?),
+        _ if quoted => Err(ReflectPathError::ExpectedMap {
+            index: current_index,
+        }),
+        _ => Err(ReflectPathError::ExpectedList {
+            index: current_index,
+        }),
+    }
+}
+
+fn read_element_mut<'r, 'p>(
+    current: &'r mut dyn Reflect,
+    value: &'p str,
+    quoted: bool,
+    current_index: usize,
+) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
+    match current.reflect_mut() {
+        ReflectMut::List(reflect_list) if !quoted => {
+            let list_index = value.parse::<usize>()?;
+            Ok(reflect_list
+                .get_mut(list_index)
+                .ok_or(ReflectPathError::InvalidListIndex {
+                    index: current_index,
+                    list_index,
+                })?)
+        }
+        ReflectMut::Map(reflect_map) => {
+            let invalid_key = || ReflectPathError::InvalidMapKey {
+                index: current_index,
+                key: value,
+            };
+            let key = map_key(reflect_map, value, quoted).ok_or_else(invalid_key)?;
+            Ok(reflect_map.get_mut(&*key).ok_or_else(invalid_key)?)
+        }
+        _ if quoted => Err(ReflectPathError::ExpectedMap {
+            index: current_index,
+        }),
+        _ => Err(ReflectPathError::ExpectedList {
+            index: current_index,
+        }),
+    }
+}
+
+/// Returns the key of `map` that is written in a path as `value`, like `"sword"` for a quoted key
+/// or `1` for an integer key. Unquoted keys are parsed as the integer key type of the map, or as
+/// every integer type for maps without a key type.
+fn map_key(map: &dyn Map, value: &str, quoted: bool) -> Option<Box<dyn Reflect>> {
+    if quoted {
+        let key = value.to_string();
+        return if map.get(&key).is_some() {
+            Some(Box::new(key))
+        } else {
+            None
+        };
+    }
+    let key_type_id = map.key_type_id();
+    macro_rules! integer_key {
+        ($($ty:ty),*) => {
+            $(if key_type_id.map_or(true, |key_type_id| key_type_id == TypeId::of::<$ty>()) {
+                if let Ok(key) = value.parse::<$ty>() {
+                    if map.get(&key).is_some() {
+                        return Some(Box::new(key));
+                    }
+                }
+            })*
+        };
+    }
+    integer_key!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    None
+}
+
 enum Token<'a> {
     Dot,
     OpenBracket,
     CloseBracket,
     Ident(&'a str),
+    /// A quoted map key, without the quotes
+    Key(&'a str),
+    /// A quote without a closing quote
+    Quote,
 }
 
 fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
@@ -285,6 +495,20 @@ fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
             *index += 1;
             return Some(Token::CloseBracket);
         }
+        '"' => {
+            *index += 1;
+            return Some(match path[*index..].find('"') {
+                Some(end) => {
+                    let key = Token::Key(&path[*index..*index + end]);
+                    *index += end + 1;
+                    key
+                }
+                None => {
+                    *index = path.len();
+                    Token::Quote
+                }
+            });
+        }
         _ => {}
     }
 
@@ -310,6 +534,8 @@ mod tests {
     use super::GetPath;
     use crate as bevy_reflect;
     use crate::*;
+    use bevy_utils::HashMap;
+
     #[test]
     fn reflect_path() {
         #[derive(Reflect)]
@@ -318,6 +544,9 @@ mod tests {
             x: B,
             y: Vec<C>,
             z: D,
+            e: F,
+            m: HashMap<String, C>,
+            n: HashMap<u32, usize>,
         }
 
         #[derive(Reflect)]
@@ -326,7 +555,7 @@ mod tests {
             bar: C,
         }
 
-        #[derive(Reflect)]
+        #[derive(Reflect, Default, Clone)]
         struct C {
             baz: f32,
         }
@@ -337,6 +566,18 @@ mod tests {
         #[derive(Reflect)]
         struct E(f32, usize);
 
+        #[derive(Reflect)]
+        enum F {
+            Tuple(C),
+            Struct { value: usize },
+        }
+
+        let mut m = HashMap::default();
+        m.insert("sword".to_string(), C { baz: 7.0 });
+        m.insert("shield.old]".to_string(), C { baz: 8.0 });
+        let mut n = HashMap::default();
+        n.insert(3, 30);
+
         let mut a = A {
             w: 1,
             x: B {
@@ -345,6 +586,9 @@ mod tests {
             },
             y: vec![C { baz: 1.0 }, C { baz: 2.0 }],
             z: D(E(10.0, 42)),
+            e: F::Tuple(C { baz: 5.0 }),
+            m,
+            n,
         };
 
         assert_eq!(*a.get_path::<usize>("w").unwrap(), 1);
@@ -352,10 +596,26 @@ mod tests {
         assert_eq!(*a.get_path::<f32>("x.bar.baz").unwrap(), 3.14);
         assert_eq!(*a.get_path::<f32>("y[1].baz").unwrap(), 2.0);
         assert_eq!(*a.get_path::<usize>("z.0.1").unwrap(), 42);
+        assert_eq!(*a.get_path::<f32>("e.0.baz").unwrap(), 5.0);
+
+        assert_eq!(*a.get_path::<f32>("m[\"sword\"].baz").unwrap(), 7.0);
+        assert_eq!(*a.get_path::<f32>("m[\"shield.old]\"].baz").unwrap(), 8.0);
+        assert_eq!(*a.get_path::<usize>("n[3]").unwrap(), 30);
+        let dynamic_map: Box<dyn Reflect> = Box::new(a.n.clone_dynamic());
+        assert_eq!(*dynamic_map.get_path::<usize>("[3]").unwrap(), 30);
 
         *a.get_path_mut::<f32>("y[1].baz").unwrap() = 3.0;
         assert_eq!(a.y[1].baz, 3.0);
 
+        *a.get_path_mut::<f32>("m[\"sword\"].baz").unwrap() = 9.0;
+        assert_eq!(a.m["sword"].baz, 9.0);
+        *a.get_path_mut::<usize>("n[3]").unwrap() = 31;
+        assert_eq!(a.n[&3], 31);
+
+        a.e = F::Struct { value: 1 };
+        *a.get_path_mut::<usize>("e.value").unwrap() = 2;
+        assert_eq!(*a.get_path::<usize>("e.value").unwrap(), 2);
+
         assert_eq!(
             a.path("x.notreal").err().unwrap(),
             ReflectPathError::InvalidField {
@@ -383,5 +643,125 @@ mod tests {
             a.path("y[badindex]"),
             Err(ReflectPathError::IndexParseError(_))
         ));
+
+        assert_eq!(
+            a.path("m[\"axe\"]").err().unwrap(),
+            ReflectPathError::InvalidMapKey {
+                index: 2,
+                key: "axe"
+            }
+        );
+
+        assert_eq!(
+            a.path("m[sword]").err().unwrap(),
+            ReflectPathError::InvalidMapKey {
+                index: 2,
+                key: "sword"
+            }
+        );
+
+        assert_eq!(
+            a.path("y[\"0\"]").err().unwrap(),
+            ReflectPathError::ExpectedMap { index: 2 }
+        );
+
+        assert_eq!(
+            a.path("m[\"sword").err().unwrap(),
+            ReflectPathError::ExpectedToken {
+                index: 2,
+                token: "\""
+            }
+        );
+    }
+
+    #[test]
+    fn set_path_from_deserializer() {
+        #[derive(Reflect)]
+        struct A {
+            x: B,
+            inventory: HashMap<String, B>,
+            name: String,
+        }
+
+        #[derive(Reflect, Clone)]
+        struct B {
+            damage: u32,
+            range: Option<u32>,
+        }
+
+        let mut inventory = HashMap::default();
+        inventory.insert(
+            "sword".to_string(),
+            B {
+                damage: 5,
+                range: None,
+            },
+        );
+        let mut a = A {
+            x: B {
+                damage: 1,
+                range: None,
+            },
+            inventory,
+            name: "a".to_string(),
+        };
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<Option<u32>>();
+        registry.register::<String>();
+
+        fn ron(value: &str) -> ron::de::Deserializer<'_> {
+            ron::de::Deserializer::from_str(value).unwrap()
+        }
+
+        a.set_path_from_deserializer("inventory[\"sword\"].damage", &mut ron("42"), &registry)
+            .unwrap();
+        assert_eq!(a.inventory["sword"].damage, 42);
+        a.set_path_from_deserializer("x.range", &mut ron("Some(3)"), &registry)
+            .unwrap();
+        assert_eq!(a.x.range, Some(3));
+        a.set_path_from_deserializer("name", &mut ron("\"b\""), &registry)
+            .unwrap();
+        assert_eq!(a.name, "b");
+
+        assert!(matches!(
+            a.set_path_from_deserializer("x.damage", &mut ron("-1"), &registry),
+            Err(ReflectPathError::InvalidValue(_))
+        ));
+        assert_eq!(
+            a.set_path_from_deserializer("x", &mut ron("(damage: 1)"), &registry),
+            Err(ReflectPathError::MissingDeserializer {
+                type_name: std::any::type_name::<B>().to_string()
+            })
+        );
+        assert_eq!(a.x.damage, 1);
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn set_path_from_str() {
+        #[derive(Reflect)]
+        struct A {
+            x: u32,
+            range: Option<u32>,
+        }
+
+        let mut a = A { x: 1, range: None };
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<Option<u32>>();
+
+        a.set_path_from_str("x", "42", &registry).unwrap();
+        assert_eq!(a.x, 42);
+        a.set_path_from_str("range", "Some(3)", &registry).unwrap();
+        assert_eq!(a.range, Some(3));
+
+        // trailing input is an error, and the value is left unchanged
+        assert!(matches!(
+            a.set_path_from_str("x", "7 8", &registry),
+            Err(ReflectPathError::InvalidValue(_))
+        ));
+        assert_eq!(a.x, 42);
     }
 }
@@ -0,0 +1,137 @@
+use crate::{ReflectTypeUuid, TypeInfo, TypeRegistration, TypeRegistry, VariantType};
+use serde::{Deserialize, Serialize};
+
+/// A machine-readable description of the types in a [`TypeRegistry`], which tools can use to
+/// author and check serialized scenes without linking the types themselves. It can be written
+/// with any serde format.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrySchema {
+    /// The registered types, sorted by name
+    pub types: Vec<TypeSchema>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeSchema {
+    pub name: String,
+    pub short_name: String,
+    /// The uuid of the type, if it is registered with `#[reflect(TypeUuid)]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    pub kind: TypeKindSchema,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeKindSchema {
+    Struct {
+        fields: Vec<FieldSchema>,
+    },
+    TupleStruct {
+        fields: Vec<FieldSchema>,
+    },
+    Tuple {
+        fields: Vec<FieldSchema>,
+    },
+    List {
+        item: String,
+    },
+    Map {
+        key: String,
+        value: String,
+    },
+    Enum {
+        variants: Vec<VariantSchema>,
+    },
+    /// A value that is serialized with its own serde implementation
+    Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSchema {
+    /// The name of the field, for fields of structs and struct variants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub type_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantSchema {
+    pub name: String,
+    pub kind: VariantKindSchema,
+    pub fields: Vec<FieldSchema>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantKindSchema {
+    Struct,
+    Tuple,
+    Unit,
+}
+
+impl TypeRegistry {
+    /// Describes every registered type.
+    pub fn schema(&self) -> RegistrySchema {
+        let mut types = self.iter().map(TypeSchema::new).collect::<Vec<_>>();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+        RegistrySchema { types }
+    }
+}
+
+impl TypeSchema {
+    pub fn new(registration: &TypeRegistration) -> Self {
+        let kind = match registration.type_info() {
+            TypeInfo::Struct(info) => TypeKindSchema::Struct {
+                fields: fields_schema(&info.fields),
+            },
+            TypeInfo::TupleStruct(info) => TypeKindSchema::TupleStruct {
+                fields: fields_schema(&info.fields),
+            },
+            TypeInfo::Tuple(info) => TypeKindSchema::Tuple {
+                fields: fields_schema(&info.fields),
+            },
+            TypeInfo::List(info) => TypeKindSchema::List {
+                item: info.item_type_name.to_string(),
+            },
+            TypeInfo::Map(info) => TypeKindSchema::Map {
+                key: info.key_type_name.to_string(),
+                value: info.value_type_name.to_string(),
+            },
+            TypeInfo::Enum(info) => TypeKindSchema::Enum {
+                variants: info
+                    .variants
+                    .iter()
+                    .map(|variant| VariantSchema {
+                        name: variant.name.to_string(),
+                        kind: match variant.variant_type {
+                            VariantType::Struct => VariantKindSchema::Struct,
+                            VariantType::Tuple => VariantKindSchema::Tuple,
+                            VariantType::Unit => VariantKindSchema::Unit,
+                        },
+                        fields: fields_schema(&variant.fields),
+                    })
+                    .collect(),
+            },
+            TypeInfo::Value(_) => TypeKindSchema::Value,
+        };
+
+        TypeSchema {
+            name: registration.name().to_string(),
+            short_name: registration.short_name().to_string(),
+            uuid: registration
+                .data::<ReflectTypeUuid>()
+                .map(|type_uuid| type_uuid.type_uuid().to_string()),
+            kind,
+        }
+    }
+}
+
+fn fields_schema(fields: &[crate::FieldInfo]) -> Vec<FieldSchema> {
+    fields
+        .iter()
+        .map(|field| FieldSchema {
+            name: field.name.map(|name| name.to_string()),
+            type_name: field.type_name.to_string(),
+        })
+        .collect()
+}
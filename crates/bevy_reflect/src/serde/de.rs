@@ -1,6 +1,8 @@
 use crate::{
-    serde::type_fields, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct,
-    Reflect, ReflectDeserialize, TypeRegistry,
+    serde::{patch_fields, type_fields},
+    DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct,
+    DynamicVariant, PathSegment, Reflect, ReflectChange, ReflectDeserialize, ReflectPatch,
+    TypeRegistry,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -148,12 +150,13 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                     type_name = Some(map.next_value()?);
                 }
                 type_fields::MAP => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let map = map.next_value_seed(MapDeserializer {
+                    let mut map = map.next_value_seed(MapDeserializer {
                         registry: self.registry,
                     })?;
+                    map.set_name(type_name);
                     return Ok(Box::new(map));
                 }
                 type_fields::STRUCT => {
@@ -177,23 +180,35 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                     return Ok(Box::new(tuple_struct));
                 }
                 type_fields::TUPLE => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let tuple = map.next_value_seed(TupleDeserializer {
+                    let mut tuple = map.next_value_seed(TupleDeserializer {
                         registry: self.registry,
                     })?;
+                    tuple.set_name(type_name);
                     return Ok(Box::new(tuple));
                 }
                 type_fields::LIST => {
-                    let _type_name = type_name
+                    let type_name = type_name
                         .take()
                         .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
-                    let list = map.next_value_seed(ListDeserializer {
+                    let mut list = map.next_value_seed(ListDeserializer {
                         registry: self.registry,
                     })?;
+                    list.set_name(type_name);
                     return Ok(Box::new(list));
                 }
+                type_fields::ENUM => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut dynamic_enum = map.next_value_seed(EnumDeserializer {
+                        registry: self.registry,
+                    })?;
+                    dynamic_enum.set_name(type_name);
+                    return Ok(Box::new(dynamic_enum));
+                }
                 type_fields::VALUE => {
                     let type_name = type_name
                         .take()
@@ -452,3 +467,337 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
         Ok(tuple)
     }
 }
+
+struct EnumDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EnumDeserializer<'a> {
+    type Value = DynamicEnum;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EnumVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct EnumVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("enum value")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut variant_name: Option<String> = None;
+        let mut variant = DynamicVariant::Unit;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                type_fields::VARIANT => {
+                    variant_name = Some(map.next_value()?);
+                }
+                type_fields::STRUCT => {
+                    variant = DynamicVariant::Struct(map.next_value_seed(StructDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                type_fields::TUPLE => {
+                    variant = DynamicVariant::Tuple(map.next_value_seed(TupleDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                _ => {
+                    return Err(de::Error::unknown_field(
+                        key.as_str(),
+                        &[
+                            type_fields::VARIANT,
+                            type_fields::STRUCT,
+                            type_fields::TUPLE,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let variant_name =
+            variant_name.ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+        let mut dynamic_enum = DynamicEnum::default();
+        dynamic_enum.set_variant(&variant_name, variant);
+        Ok(dynamic_enum)
+    }
+}
+
+/// Deserializes a [`ReflectPatch`] serialized with
+/// [`ReflectPatchSerializer`](crate::serde::ReflectPatchSerializer). Like the values deserialized
+/// with [`ReflectDeserializer`], the values of the patch are dynamic values.
+pub struct ReflectPatchDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectPatchDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        ReflectPatchDeserializer { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectPatchDeserializer<'a> {
+    type Value = ReflectPatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ReflectPatchVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ReflectPatchVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectPatchVisitor<'a> {
+    type Value = ReflectPatch;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of changes")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut patch = ReflectPatch::default();
+        while let Some(change) = seq.next_element_seed(ReflectChangeDeserializer {
+            registry: self.registry,
+        })? {
+            patch.changes.push(change);
+        }
+        Ok(patch)
+    }
+}
+
+struct ReflectChangeDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectChangeDeserializer<'a> {
+    type Value = ReflectChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ReflectChangeVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ReflectChangeVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectChangeVisitor<'a> {
+    type Value = ReflectChange;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("change")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut op: Option<String> = None;
+        let mut path = None;
+        let mut old = None;
+        let mut new = None;
+        let mut index = None;
+        let mut key = None;
+        let mut value = None;
+        while let Some(field) = map.next_key::<String>()? {
+            match field.as_str() {
+                patch_fields::OP => op = Some(map.next_value()?),
+                patch_fields::PATH => {
+                    path = Some(map.next_value_seed(PathDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                patch_fields::OLD => {
+                    old = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                patch_fields::NEW => {
+                    new = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                patch_fields::INDEX => index = Some(map.next_value()?),
+                patch_fields::KEY => {
+                    key = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                patch_fields::VALUE => {
+                    value = Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                _ => {
+                    return Err(de::Error::unknown_field(
+                        field.as_str(),
+                        &[
+                            patch_fields::OP,
+                            patch_fields::PATH,
+                            patch_fields::OLD,
+                            patch_fields::NEW,
+                            patch_fields::INDEX,
+                            patch_fields::KEY,
+                            patch_fields::VALUE,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let op = op.ok_or_else(|| de::Error::missing_field(patch_fields::OP))?;
+        let path = path.ok_or_else(|| de::Error::missing_field(patch_fields::PATH))?;
+        let missing = de::Error::missing_field;
+        Ok(match op.as_str() {
+            patch_fields::MODIFY => ReflectChange::Modify {
+                path,
+                old: old.ok_or_else(|| missing(patch_fields::OLD))?,
+                new: new.ok_or_else(|| missing(patch_fields::NEW))?,
+            },
+            patch_fields::LIST_INSERT => ReflectChange::ListInsert {
+                path,
+                index: index.ok_or_else(|| missing(patch_fields::INDEX))?,
+                value: value.ok_or_else(|| missing(patch_fields::VALUE))?,
+            },
+            patch_fields::LIST_REMOVE => ReflectChange::ListRemove {
+                path,
+                index: index.ok_or_else(|| missing(patch_fields::INDEX))?,
+                value: value.ok_or_else(|| missing(patch_fields::VALUE))?,
+            },
+            patch_fields::MAP_INSERT => ReflectChange::MapInsert {
+                path,
+                key: key.ok_or_else(|| missing(patch_fields::KEY))?,
+                value: value.ok_or_else(|| missing(patch_fields::VALUE))?,
+            },
+            patch_fields::MAP_REMOVE => ReflectChange::MapRemove {
+                path,
+                key: key.ok_or_else(|| missing(patch_fields::KEY))?,
+                value: value.ok_or_else(|| missing(patch_fields::VALUE))?,
+            },
+            _ => {
+                return Err(de::Error::unknown_variant(
+                    op.as_str(),
+                    &[
+                        patch_fields::MODIFY,
+                        patch_fields::LIST_INSERT,
+                        patch_fields::LIST_REMOVE,
+                        patch_fields::MAP_INSERT,
+                        patch_fields::MAP_REMOVE,
+                    ],
+                ))
+            }
+        })
+    }
+}
+
+struct PathDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PathDeserializer<'a> {
+    type Value = Vec<PathSegment>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PathVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct PathVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PathVisitor<'a> {
+    type Value = Vec<PathSegment>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("path")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut path = Vec::new();
+        while let Some(segment) = seq.next_element_seed(PathSegmentDeserializer {
+            registry: self.registry,
+        })? {
+            path.push(segment);
+        }
+        Ok(path)
+    }
+}
+
+struct PathSegmentDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PathSegmentDeserializer<'a> {
+    type Value = PathSegment;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PathSegmentVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct PathSegmentVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PathSegmentVisitor<'a> {
+    type Value = PathSegment;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("path segment")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let field = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        match field.as_str() {
+            patch_fields::FIELD => Ok(PathSegment::Field(map.next_value()?)),
+            patch_fields::INDEX => Ok(PathSegment::Index(map.next_value()?)),
+            patch_fields::KEY => Ok(PathSegment::Key(
+                map.next_value_seed(ReflectDeserializer::new(self.registry))?,
+            )),
+            _ => Err(de::Error::unknown_field(
+                field.as_str(),
+                &[patch_fields::FIELD, patch_fields::INDEX, patch_fields::KEY],
+            )),
+        }
+    }
+}
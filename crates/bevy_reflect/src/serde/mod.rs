@@ -4,7 +4,8 @@ mod ser;
 pub use de::*;
 pub use ser::*;
 
-pub(crate) mod type_fields {
+/// The field names of the maps that reflected values are serialized as.
+pub mod type_fields {
     pub const TYPE: &str = "type";
     pub const MAP: &str = "map";
     pub const STRUCT: &str = "struct";
@@ -12,4 +13,23 @@ pub(crate) mod type_fields {
     pub const TUPLE: &str = "tuple";
     pub const LIST: &str = "list";
     pub const VALUE: &str = "value";
+    pub const ENUM: &str = "enum";
+    pub const VARIANT: &str = "variant";
+}
+
+pub(crate) mod patch_fields {
+    pub const OP: &str = "op";
+    pub const PATH: &str = "path";
+    pub const OLD: &str = "old";
+    pub const NEW: &str = "new";
+    pub const INDEX: &str = "index";
+    pub const KEY: &str = "key";
+    pub const VALUE: &str = "value";
+    pub const FIELD: &str = "field";
+
+    pub const MODIFY: &str = "modify";
+    pub const LIST_INSERT: &str = "list_insert";
+    pub const LIST_REMOVE: &str = "list_remove";
+    pub const MAP_INSERT: &str = "map_insert";
+    pub const MAP_REMOVE: &str = "map_remove";
 }
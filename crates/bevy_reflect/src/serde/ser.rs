@@ -1,5 +1,7 @@
 use crate::{
-    serde::type_fields, List, Map, Reflect, ReflectRef, Struct, Tuple, TupleStruct, TypeRegistry,
+    serde::{patch_fields, type_fields},
+    Enum, List, Map, PathSegment, Reflect, ReflectChange, ReflectPatch, ReflectRef, Struct, Tuple,
+    TupleStruct, TypeRegistry, VariantType,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -72,6 +74,11 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Enum(value) => EnumSerializer {
+                enum_value: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Value(value) => ReflectValueSerializer {
                 registry: self.registry,
                 value,
@@ -313,3 +320,223 @@ impl<'a> Serialize for ListValueSerializer<'a> {
         state.end()
     }
 }
+
+pub struct EnumSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry(type_fields::TYPE, self.enum_value.type_name())?;
+        state.serialize_entry(
+            type_fields::ENUM,
+            &EnumValueSerializer {
+                enum_value: self.enum_value,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EnumValueSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant_type = self.enum_value.variant_type();
+        let len = if variant_type == VariantType::Unit {
+            1
+        } else {
+            2
+        };
+        let mut state = serializer.serialize_map(Some(len))?;
+        state.serialize_entry(type_fields::VARIANT, self.enum_value.variant_name())?;
+        let fields = VariantFieldsSerializer {
+            enum_value: self.enum_value,
+            registry: self.registry,
+        };
+        match variant_type {
+            VariantType::Struct => state.serialize_entry(type_fields::STRUCT, &fields)?,
+            VariantType::Tuple => state.serialize_entry(type_fields::TUPLE, &fields)?,
+            VariantType::Unit => {}
+        }
+        state.end()
+    }
+}
+
+/// Serializes the fields of a struct variant as a map, and the fields of a tuple variant as a
+/// sequence.
+pub struct VariantFieldsSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantFieldsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.enum_value.variant_type() == VariantType::Struct {
+            let mut state = serializer.serialize_map(Some(self.enum_value.field_len()))?;
+            for (index, value) in self.enum_value.iter_fields().enumerate() {
+                let key = self.enum_value.name_at(index).unwrap();
+                state.serialize_entry(key, &ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        } else {
+            let mut state = serializer.serialize_seq(Some(self.enum_value.field_len()))?;
+            for value in self.enum_value.iter_fields() {
+                state.serialize_element(&ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        }
+    }
+}
+
+/// Serializes a [`ReflectPatch`] as a list of changes, like
+/// `{"op": "list_insert", "path": [{"field": "a"}], "index": 0, "value": ...}`.
+pub struct ReflectPatchSerializer<'a> {
+    pub patch: &'a ReflectPatch,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectPatchSerializer<'a> {
+    pub fn new(patch: &'a ReflectPatch, registry: &'a TypeRegistry) -> Self {
+        ReflectPatchSerializer { patch, registry }
+    }
+}
+
+impl<'a> Serialize for ReflectPatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.patch.changes.len()))?;
+        for change in self.patch.changes.iter() {
+            state.serialize_element(&ReflectChangeSerializer {
+                change,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct ReflectChangeSerializer<'a> {
+    change: &'a ReflectChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ReflectChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(4))?;
+        let path = PathSerializer {
+            path: self.change.path(),
+            registry: self.registry,
+        };
+        match self.change {
+            ReflectChange::Modify { old, new, .. } => {
+                state.serialize_entry(patch_fields::OP, patch_fields::MODIFY)?;
+                state.serialize_entry(patch_fields::PATH, &path)?;
+                state.serialize_entry(
+                    patch_fields::OLD,
+                    &ReflectSerializer::new(&**old, self.registry),
+                )?;
+                state.serialize_entry(
+                    patch_fields::NEW,
+                    &ReflectSerializer::new(&**new, self.registry),
+                )?;
+            }
+            ReflectChange::ListInsert { index, value, .. }
+            | ReflectChange::ListRemove { index, value, .. } => {
+                let op = match self.change {
+                    ReflectChange::ListInsert { .. } => patch_fields::LIST_INSERT,
+                    _ => patch_fields::LIST_REMOVE,
+                };
+                state.serialize_entry(patch_fields::OP, op)?;
+                state.serialize_entry(patch_fields::PATH, &path)?;
+                state.serialize_entry(patch_fields::INDEX, index)?;
+                state.serialize_entry(
+                    patch_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+            ReflectChange::MapInsert { key, value, .. }
+            | ReflectChange::MapRemove { key, value, .. } => {
+                let op = match self.change {
+                    ReflectChange::MapInsert { .. } => patch_fields::MAP_INSERT,
+                    _ => patch_fields::MAP_REMOVE,
+                };
+                state.serialize_entry(patch_fields::OP, op)?;
+                state.serialize_entry(patch_fields::PATH, &path)?;
+                state.serialize_entry(
+                    patch_fields::KEY,
+                    &ReflectSerializer::new(&**key, self.registry),
+                )?;
+                state.serialize_entry(
+                    patch_fields::VALUE,
+                    &ReflectSerializer::new(&**value, self.registry),
+                )?;
+            }
+        }
+        state.end()
+    }
+}
+
+struct PathSerializer<'a> {
+    path: &'a [PathSegment],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for PathSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.path.len()))?;
+        for segment in self.path.iter() {
+            state.serialize_element(&PathSegmentSerializer {
+                segment,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct PathSegmentSerializer<'a> {
+    segment: &'a PathSegment,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for PathSegmentSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(1))?;
+        match self.segment {
+            PathSegment::Field(name) => state.serialize_entry(patch_fields::FIELD, name)?,
+            PathSegment::Index(index) => state.serialize_entry(patch_fields::INDEX, index)?,
+            PathSegment::Key(key) => state.serialize_entry(
+                patch_fields::KEY,
+                &ReflectSerializer::new(&**key, self.registry),
+            )?,
+        }
+        state.end()
+    }
+}
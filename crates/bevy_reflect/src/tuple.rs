@@ -1,6 +1,9 @@
 use std::any::Any;
 
-use crate::{serde::Serializable, Reflect, ReflectMut, ReflectRef};
+use crate::{
+    serde::Serializable, FieldInfo, FromReflect, Reflect, ReflectMut, ReflectRef, TupleInfo,
+    TypeInfo, Typed,
+};
 
 pub trait Tuple: Reflect {
     fn field(&self, index: usize) -> Option<&dyn Reflect>;
@@ -329,6 +332,25 @@ macro_rules! impl_reflect_tuple {
                 None
             }
         }
+
+        impl<$($name: FromReflect),*> FromReflect for ($($name,)*) {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::Tuple(_ref_tuple) = reflect.reflect_ref() {
+                    Some(($($name::from_reflect(_ref_tuple.field($index)?)?,)*))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<$($name: Reflect),*> Typed for ($($name,)*) {
+            fn type_info() -> TypeInfo {
+                TypeInfo::Tuple(TupleInfo {
+                    type_name: std::any::type_name::<Self>(),
+                    fields: vec![$(FieldInfo::unnamed::<$name>(),)*],
+                })
+            }
+        }
     }
 }
 
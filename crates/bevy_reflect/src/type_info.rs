@@ -0,0 +1,159 @@
+use crate::{Reflect, VariantType};
+
+/// A reflected type that can describe itself without an instance.
+///
+/// This is implemented by `#[derive(Reflect)]` and `impl_reflect_value!`, and is stored in the
+/// [`TypeRegistration`](crate::TypeRegistration) of every registered type.
+pub trait Typed: Reflect {
+    fn type_info() -> TypeInfo;
+}
+
+/// The static description of a reflected type, matching the [`ReflectRef`](crate::ReflectRef)
+/// of its values. Fields with `#[reflect(ignore)]` are not included.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeInfo {
+    Struct(StructInfo),
+    TupleStruct(TupleStructInfo),
+    Tuple(TupleInfo),
+    List(ListInfo),
+    Map(MapInfo),
+    Enum(EnumInfo),
+    Value(ValueInfo),
+}
+
+impl TypeInfo {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TypeInfo::Struct(info) => info.type_name,
+            TypeInfo::TupleStruct(info) => info.type_name,
+            TypeInfo::Tuple(info) => info.type_name,
+            TypeInfo::List(info) => info.type_name,
+            TypeInfo::Map(info) => info.type_name,
+            TypeInfo::Enum(info) => info.type_name,
+            TypeInfo::Value(info) => info.type_name,
+        }
+    }
+}
+
+/// A field of a struct, tuple struct, tuple or enum variant. Only the fields of structs and struct
+/// variants have a name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldInfo {
+    pub name: Option<&'static str>,
+    pub type_name: &'static str,
+    pub attributes: FieldAttributes,
+}
+
+impl FieldInfo {
+    pub fn named<T: ?Sized>(name: &'static str) -> Self {
+        FieldInfo {
+            name: Some(name),
+            type_name: std::any::type_name::<T>(),
+            attributes: FieldAttributes::default(),
+        }
+    }
+
+    pub fn unnamed<T: ?Sized>() -> Self {
+        FieldInfo {
+            name: None,
+            type_name: std::any::type_name::<T>(),
+            attributes: FieldAttributes::default(),
+        }
+    }
+
+    pub fn with_attributes(mut self, attributes: FieldAttributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
+}
+
+/// Editor metadata of a field, set with field attributes like
+/// `#[reflect(range(0.0, 1.0), step = 0.1, units = "m", hidden, read_only)]`. The doc comment of
+/// the field is used as its `docs`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldAttributes {
+    /// The inclusive range of valid values of a numeric field
+    pub range: Option<(f64, f64)>,
+    /// The amount a numeric field changes by in an editor
+    pub step: Option<f64>,
+    pub docs: Option<&'static str>,
+    /// The field should not be shown in an editor
+    pub hidden: bool,
+    /// The field should be shown in an editor, but not be changed
+    pub read_only: bool,
+    pub units: Option<&'static str>,
+}
+
+impl FieldAttributes {
+    /// Returns `false` if the field has a range that does not contain `value`.
+    pub fn in_range(&self, value: f64) -> bool {
+        match self.range {
+            Some((min, max)) => min <= value && value <= max,
+            None => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructInfo {
+    pub type_name: &'static str,
+    pub fields: Vec<FieldInfo>,
+}
+
+impl StructInfo {
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == Some(name))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleStructInfo {
+    pub type_name: &'static str,
+    pub fields: Vec<FieldInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleInfo {
+    pub type_name: &'static str,
+    pub fields: Vec<FieldInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListInfo {
+    pub type_name: &'static str,
+    pub item_type_name: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapInfo {
+    pub type_name: &'static str,
+    pub key_type_name: &'static str,
+    pub value_type_name: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumInfo {
+    pub type_name: &'static str,
+    pub variants: Vec<VariantInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantInfo {
+    pub name: &'static str,
+    pub variant_type: VariantType,
+    pub fields: Vec<FieldInfo>,
+}
+
+/// A type that is reflected as an opaque [`ReflectRef::Value`](crate::ReflectRef::Value).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueInfo {
+    pub type_name: &'static str,
+}
+
+impl ValueInfo {
+    pub fn of<T: ?Sized>() -> Self {
+        ValueInfo {
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+}
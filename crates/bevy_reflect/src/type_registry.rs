@@ -1,4 +1,4 @@
-use crate::Reflect;
+use crate::{FieldAttributes, Reflect, TypeInfo, Typed};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -107,6 +107,7 @@ pub struct TypeRegistration {
     type_id: TypeId,
     short_name: String,
     name: &'static str,
+    type_info: TypeInfo,
     data: HashMap<TypeId, Box<dyn TypeData>>,
 }
 
@@ -132,7 +133,7 @@ impl TypeRegistration {
         self.data.insert(TypeId::of::<T>(), Box::new(data));
     }
 
-    pub fn of<T: Reflect>() -> Self {
+    pub fn of<T: Typed>() -> Self {
         let ty = TypeId::of::<T>();
         let type_name = std::any::type_name::<T>();
         Self {
@@ -140,9 +141,29 @@ impl TypeRegistration {
             data: HashMap::default(),
             name: type_name,
             short_name: Self::get_short_name(type_name),
+            type_info: T::type_info(),
         }
     }
 
+    /// Returns the static description of the type.
+    pub fn type_info(&self) -> &TypeInfo {
+        &self.type_info
+    }
+
+    /// Returns the metadata of the field of a struct with the given name, or of the field of a
+    /// tuple struct with the given index, like `"0"`.
+    pub fn field_attributes(&self, name: &str) -> Option<&FieldAttributes> {
+        match &self.type_info {
+            TypeInfo::Struct(info) => info.field(name),
+            TypeInfo::TupleStruct(info) => name
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| info.fields.get(index)),
+            _ => None,
+        }
+        .map(|field| &field.attributes)
+    }
+
     pub fn short_name(&self) -> &str {
         &self.short_name
     }
@@ -201,6 +222,7 @@ impl Clone for TypeRegistration {
             name: self.name,
             short_name: self.short_name.clone(),
             type_id: self.type_id,
+            type_info: self.type_info.clone(),
         }
     }
 }
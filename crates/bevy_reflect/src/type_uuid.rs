@@ -1,3 +1,4 @@
+use crate::FromType;
 pub use bevy_reflect_derive::TypeUuid;
 pub use bevy_utils::Uuid;
 
@@ -22,3 +23,23 @@ where
         std::any::type_name::<Self>()
     }
 }
+
+/// Type data with the [`TypeUuid`] of a type, added with `#[reflect(TypeUuid)]`.
+#[derive(Clone)]
+pub struct ReflectTypeUuid {
+    type_uuid: Uuid,
+}
+
+impl ReflectTypeUuid {
+    pub fn type_uuid(&self) -> Uuid {
+        self.type_uuid
+    }
+}
+
+impl<T: TypeUuid> FromType<T> for ReflectTypeUuid {
+    fn from_type() -> Self {
+        ReflectTypeUuid {
+            type_uuid: T::TYPE_UUID,
+        }
+    }
+}
@@ -1,13 +1,56 @@
-use super::texture::{ImageType, Texture, TextureError};
+use super::{
+    texture::{ImageType, Texture, TextureError},
+    FilterMode, TextureFormat,
+};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{
+    deserialize_ron_settings, AssetLoader, AssetLoaderSettings, LoadContext, LoadedAsset,
+};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Loader for images that can be read by the `image` crate.
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
+/// Settings for [`ImageTextureLoader`], read from the `.meta` file next to an image, e.g.
+/// `(nearest_filtering: true, srgb: false)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageTextureSettings {
+    /// Samples the texture with nearest filtering instead of linear filtering.
+    pub nearest_filtering: bool,
+    /// Interprets 8-bit color data as sRGB. Disable this for data textures such as normal maps.
+    pub srgb: bool,
+}
+
+impl Default for ImageTextureSettings {
+    fn default() -> Self {
+        Self {
+            nearest_filtering: false,
+            srgb: true,
+        }
+    }
+}
+
+impl ImageTextureSettings {
+    fn apply(&self, texture: &mut Texture) {
+        if self.nearest_filtering {
+            texture.sampler.mag_filter = FilterMode::Nearest;
+            texture.sampler.min_filter = FilterMode::Nearest;
+            texture.sampler.mipmap_filter = FilterMode::Nearest;
+        }
+        if !self.srgb {
+            texture.format = match texture.format {
+                TextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8UnormSrgb => TextureFormat::Bgra8Unorm,
+                format => format,
+            };
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "png")]
     "png",
@@ -33,7 +76,7 @@ impl AssetLoader for ImageTextureLoader {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img =
+            let mut dyn_img =
                 Texture::from_buffer(bytes, ImageType::Extension(ext)).map_err(|err| {
                     FileTextureError {
                         error: err,
@@ -41,6 +84,10 @@ impl AssetLoader for ImageTextureLoader {
                     }
                 })?;
 
+            if let Some(settings) = load_context.settings::<ImageTextureSettings>() {
+                settings.apply(&mut dyn_img);
+            }
+
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
         })
@@ -49,6 +96,14 @@ impl AssetLoader for ImageTextureLoader {
     fn extensions(&self) -> &[&str] {
         FILE_EXTENSIONS
     }
+
+    fn deserialize_settings(&self, meta: &[u8]) -> Result<Option<Box<dyn AssetLoaderSettings>>> {
+        deserialize_ron_settings::<ImageTextureSettings>(meta)
+    }
+
+    fn has_settings(&self) -> bool {
+        true
+    }
 }
 
 /// An error that occurs when loading a texture from a file
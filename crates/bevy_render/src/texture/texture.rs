@@ -4,7 +4,7 @@ use super::{Extent3d, SamplerDescriptor, TextureDescriptor, TextureDimension, Te
 use crate::renderer::{
     RenderResource, RenderResourceContext, RenderResourceId, RenderResourceType,
 };
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetSize, Assets, Handle};
 use bevy_ecs::{event::EventReader, system::Res};
 use bevy_reflect::TypeUuid;
 use bevy_utils::HashSet;
@@ -23,6 +23,12 @@ pub struct Texture {
     pub sampler: SamplerDescriptor,
 }
 
+impl AssetSize for Texture {
+    fn asset_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data.capacity()
+    }
+}
+
 impl Default for Texture {
     fn default() -> Self {
         Texture {
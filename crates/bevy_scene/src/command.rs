@@ -6,7 +6,7 @@ use bevy_ecs::{
 };
 use bevy_transform::hierarchy::ChildBuilder;
 
-use crate::{Scene, SceneSpawner};
+use crate::{DynamicScene, Prefab, Scene, SceneSpawner};
 
 pub struct SpawnScene {
     scene_handle: Handle<Scene>,
@@ -19,14 +19,46 @@ impl Command for SpawnScene {
     }
 }
 
+pub struct SpawnDynamicScene {
+    scene_handle: Handle<DynamicScene>,
+}
+
+impl Command for SpawnDynamicScene {
+    fn write(self, world: &mut World) {
+        let mut spawner = world.get_resource_mut::<SceneSpawner>().unwrap();
+        spawner.spawn_dynamic(self.scene_handle);
+    }
+}
+
+pub struct SpawnPrefab {
+    prefab_handle: Handle<Prefab>,
+}
+
+impl Command for SpawnPrefab {
+    fn write(self, world: &mut World) {
+        let mut spawner = world.get_resource_mut::<SceneSpawner>().unwrap();
+        spawner.spawn_prefab(self.prefab_handle);
+    }
+}
+
 pub trait SpawnSceneCommands {
     fn spawn_scene(&mut self, scene: Handle<Scene>);
+    fn spawn_dynamic_scene(&mut self, scene: Handle<DynamicScene>);
+    fn spawn_prefab(&mut self, prefab: Handle<Prefab>);
 }
 
 impl<'w, 's> SpawnSceneCommands for Commands<'w, 's> {
     fn spawn_scene(&mut self, scene_handle: Handle<Scene>) {
         self.add(SpawnScene { scene_handle });
     }
+
+    fn spawn_dynamic_scene(&mut self, scene_handle: Handle<DynamicScene>) {
+        self.add(SpawnDynamicScene { scene_handle });
+    }
+
+    fn spawn_prefab(&mut self, prefab_handle: Handle<Prefab>) {
+        self.add(SpawnPrefab { prefab_handle });
+    }
 }
 
 pub struct SpawnSceneAsChild {
@@ -41,8 +73,21 @@ impl Command for SpawnSceneAsChild {
     }
 }
 
+pub struct SpawnDynamicSceneAsChild {
+    scene_handle: Handle<DynamicScene>,
+    parent: Entity,
+}
+
+impl Command for SpawnDynamicSceneAsChild {
+    fn write(self, world: &mut World) {
+        let mut spawner = world.get_resource_mut::<SceneSpawner>().unwrap();
+        spawner.spawn_dynamic_as_child(self.scene_handle, self.parent);
+    }
+}
+
 pub trait SpawnSceneAsChildCommands {
     fn spawn_scene(&mut self, scene: Handle<Scene>) -> &mut Self;
+    fn spawn_dynamic_scene(&mut self, scene: Handle<DynamicScene>) -> &mut Self;
 }
 
 impl<'w, 's, 'a> SpawnSceneAsChildCommands for ChildBuilder<'w, 's, 'a> {
@@ -53,4 +98,12 @@ impl<'w, 's, 'a> SpawnSceneAsChildCommands for ChildBuilder<'w, 's, 'a> {
         });
         self
     }
+
+    fn spawn_dynamic_scene(&mut self, scene_handle: Handle<DynamicScene>) -> &mut Self {
+        self.add_command(SpawnDynamicSceneAsChild {
+            scene_handle,
+            parent: self.parent_entity(),
+        });
+        self
+    }
 }
@@ -1,17 +1,26 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{
+    format::{binary_scene_data, serialize_binary, SceneFormatError},
+    serde::{SceneDeserializer, SceneSerializer, ValidatingSceneDeserializer},
+    DynamicSceneBuilder, Scene, SceneSpawnError, SceneValidation,
+};
 use anyhow::Result;
+use bevy_asset::{load_handle_paths, make_handles_strong, AssetServer, HandleUntyped};
 use bevy_ecs::{
     entity::EntityMap,
-    reflect::{ReflectComponent, ReflectMapEntities},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
-use serde::Serialize;
+use bevy_reflect::{Reflect, ReflectMut, TypeRegistry, TypeRegistryArc, TypeUuid};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
+use serde::{de::DeserializeSeed, Serialize};
+use std::cell::Cell;
 
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
     pub entities: Vec<Entity>,
+    /// Resources, which need to be registered with [`ReflectResource`]
+    pub resources: Vec<Box<dyn Reflect>>,
 }
 
 pub struct Entity {
@@ -24,40 +33,64 @@ impl DynamicScene {
         Self::from_world(&scene.world, type_registry)
     }
 
+    /// Creates a scene from the reflected components of the entities in `world`. Use a
+    /// [`DynamicSceneBuilder`] to select entities, components and resources.
+    ///
+    /// If `world` has an [`AssetServer`], handles to assets that were loaded from a path are
+    /// stored as their [`AssetPath`](bevy_asset::AssetPath), so they are serialized as the path
+    /// of the asset.
     pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
-        let mut scene = DynamicScene::default();
-        let type_registry = type_registry.read();
-        for archetype in world.archetypes().iter() {
-            let entities_offset = scene.entities.len();
-            for entity in archetype.entities() {
-                scene.entities.push(Entity {
-                    entity: entity.id(),
-                    components: Vec::new(),
-                });
-            }
+        let entities = world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().copied());
+        DynamicSceneBuilder::new(world, type_registry)
+            .extract_entities(entities)
+            .build()
+    }
 
-            for component_id in archetype.components() {
-                let reflect_component = world
-                    .components()
-                    .get_info(component_id)
-                    .and_then(|info| type_registry.get(info.type_id().unwrap()))
-                    .and_then(|registration| registration.data::<ReflectComponent>());
-                if let Some(reflect_component) = reflect_component {
-                    for (i, entity) in archetype.entities().iter().enumerate() {
-                        if let Some(component) = reflect_component.reflect_component(world, *entity)
-                        {
-                            scene.entities[entities_offset + i]
-                                .components
-                                .push(component.clone_value());
-                        }
-                    }
-                }
+    /// Creates a scene from the entities of a spawned scene instance, where `entity_map` maps the
+    /// entities of the spawned scene to the entities of `world`. The entities keep their ids from
+    /// the spawned scene, and so do the entities referenced by their components, so the scene can
+    /// be compared to the spawned scene with [`ScenePatch::diff`](crate::ScenePatch::diff).
+    ///
+    /// Resources are not extracted, so the resources of `world` need to be added to the scene to
+    /// compare them to the resources of the spawned scene.
+    pub fn from_instance(
+        world: &World,
+        entity_map: &EntityMap,
+        type_registry: &TypeRegistryArc,
+    ) -> Self {
+        let mut scene = DynamicSceneBuilder::new(world, type_registry)
+            .extract_entities(entity_map.values())
+            .build();
+        let scene_entities = entity_map
+            .iter()
+            .map(|(scene_entity, entity)| (entity, scene_entity))
+            .collect::<HashMap<_, _>>();
+        let scene_ids = scene_entities
+            .iter()
+            .map(|(entity, scene_entity)| (entity.id(), scene_entity.id()))
+            .collect::<HashMap<_, _>>();
+        for scene_entity in scene.entities.iter_mut() {
+            scene_entity.entity = scene_ids[&scene_entity.entity];
+            for component in scene_entity.components.iter_mut() {
+                map_reflect_entities(&mut **component, &|entity| {
+                    scene_entities.get(&entity).copied()
+                });
             }
         }
-
+        scene
+            .entities
+            .sort_by_key(|scene_entity| scene_entity.entity);
         scene
     }
 
+    /// Writes the entities and resources of the scene to `world`, using `entity_map` to find the
+    /// entities that were written before.
+    ///
+    /// Handles that are stored as their [`AssetPath`](bevy_asset::AssetPath) are loaded with the
+    /// [`AssetServer`] of `world`, and become strong handles.
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -65,48 +98,128 @@ impl DynamicScene {
     ) -> Result<(), SceneSpawnError> {
         let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
         let type_registry = registry.read();
+        let asset_server = world.get_resource::<AssetServer>().cloned();
+        self.write_resources(world, &type_registry, asset_server.as_ref())?;
+        self.write_entities(world, entity_map, &type_registry, asset_server.as_ref())?;
+        map_entities(world, entity_map, &type_registry)
+    }
+
+    pub(crate) fn write_resources(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        asset_server: Option<&AssetServer>,
+    ) -> Result<(), SceneSpawnError> {
+        for resource in self.resources.iter() {
+            write_resource(world, &**resource, type_registry, asset_server)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the components of the scene entities without mapping the entities they reference,
+    /// which has to happen once after all components are written.
+    pub(crate) fn write_entities(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        type_registry: &TypeRegistry,
+        asset_server: Option<&AssetServer>,
+    ) -> Result<(), SceneSpawnError> {
         for scene_entity in self.entities.iter() {
             let entity = *entity_map
                 .entry(bevy_ecs::entity::Entity::new(scene_entity.entity))
                 .or_insert_with(|| world.spawn().id());
             for component in scene_entity.components.iter() {
-                let registration = type_registry
-                    .get_with_name(component.type_name())
-                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                        type_name: component.type_name().to_string(),
-                    })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_name: component.type_name().to_string(),
-                        }
-                    })?;
-                if world
-                    .entity(entity)
-                    .contains_type_id(registration.type_id())
-                {
-                    reflect_component.apply_component(world, entity, &**component);
-                } else {
-                    reflect_component.add_component(world, entity, &**component);
-                }
+                write_component(world, entity, &**component, type_registry, asset_server)?;
             }
         }
+        Ok(())
+    }
 
-        for registration in type_registry.iter() {
-            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, entity_map)
-                    .unwrap();
+    /// Validates that the components and resources of the scene can be spawned, and saved and
+    /// loaded again. Use [`DynamicSceneBuilder::build_validated`] to also find the components and
+    /// resources that were skipped when the scene was captured.
+    pub fn validate(&self, type_registry: &TypeRegistry) -> SceneValidation {
+        let mut validation = SceneValidation::default();
+        for scene_entity in self.entities.iter() {
+            for component in scene_entity.components.iter() {
+                validation.validate_component(scene_entity.entity, &**component, type_registry);
             }
         }
-
-        Ok(())
+        for resource in self.resources.iter() {
+            validation.validate_resource(&**resource, type_registry);
+        }
+        validation
     }
 
     // TODO: move to AssetSaver when it is implemented
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serializes the scene into the binary scene format, see [`serialize_binary`].
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, SceneFormatError> {
+        serialize_binary(SceneSerializer::new(self, registry))
+    }
+
+    /// Deserializes a scene from the RON scene format.
+    pub fn from_ron(bytes: &[u8], type_registry: &TypeRegistry) -> Result<Self, SceneFormatError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let scene_deserializer = SceneDeserializer { type_registry };
+        Ok(scene_deserializer.deserialize(&mut deserializer)?)
+    }
+
+    /// Deserializes a scene from the binary scene format.
+    pub fn from_binary(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<Self, SceneFormatError> {
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(binary_scene_data(bytes)?);
+        let scene_deserializer = SceneDeserializer { type_registry };
+        Ok(scene_deserializer.deserialize(&mut deserializer)?)
+    }
+
+    /// Deserializes a scene from the RON scene format, skipping the components and resources of
+    /// unregistered types. Returns the scene with the skipped values and the issues found by
+    /// [`DynamicScene::validate`].
+    pub fn from_ron_validated(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<(Self, SceneValidation), SceneFormatError> {
+        let mut validation = SceneValidation::default();
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let scene = ValidatingSceneDeserializer {
+            type_registry,
+            validation: &mut validation,
+        }
+        .deserialize(&mut deserializer)?;
+        validation
+            .issues
+            .extend(scene.validate(type_registry).issues);
+        Ok((scene, validation))
+    }
+
+    /// Deserializes a scene from the binary scene format like
+    /// [`DynamicScene::from_ron_validated`].
+    pub fn from_binary_validated(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<(Self, SceneValidation), SceneFormatError> {
+        let mut validation = SceneValidation::default();
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(binary_scene_data(bytes)?);
+        let scene = ValidatingSceneDeserializer {
+            type_registry,
+            validation: &mut validation,
+        }
+        .deserialize(&mut deserializer)?;
+        validation
+            .issues
+            .extend(scene.validate(type_registry).issues);
+        Ok((scene, validation))
+    }
 }
 
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
@@ -122,3 +235,380 @@ where
     serialize.serialize(&mut ron_serializer)?;
     Ok(String::from_utf8(buf).unwrap())
 }
+
+/// Adds `component` to `entity`, or applies it to the component the entity already has. Only the
+/// fields present in `component` are applied to an existing component.
+pub(crate) fn write_component(
+    world: &mut World,
+    entity: bevy_ecs::entity::Entity,
+    component: &dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: Option<&AssetServer>,
+) -> Result<(), SceneSpawnError> {
+    let registration = type_registry
+        .get_with_name(component.type_name())
+        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+            type_name: component.type_name().to_string(),
+        })?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredComponent {
+            type_name: component.type_name().to_string(),
+        }
+    })?;
+    let (component, loaded_handles) = load_handles(component, type_registry, asset_server);
+    if world
+        .entity(entity)
+        .contains_type_id(registration.type_id())
+    {
+        reflect_component.apply_component(world, entity, &*component);
+    } else {
+        reflect_component.try_add_component(world, entity, &*component)?;
+    }
+    if let (Some(asset_server), Some(mut component)) = (
+        asset_server,
+        reflect_component.reflect_component_mut(world, entity),
+    ) {
+        make_loaded_handles_strong(
+            &mut *component,
+            &loaded_handles,
+            type_registry,
+            asset_server,
+        );
+    }
+    Ok(())
+}
+
+/// Inserts `resource` into `world`, or applies it to the resource `world` already has.
+pub(crate) fn write_resource(
+    world: &mut World,
+    resource: &dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: Option<&AssetServer>,
+) -> Result<(), SceneSpawnError> {
+    let registration = type_registry
+        .get_with_name(resource.type_name())
+        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+            type_name: resource.type_name().to_string(),
+        })?;
+    let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredResource {
+            type_name: resource.type_name().to_string(),
+        }
+    })?;
+    let (resource, loaded_handles) = load_handles(resource, type_registry, asset_server);
+    if reflect_resource.reflect_resource(world).is_some() {
+        reflect_resource.apply_resource(world, &*resource);
+    } else {
+        reflect_resource.insert_resource(world, &*resource);
+    }
+    if let (Some(asset_server), Some(mut resource)) =
+        (asset_server, reflect_resource.reflect_resource_mut(world))
+    {
+        make_loaded_handles_strong(&mut *resource, &loaded_handles, type_registry, asset_server);
+    }
+    Ok(())
+}
+
+/// Removes the resource with the type name `type_name` from `world`, if it exists.
+pub(crate) fn remove_resource(
+    world: &mut World,
+    type_name: &str,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let registration = type_registry.get_with_name(type_name).ok_or_else(|| {
+        SceneSpawnError::UnregisteredType {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredResource {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    reflect_resource.remove_resource(world);
+    Ok(())
+}
+
+/// Removes the component with the type name `type_name` from `entity`, if the entity has it.
+pub(crate) fn remove_component(
+    world: &mut World,
+    entity: bevy_ecs::entity::Entity,
+    type_name: &str,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let registration = type_registry.get_with_name(type_name).ok_or_else(|| {
+        SceneSpawnError::UnregisteredType {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredComponent {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    if world
+        .entity(entity)
+        .contains_type_id(registration.type_id())
+    {
+        reflect_component.remove_component(world, entity);
+    }
+    Ok(())
+}
+
+/// Clones `value`, and loads the handles that are stored as their asset path.
+fn load_handles(
+    value: &dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: Option<&AssetServer>,
+) -> (Box<dyn Reflect>, Vec<HandleUntyped>) {
+    let mut value = value.clone_value();
+    let loaded_handles = match asset_server {
+        Some(asset_server) => load_handle_paths(&mut *value, type_registry, asset_server),
+        None => Vec::new(),
+    };
+    (value, loaded_handles)
+}
+
+/// The loaded handles are weak after applying them, as reflection only sets the id.
+fn make_loaded_handles_strong(
+    value: &mut dyn Reflect,
+    loaded_handles: &[HandleUntyped],
+    type_registry: &TypeRegistry,
+    asset_server: &AssetServer,
+) {
+    if !loaded_handles.is_empty() {
+        let handle_ids = loaded_handles
+            .iter()
+            .map(|handle| handle.id)
+            .collect::<HashSet<_>>();
+        make_handles_strong(value, type_registry, asset_server, &handle_ids);
+    }
+}
+
+/// Maps the entities referenced by components from scene entities to the spawned entities.
+///
+/// Components that reference entities outside of the scene cannot be mapped, like the `Parent`
+/// of an entity whose parent was not extracted with it, so they are removed from the spawned
+/// entities.
+pub(crate) fn map_entities(
+    world: &mut World,
+    entity_map: &EntityMap,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    for registration in type_registry.iter() {
+        let map_entities_reflect = match registration.data::<ReflectMapEntities>() {
+            Some(map_entities_reflect) => map_entities_reflect,
+            None => continue,
+        };
+        if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+            for entity in entity_map.values() {
+                let is_mappable = match reflect_component.reflect_component(world, entity) {
+                    Some(component) => {
+                        references_only(component, |entity| entity_map.get(entity).is_ok())
+                    }
+                    None => true,
+                };
+                if !is_mappable {
+                    warn!(
+                        "Removing the `{}` component of {:?}, as it references an entity that is not part of the scene.",
+                        registration.short_name(),
+                        entity
+                    );
+                    reflect_component.remove_component(world, entity);
+                }
+            }
+        }
+        map_entities_reflect.map_entities(world, entity_map)?;
+    }
+    Ok(())
+}
+
+/// Returns `true` if `is_known` returns `true` for all entities referenced by the reflected
+/// `value`.
+fn references_only(
+    value: &dyn Reflect,
+    is_known: impl Fn(bevy_ecs::entity::Entity) -> bool,
+) -> bool {
+    let all_known = Cell::new(true);
+    map_reflect_entities(&mut *value.clone_value(), &|entity| {
+        if !is_known(entity) {
+            all_known.set(false);
+        }
+        None
+    });
+    all_known.get()
+}
+
+/// Replaces the entities referenced by a reflected value with the entities returned by `map`.
+/// Entities for which `map` returns `None` are left as they are.
+pub(crate) fn map_reflect_entities(
+    value: &mut dyn Reflect,
+    map: &dyn Fn(bevy_ecs::entity::Entity) -> Option<bevy_ecs::entity::Entity>,
+) {
+    if let Some(entity) = value.downcast_mut::<bevy_ecs::entity::Entity>() {
+        if let Some(mapped_entity) = map(*entity) {
+            *entity = mapped_entity;
+        }
+        return;
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for i in 0..value.field_len() {
+                map_reflect_entities(value.field_at_mut(i).unwrap(), map);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for i in 0..value.field_len() {
+                map_reflect_entities(value.field_mut(i).unwrap(), map);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for i in 0..value.field_len() {
+                map_reflect_entities(value.field_mut(i).unwrap(), map);
+            }
+        }
+        ReflectMut::List(value) => {
+            for i in 0..value.len() {
+                map_reflect_entities(value.get_mut(i).unwrap(), map);
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys = (0..value.len())
+                .map(|i| value.get_at(i).unwrap().0.clone_value())
+                .collect::<Vec<_>>();
+            for key in keys {
+                map_reflect_entities(value.get_mut(&*key).unwrap(), map);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for i in 0..value.field_len() {
+                map_reflect_entities(value.field_at_mut(i).unwrap(), map);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::Health, DynamicScene, DynamicSceneBuilder, Entity as SceneEntity,
+        SceneSpawnError,
+    };
+    use bevy_ecs::{
+        entity::{Entity, EntityMap},
+        reflect::{ReflectComponent, ReflectComponentError},
+        world::World,
+    };
+    use bevy_reflect::{DynamicStruct, FromReflect, Reflect, TypeRegistryArc};
+    use bevy_transform::prelude::{Children, Parent};
+
+    /// A component without `Default`, which is constructed with `FromReflect` instead.
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    #[reflect(Component(from_reflect))]
+    struct Owner {
+        name: String,
+    }
+
+    fn owner(name: Option<&str>) -> Box<dyn Reflect> {
+        let mut owner = DynamicStruct::default();
+        owner.set_name(std::any::type_name::<Owner>().to_string());
+        if let Some(name) = name {
+            owner.insert("name", name.to_string());
+        }
+        Box::new(owner)
+    }
+
+    fn world() -> World {
+        let world = crate::test_utils::world();
+        world
+            .get_resource::<TypeRegistryArc>()
+            .unwrap()
+            .write()
+            .register::<Owner>();
+        world
+    }
+
+    #[test]
+    fn spawn_from_reflect_component() {
+        let mut world = world();
+        let scene = DynamicScene {
+            entities: vec![SceneEntity {
+                entity: 0,
+                components: vec![owner(Some("player"))],
+            }],
+            resources: Vec::new(),
+        };
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let entity = entity_map.get(bevy_ecs::entity::Entity::new(0)).unwrap();
+        assert_eq!(
+            world.get::<Owner>(entity),
+            Some(&Owner {
+                name: "player".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn spawn_incomplete_from_reflect_component() {
+        let mut world = world();
+        let scene = DynamicScene {
+            entities: vec![SceneEntity {
+                entity: 0,
+                components: vec![owner(None)],
+            }],
+            resources: Vec::new(),
+        };
+        let result = scene.write_to_world(&mut world, &mut EntityMap::default());
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::ReflectComponent(
+                ReflectComponentError::FromReflect { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn spawn_descendants_of_child() {
+        let mut world = world();
+        let grandchild = world.spawn().insert(Health { current: 3, max: 3 }).id();
+        let child = world
+            .spawn()
+            .insert(Health { current: 2, max: 2 })
+            .insert(Children::with(&[grandchild]))
+            .id();
+        let root = world.spawn().insert(Children::with(&[child])).id();
+        world.entity_mut(child).insert(Parent(root));
+        world.entity_mut(grandchild).insert(Parent(child));
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let scene = DynamicSceneBuilder::new(&world, &registry)
+            .extract_descendants(child)
+            .build();
+
+        // the parent of the extracted child is not part of the scene, so the child becomes a root
+        let mut other_world = self::world();
+        let mut entity_map = EntityMap::default();
+        scene
+            .write_to_world(&mut other_world, &mut entity_map)
+            .unwrap();
+        let spawned_child = entity_map.get(Entity::new(child.id())).unwrap();
+        let spawned_grandchild = entity_map.get(Entity::new(grandchild.id())).unwrap();
+        assert_eq!(other_world.entities().len(), 2);
+        assert!(other_world.get::<Parent>(spawned_child).is_none());
+        assert_eq!(
+            other_world.get::<Health>(spawned_child),
+            Some(&Health { current: 2, max: 2 })
+        );
+        assert_eq!(
+            &**other_world.get::<Children>(spawned_child).unwrap(),
+            &[spawned_grandchild]
+        );
+        assert_eq!(
+            other_world.get::<Parent>(spawned_grandchild),
+            Some(&Parent(spawned_child))
+        );
+    }
+}
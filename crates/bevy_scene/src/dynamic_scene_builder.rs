@@ -0,0 +1,421 @@
+use crate::{
+    DynamicScene, Entity as DynamicEntity, SceneValidation, SceneValidationError,
+    SceneValidationIssueKind,
+};
+use bevy_asset::{handle_ids_to_paths, AssetServer};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{FilterFetch, QueryState, WorldQuery},
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc};
+use bevy_transform::prelude::Children;
+use bevy_utils::HashSet;
+use std::any::TypeId;
+
+/// Builds a [`DynamicScene`] from selected entities and resources of a [`World`].
+///
+/// Entities and resources are only extracted when the scene is built, so the component filters
+/// apply to all extracted entities, regardless of the order of the calls.
+///
+/// ```ignore
+/// let scene = DynamicSceneBuilder::new(&world, &type_registry)
+///     .extract_query(&mut world.query_filtered::<Entity, With<Player>>())
+///     .extract_descendants(level)
+///     .deny::<GlobalTransform>()
+///     .extract_resource::<Score>()
+///     .build();
+/// ```
+pub struct DynamicSceneBuilder<'w> {
+    world: &'w World,
+    type_registry: TypeRegistryArc,
+    entities: Vec<Entity>,
+    extracted_entities: HashSet<Entity>,
+    resources: Vec<TypeId>,
+    extract_all_resources: bool,
+    allowed_components: Option<HashSet<TypeId>>,
+    denied_components: HashSet<TypeId>,
+}
+
+impl<'w> DynamicSceneBuilder<'w> {
+    pub fn new(world: &'w World, type_registry: &TypeRegistryArc) -> Self {
+        DynamicSceneBuilder {
+            world,
+            type_registry: type_registry.clone(),
+            entities: Vec::new(),
+            extracted_entities: HashSet::default(),
+            resources: Vec::new(),
+            extract_all_resources: false,
+            allowed_components: None,
+            denied_components: HashSet::default(),
+        }
+    }
+
+    /// Only extracts components of the allowed types. All reflected components are extracted if
+    /// no component type is allowed.
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.allowed_components
+            .get_or_insert_with(HashSet::default)
+            .insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Does not extract components of type `T`, even if they are allowed.
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.denied_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn extract_entity(mut self, entity: Entity) -> Self {
+        if self.extracted_entities.insert(entity) {
+            self.entities.push(entity);
+        }
+        self
+    }
+
+    pub fn extract_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        for entity in entities {
+            self = self.extract_entity(entity);
+        }
+        self
+    }
+
+    /// Extracts the entities matching `query`.
+    pub fn extract_query<F: WorldQuery>(self, query: &mut QueryState<Entity, F>) -> Self
+    where
+        F::Fetch: FilterFetch,
+    {
+        let world = self.world;
+        self.extract_entities(query.iter(world))
+    }
+
+    /// Extracts `root` and its descendants.
+    pub fn extract_descendants(mut self, root: Entity) -> Self {
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            self = self.extract_entity(entity);
+            if let Some(children) = self.world.get::<Children>(entity) {
+                // reversed, so children are extracted in order
+                stack.extend(children.iter().rev());
+            }
+        }
+        self
+    }
+
+    /// Extracts the resource of type `T`, which needs to be registered with [`ReflectResource`].
+    pub fn extract_resource<T: Component>(mut self) -> Self {
+        let type_id = TypeId::of::<T>();
+        if !self.resources.contains(&type_id) {
+            self.resources.push(type_id);
+        }
+        self
+    }
+
+    /// Extracts all resources that are registered with [`ReflectResource`].
+    pub fn extract_resources(mut self) -> Self {
+        self.extract_all_resources = true;
+        self
+    }
+
+    /// Builds the scene. Components and resources whose types are not registered with
+    /// [`ReflectComponent`] or [`ReflectResource`] are skipped, see [`Self::build_validated`].
+    pub fn build(self) -> DynamicScene {
+        self.build_validated().0
+    }
+
+    /// Builds the scene, failing if a component or resource was skipped, or cannot be serialized.
+    pub fn try_build(self) -> Result<DynamicScene, SceneValidationError> {
+        let (scene, validation) = self.build_validated();
+        validation.into_result()?;
+        Ok(scene)
+    }
+
+    /// Builds the scene, and reports the components and resources that were skipped, or that
+    /// cannot be serialized.
+    pub fn build_validated(self) -> (DynamicScene, SceneValidation) {
+        let type_registry = self.type_registry.read();
+        let asset_server = self.world.get_resource::<AssetServer>();
+        let mut scene = DynamicScene::default();
+        let mut validation = SceneValidation::default();
+
+        for entity in self.entities.iter() {
+            let entity_ref = match self.world.get_entity(*entity) {
+                Some(entity_ref) => entity_ref,
+                None => continue,
+            };
+            let mut components = Vec::new();
+            for component_id in entity_ref.archetype().components() {
+                let info = match self.world.components().get_info(component_id) {
+                    Some(info) => info,
+                    None => continue,
+                };
+                let type_id = match info.type_id() {
+                    Some(type_id) => type_id,
+                    None => {
+                        validation.push(
+                            Some(entity.id()),
+                            info.name(),
+                            SceneValidationIssueKind::UnregisteredType,
+                        );
+                        continue;
+                    }
+                };
+                if !self.is_component_extracted(type_id) {
+                    continue;
+                }
+                let reflect_component = match type_registry.get(type_id) {
+                    Some(registration) => match registration.data::<ReflectComponent>() {
+                        Some(reflect_component) => reflect_component,
+                        None => {
+                            validation.push(
+                                Some(entity.id()),
+                                info.name(),
+                                SceneValidationIssueKind::UnregisteredComponent,
+                            );
+                            continue;
+                        }
+                    },
+                    None => {
+                        validation.push(
+                            Some(entity.id()),
+                            info.name(),
+                            SceneValidationIssueKind::UnregisteredType,
+                        );
+                        continue;
+                    }
+                };
+                if let Some(component) = reflect_component.reflect_component(self.world, *entity) {
+                    let component = clone_reflect(component, &type_registry, asset_server);
+                    validation.validate_serializable(
+                        Some(entity.id()),
+                        &*component,
+                        &type_registry,
+                    );
+                    components.push(component);
+                }
+            }
+            scene.entities.push(DynamicEntity {
+                entity: entity.id(),
+                components,
+            });
+        }
+
+        let mut resources = self.resources.clone();
+        if self.extract_all_resources {
+            let mut all_resources = type_registry
+                .iter()
+                .filter(|registration| registration.data::<ReflectResource>().is_some())
+                .map(|registration| (registration.name(), registration.type_id()))
+                .collect::<Vec<_>>();
+            // the registry is unordered, so the resources are sorted for a stable scene
+            all_resources.sort_unstable();
+            for (_, type_id) in all_resources {
+                if !resources.contains(&type_id) {
+                    resources.push(type_id);
+                }
+            }
+        }
+        for type_id in resources {
+            let reflect_resource = match type_registry.get(type_id) {
+                Some(registration) => match registration.data::<ReflectResource>() {
+                    Some(reflect_resource) => reflect_resource,
+                    None => {
+                        validation.push(
+                            None,
+                            registration.name(),
+                            SceneValidationIssueKind::UnregisteredResource,
+                        );
+                        continue;
+                    }
+                },
+                None => {
+                    let type_name = self
+                        .world
+                        .components()
+                        .get_resource_id(type_id)
+                        .and_then(|component_id| self.world.components().get_info(component_id))
+                        .map_or("<unknown>", |info| info.name());
+                    validation.push(None, type_name, SceneValidationIssueKind::UnregisteredType);
+                    continue;
+                }
+            };
+            if let Some(resource) = reflect_resource.reflect_resource(self.world) {
+                let resource = clone_reflect(resource, &type_registry, asset_server);
+                validation.validate_serializable(None, &*resource, &type_registry);
+                scene.resources.push(resource);
+            }
+        }
+
+        (scene, validation)
+    }
+
+    fn is_component_extracted(&self, type_id: TypeId) -> bool {
+        let allowed = match &self.allowed_components {
+            Some(allowed_components) => allowed_components.contains(&type_id),
+            None => true,
+        };
+        allowed && !self.denied_components.contains(&type_id)
+    }
+}
+
+fn clone_reflect(
+    value: &dyn Reflect,
+    type_registry: &TypeRegistry,
+    asset_server: Option<&AssetServer>,
+) -> Box<dyn Reflect> {
+    let mut value = value.clone_value();
+    if let Some(asset_server) = asset_server {
+        handle_ids_to_paths(&mut *value, type_registry, asset_server);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{world, Gravity, Health, Speed},
+        DynamicSceneBuilder, SceneValidationIssueKind,
+    };
+    use bevy_ecs::{entity::EntityMap, world::World};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_transform::prelude::Children;
+
+    struct Unregistered;
+
+    fn type_names(components: &[Box<dyn Reflect>]) -> Vec<&str> {
+        components
+            .iter()
+            .map(|component| component.type_name())
+            .collect()
+    }
+
+    #[test]
+    fn allow_and_deny_components() {
+        let mut world = world();
+        let entity = world
+            .spawn()
+            .insert(Speed { value: 1.0 })
+            .insert(Health { current: 2, max: 2 })
+            .insert(Unregistered)
+            .id();
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap();
+
+        let scene = DynamicSceneBuilder::new(&world, registry)
+            .extract_entity(entity)
+            .deny::<Health>()
+            .build();
+        assert_eq!(
+            type_names(&scene.entities[0].components),
+            [std::any::type_name::<Speed>()]
+        );
+
+        // denied components are skipped even if they are allowed
+        let scene = DynamicSceneBuilder::new(&world, registry)
+            .allow::<Speed>()
+            .allow::<Health>()
+            .deny::<Speed>()
+            .extract_entity(entity)
+            .build();
+        assert_eq!(
+            type_names(&scene.entities[0].components),
+            [std::any::type_name::<Health>()]
+        );
+
+        // unregistered components are reported, unless they are not allowed
+        let (_, validation) = DynamicSceneBuilder::new(&world, registry)
+            .extract_entity(entity)
+            .build_validated();
+        assert_eq!(validation.issues.len(), 1);
+        assert_eq!(validation.issues[0].entity, Some(entity.id()));
+        assert_eq!(
+            validation.issues[0].kind,
+            SceneValidationIssueKind::UnregisteredType
+        );
+        let (_, validation) = DynamicSceneBuilder::new(&world, registry)
+            .extract_entity(entity)
+            .allow::<Speed>()
+            .build_validated();
+        assert!(validation.is_valid());
+    }
+
+    #[test]
+    fn extract_descendants() {
+        let mut world = world();
+        let grandchild = world.spawn().insert(Speed { value: 3.0 }).id();
+        let child_a = world
+            .spawn()
+            .insert(Speed { value: 2.0 })
+            .insert(Children::with(&[grandchild]))
+            .id();
+        let child_b = world.spawn().insert(Speed { value: 4.0 }).id();
+        let root = world
+            .spawn()
+            .insert(Speed { value: 1.0 })
+            .insert(Children::with(&[child_a, child_b]))
+            .id();
+        world.spawn().insert(Speed { value: 5.0 });
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap();
+
+        let scene = DynamicSceneBuilder::new(&world, registry)
+            .extract_descendants(root)
+            .extract_entity(child_b)
+            .allow::<Speed>()
+            .build();
+        let entities = scene
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entities,
+            [root.id(), child_a.id(), grandchild.id(), child_b.id()]
+        );
+    }
+
+    #[test]
+    fn resources_round_trip() {
+        let mut world = world();
+        world.insert_resource(Gravity { value: -9.81 });
+        world.insert_resource(Speed { value: 1.0 });
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+
+        let scene = DynamicSceneBuilder::new(&world, &registry)
+            .extract_resources()
+            .build();
+        assert_eq!(
+            type_names(&scene.resources),
+            [std::any::type_name::<Gravity>()]
+        );
+
+        let mut other_world = World::new();
+        other_world.insert_resource(registry.clone());
+        scene
+            .write_to_world(&mut other_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(
+            other_world.get_resource::<Gravity>(),
+            Some(&Gravity { value: -9.81 })
+        );
+
+        // resources that already exist are updated
+        other_world.insert_resource(Gravity { value: 0.0 });
+        scene
+            .write_to_world(&mut other_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(
+            other_world.get_resource::<Gravity>(),
+            Some(&Gravity { value: -9.81 })
+        );
+        assert!(other_world.get_resource::<Speed>().is_none());
+
+        let (_, validation) = DynamicSceneBuilder::new(&world, &registry)
+            .extract_resource::<Speed>()
+            .build_validated();
+        assert_eq!(
+            validation.issues[0].kind,
+            SceneValidationIssueKind::UnregisteredResource
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::DynamicScene;
+use bevy_reflect::TypeRegistryArc;
+use serde::Serialize;
+use thiserror::Error;
+
+/// The bytes binary scene files start with, followed by [`BINARY_SCENE_VERSION`].
+pub const BINARY_SCENE_MAGIC: &[u8; 4] = b"BSCN";
+/// The version of the binary scene format, which is increased when the encoding changes.
+pub const BINARY_SCENE_VERSION: u8 = 1;
+
+#[derive(Error, Debug)]
+pub enum SceneFormatError {
+    #[error("invalid RON scene: {0}")]
+    Ron(#[from] ron::Error),
+    #[error("failed to encode binary scene: {0}")]
+    BinaryEncode(#[from] rmp_serde::encode::Error),
+    #[error("failed to decode binary scene: {0}")]
+    BinaryDecode(#[from] rmp_serde::decode::Error),
+    #[error("not a binary scene, or a binary scene of an unsupported version")]
+    InvalidBinaryHeader,
+}
+
+/// Serializes a scene into the binary scene format, which is faster to load than RON. The format
+/// is MessagePack with a header, and contains the same data as the RON format, so scenes can be
+/// converted between the formats without losing anything.
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, SceneFormatError>
+where
+    S: Serialize,
+{
+    let mut buf = BINARY_SCENE_MAGIC.to_vec();
+    buf.push(BINARY_SCENE_VERSION);
+    // structs are encoded as maps, as the scene deserializers look up their fields by name
+    let mut serializer = rmp_serde::Serializer::new(&mut buf).with_struct_map();
+    serialize.serialize(&mut serializer)?;
+    Ok(buf)
+}
+
+/// Returns the MessagePack data of a binary scene, after checking its header.
+pub(crate) fn binary_scene_data(bytes: &[u8]) -> Result<&[u8], SceneFormatError> {
+    let header_len = BINARY_SCENE_MAGIC.len() + 1;
+    if bytes.len() < header_len
+        || &bytes[..BINARY_SCENE_MAGIC.len()] != BINARY_SCENE_MAGIC
+        || bytes[BINARY_SCENE_MAGIC.len()] != BINARY_SCENE_VERSION
+    {
+        return Err(SceneFormatError::InvalidBinaryHeader);
+    }
+    Ok(&bytes[header_len..])
+}
+
+/// Converts a RON scene file into a binary scene file.
+pub fn ron_scene_to_binary(
+    ron: &[u8],
+    registry: &TypeRegistryArc,
+) -> Result<Vec<u8>, SceneFormatError> {
+    let scene = DynamicScene::from_ron(ron, &registry.read())?;
+    scene.serialize_binary(registry)
+}
+
+/// Converts a binary scene file into a RON scene file.
+pub fn binary_scene_to_ron(
+    bytes: &[u8],
+    registry: &TypeRegistryArc,
+) -> Result<String, SceneFormatError> {
+    let scene = DynamicScene::from_binary(bytes, &registry.read())?;
+    Ok(scene.serialize_ron(registry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{registry, Gravity},
+        Entity as SceneEntity,
+    };
+    use bevy_asset::{AssetPath, Handle, HANDLE_FIELD_PATH};
+    use bevy_ecs::entity::Entity;
+    use bevy_reflect::DynamicStruct;
+    use bevy_transform::prelude::{Children, Parent};
+
+    #[test]
+    fn ron_binary_round_trip() {
+        let registry = registry();
+        {
+            let mut registry = registry.write();
+            registry.register::<AssetPath<'static>>();
+            registry.register::<Handle<DynamicScene>>();
+        }
+
+        // a handle captured from a world with an asset server is stored as its path
+        let mut handle = DynamicStruct::default();
+        handle.set_name(std::any::type_name::<Handle<DynamicScene>>().to_string());
+        handle.insert(
+            HANDLE_FIELD_PATH,
+            AssetPath::from("scenes/child.scn.ron#Root").to_owned(),
+        );
+        let scene = DynamicScene {
+            entities: vec![
+                SceneEntity {
+                    entity: 0,
+                    components: vec![Box::new(Children::with(&[Entity::new(1)]))],
+                },
+                SceneEntity {
+                    entity: 1,
+                    components: vec![Box::new(Parent(Entity::new(0))), Box::new(handle)],
+                },
+            ],
+            resources: vec![Box::new(Gravity { value: -9.81 })],
+        };
+
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let binary = ron_scene_to_binary(ron.as_bytes(), &registry).unwrap();
+        assert_eq!(binary, scene.serialize_binary(&registry).unwrap());
+        assert_eq!(binary_scene_to_ron(&binary, &registry).unwrap(), ron);
+
+        let scene = DynamicScene::from_binary(&binary, &registry.read()).unwrap();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.entities.len(), 2);
+        assert_eq!(scene.entities[1].components.len(), 2);
+    }
+
+    #[test]
+    fn invalid_binary_header() {
+        let registry = registry();
+        assert!(matches!(
+            binary_scene_to_ron(b"BSCN", &registry),
+            Err(SceneFormatError::InvalidBinaryHeader)
+        ));
+        assert!(matches!(
+            binary_scene_to_ron(b"SCN\x01[]", &registry),
+            Err(SceneFormatError::InvalidBinaryHeader)
+        ));
+    }
+}
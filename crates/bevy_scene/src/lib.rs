@@ -1,20 +1,33 @@
 mod command;
 mod dynamic_scene;
+mod dynamic_scene_builder;
+mod format;
+mod prefab;
 mod scene;
 mod scene_loader;
+mod scene_patch;
 mod scene_spawner;
 pub mod serde;
+#[cfg(test)]
+mod test_utils;
+mod validation;
 
 pub use command::*;
 pub use dynamic_scene::*;
+pub use dynamic_scene_builder::*;
+pub use format::*;
+pub use prefab::*;
 pub use scene::*;
 pub use scene_loader::*;
+pub use scene_patch::*;
 pub use scene_spawner::*;
+pub use validation::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+        DynamicScene, DynamicSceneBuilder, Prefab, PrefabBase, Scene, ScenePatch, SceneSpawner,
+        SpawnSceneAsChildCommands, SpawnSceneCommands,
     };
 }
 
@@ -29,8 +42,12 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
+            .add_asset::<Prefab>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
+            .init_asset_loader::<PrefabLoader>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceReady>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 scene_spawner_system.exclusive_system().at_end(),
@@ -0,0 +1,339 @@
+use crate::{
+    dynamic_scene::{map_entities, remove_component, write_component},
+    DynamicScene, SceneSpawnError,
+};
+use bevy_asset::{AssetServer, Assets, Handle};
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
+
+/// A variant of a [`DynamicScene`], which is spawned as its base scene with overrides applied to
+/// some of its entities.
+///
+/// The overrides are applied again when the base scene is modified, so a prefab instance keeps
+/// its overrides when the base scene is hot reloaded.
+#[derive(TypeUuid)]
+#[uuid = "2a6d1e7b-93c4-4f0a-b5d8-6c1f0e4a8b37"]
+pub struct Prefab {
+    pub base: PrefabBase,
+    pub entities: Vec<PrefabEntity>,
+}
+
+/// The scene a [`Prefab`] is based on.
+#[derive(Clone, Debug)]
+pub enum PrefabBase {
+    Scene(Handle<DynamicScene>),
+    /// Another prefab, whose overrides are applied before the overrides of the prefab based on it
+    Prefab(Handle<Prefab>),
+}
+
+/// The overrides of an entity of a [`Prefab`]. Entities that are not in the base scene are
+/// spawned in addition to the entities of the base scene.
+pub struct PrefabEntity {
+    /// The id of the entity in the base scene
+    pub entity: u32,
+    /// Components that are added to the entity. If the entity already has a component of the
+    /// same type, only the fields that are present in the override are applied to it.
+    pub components: Vec<Box<dyn Reflect>>,
+    /// The type names of the components that are removed from the entity
+    pub removed_components: Vec<String>,
+}
+
+impl Prefab {
+    /// Writes the scene the prefab is based on to `world`, and applies the overrides of the
+    /// prefabs in between and of the prefab itself to the written entities.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        scenes: &Assets<DynamicScene>,
+        prefabs: &Assets<Prefab>,
+    ) -> Result<(), SceneSpawnError> {
+        // the prefab and the prefabs it is based on, ending with the prefab based on the scene
+        let mut chain = vec![self];
+        let base = loop {
+            match &chain.last().unwrap().base {
+                PrefabBase::Scene(handle) => {
+                    break scenes
+                        .get(handle)
+                        .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                            handle: handle.clone_weak(),
+                        })?
+                }
+                PrefabBase::Prefab(handle) => {
+                    let prefab =
+                        prefabs
+                            .get(handle)
+                            .ok_or_else(|| SceneSpawnError::NonExistentPrefab {
+                                handle: handle.clone_weak(),
+                            })?;
+                    if chain.iter().any(|other| std::ptr::eq(*other, prefab)) {
+                        return Err(SceneSpawnError::RecursivePrefab {
+                            handle: handle.clone_weak(),
+                        });
+                    }
+                    chain.push(prefab);
+                }
+            }
+        };
+
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let type_registry = registry.read();
+        let asset_server = world.get_resource::<AssetServer>().cloned();
+        base.write_resources(world, &type_registry, asset_server.as_ref())?;
+        base.write_entities(world, entity_map, &type_registry, asset_server.as_ref())?;
+        for prefab in chain.iter().rev() {
+            prefab.write_overrides(world, entity_map, &type_registry, asset_server.as_ref())?;
+        }
+
+        map_entities(world, entity_map, &type_registry)
+    }
+
+    fn write_overrides(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        type_registry: &TypeRegistry,
+        asset_server: Option<&AssetServer>,
+    ) -> Result<(), SceneSpawnError> {
+        for prefab_entity in self.entities.iter() {
+            let entity = *entity_map
+                .entry(Entity::new(prefab_entity.entity))
+                .or_insert_with(|| world.spawn().id());
+            for type_name in prefab_entity.removed_components.iter() {
+                remove_component(world, entity, type_name, type_registry)?;
+            }
+            for component in prefab_entity.components.iter() {
+                write_component(world, entity, &**component, type_registry, asset_server)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the prefab, one of the prefabs it is based on, or the scene it is based on
+    /// is in `modified_prefabs` or `modified_scenes`.
+    pub(crate) fn is_modified(
+        prefab_handle: &Handle<Prefab>,
+        prefabs: &Assets<Prefab>,
+        modified_prefabs: &[Handle<Prefab>],
+        modified_scenes: &[Handle<DynamicScene>],
+    ) -> bool {
+        let mut visited = Vec::new();
+        let mut prefab_handle = prefab_handle;
+        while !visited.contains(&prefab_handle) {
+            if modified_prefabs.contains(prefab_handle) {
+                return true;
+            }
+            visited.push(prefab_handle);
+            match prefabs.get(prefab_handle).map(|prefab| &prefab.base) {
+                Some(PrefabBase::Scene(scene_handle)) => {
+                    return modified_scenes.contains(scene_handle)
+                }
+                Some(PrefabBase::Prefab(base_handle)) => prefab_handle = base_handle,
+                None => return false,
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{Health, Speed},
+        DynamicScene, Entity as SceneEntity, Prefab, PrefabBase, PrefabEntity, SceneSpawner,
+    };
+    use bevy_app::App;
+    use bevy_asset::{Assets, EmbeddedAssets, Handle};
+    use bevy_ecs::entity::Entity;
+    use bevy_reflect::DynamicStruct;
+
+    fn app() -> App {
+        crate::test_utils::app(EmbeddedAssets::default())
+    }
+
+    fn base_scene(health: u32, speed: f32) -> DynamicScene {
+        DynamicScene {
+            entities: vec![
+                SceneEntity {
+                    entity: 0,
+                    components: vec![
+                        Box::new(Health {
+                            current: health,
+                            max: health,
+                        }),
+                        Box::new(Speed { value: speed }),
+                    ],
+                },
+                SceneEntity {
+                    entity: 1,
+                    components: vec![Box::new(Speed { value: speed })],
+                },
+            ],
+            resources: Vec::new(),
+        }
+    }
+
+    /// Overrides the current health of entity 0 and removes its speed, adds health to entity 1,
+    /// and spawns entity 2.
+    fn prefab(base: PrefabBase) -> Prefab {
+        let mut current_health = DynamicStruct::default();
+        current_health.set_name(std::any::type_name::<Health>().to_string());
+        current_health.insert("current", 5u32);
+        Prefab {
+            base,
+            entities: vec![
+                PrefabEntity {
+                    entity: 0,
+                    components: vec![Box::new(current_health)],
+                    removed_components: vec![std::any::type_name::<Speed>().to_string()],
+                },
+                PrefabEntity {
+                    entity: 1,
+                    components: vec![Box::new(Health { current: 3, max: 3 })],
+                    removed_components: Vec::new(),
+                },
+                PrefabEntity {
+                    entity: 2,
+                    components: vec![Box::new(Speed { value: 4.0 })],
+                    removed_components: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    /// Spawns the prefab, and returns the spawned entities ordered by their id in the prefab.
+    fn spawn_prefab(app: &mut App, prefab_handle: Handle<Prefab>) -> Vec<Entity> {
+        let instance_id = app
+            .world
+            .get_resource_mut::<SceneSpawner>()
+            .unwrap()
+            .spawn_prefab(prefab_handle);
+        app.update();
+        let scene_spawner = app.world.get_resource::<SceneSpawner>().unwrap();
+        let mut entities = scene_spawner
+            .instance_entity_map(instance_id)
+            .expect("prefab should be spawned")
+            .iter()
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|(scene_entity, _)| scene_entity.id());
+        entities.into_iter().map(|(_, entity)| entity).collect()
+    }
+
+    #[test]
+    fn prefab_overrides() {
+        let mut app = app();
+        let scene_handle = app
+            .world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(base_scene(10, 1.0));
+        let prefab_handle = app
+            .world
+            .get_resource_mut::<Assets<Prefab>>()
+            .unwrap()
+            .add(prefab(PrefabBase::Scene(scene_handle)));
+        let entities = spawn_prefab(&mut app, prefab_handle);
+
+        assert_eq!(entities.len(), 3);
+        let entity_0 = app.world.entity(entities[0]);
+        assert_eq!(
+            entity_0.get::<Health>(),
+            Some(&Health {
+                current: 5,
+                max: 10
+            })
+        );
+        assert_eq!(entity_0.get::<Speed>(), None);
+        let entity_1 = app.world.entity(entities[1]);
+        assert_eq!(
+            entity_1.get::<Health>(),
+            Some(&Health { current: 3, max: 3 })
+        );
+        assert_eq!(entity_1.get::<Speed>(), Some(&Speed { value: 1.0 }));
+        let entity_2 = app.world.entity(entities[2]);
+        assert_eq!(entity_2.get::<Speed>(), Some(&Speed { value: 4.0 }));
+    }
+
+    #[test]
+    fn prefab_based_on_prefab() {
+        let mut app = app();
+        let scene_handle = app
+            .world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(base_scene(10, 1.0));
+        let mut prefabs = app.world.get_resource_mut::<Assets<Prefab>>().unwrap();
+        let base_handle = prefabs.add(prefab(PrefabBase::Scene(scene_handle)));
+        let prefab_handle = prefabs.add(Prefab {
+            base: PrefabBase::Prefab(base_handle),
+            entities: vec![PrefabEntity {
+                entity: 2,
+                components: vec![Box::new(Health { current: 1, max: 2 })],
+                removed_components: Vec::new(),
+            }],
+        });
+        let entities = spawn_prefab(&mut app, prefab_handle);
+
+        let entity_0 = app.world.entity(entities[0]);
+        assert_eq!(
+            entity_0.get::<Health>(),
+            Some(&Health {
+                current: 5,
+                max: 10
+            })
+        );
+        assert_eq!(entity_0.get::<Speed>(), None);
+        let entity_2 = app.world.entity(entities[2]);
+        assert_eq!(entity_2.get::<Speed>(), Some(&Speed { value: 4.0 }));
+        assert_eq!(
+            entity_2.get::<Health>(),
+            Some(&Health { current: 1, max: 2 })
+        );
+    }
+
+    #[test]
+    fn prefab_overrides_survive_base_reload() {
+        let mut app = app();
+        let scene_handle = app
+            .world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(base_scene(10, 1.0));
+        let mut prefabs = app.world.get_resource_mut::<Assets<Prefab>>().unwrap();
+        let base_handle = prefabs.add(prefab(PrefabBase::Scene(scene_handle.clone())));
+        let prefab_handle = prefabs.add(Prefab {
+            base: PrefabBase::Prefab(base_handle),
+            entities: Vec::new(),
+        });
+        let entities = spawn_prefab(&mut app, prefab_handle);
+
+        *app.world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .get_mut(&scene_handle)
+            .unwrap() = base_scene(20, 2.0);
+        // the modified event is sent at the end of the first update
+        app.update();
+        app.update();
+
+        let entity_0 = app.world.entity(entities[0]);
+        assert_eq!(
+            entity_0.get::<Health>(),
+            Some(&Health {
+                current: 5,
+                max: 20
+            })
+        );
+        assert_eq!(entity_0.get::<Speed>(), None);
+        let entity_1 = app.world.entity(entities[1]);
+        assert_eq!(
+            entity_1.get::<Health>(),
+            Some(&Health { current: 3, max: 3 })
+        );
+        assert_eq!(entity_1.get::<Speed>(), Some(&Speed { value: 2.0 }));
+        assert_eq!(app.world.entities().len(), 3);
+    }
+}
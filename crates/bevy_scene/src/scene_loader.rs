@@ -1,14 +1,18 @@
-use crate::serde::SceneDeserializer;
+use crate::{
+    serde::PrefabDeserializer, DynamicScene, PrefabBase, SceneValidation, SceneValidationSettings,
+};
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
-use bevy_utils::BoxedFuture;
+use bevy_utils::{tracing::warn, BoxedFuture};
 use serde::de::DeserializeSeed;
+use std::path::Path;
 
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
+    validation_settings: SceneValidationSettings,
 }
 
 impl FromWorld for SceneLoader {
@@ -16,6 +20,7 @@ impl FromWorld for SceneLoader {
         let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
         SceneLoader {
             type_registry: (&*type_registry).clone(),
+            validation_settings: validation_settings(world),
         }
     }
 }
@@ -27,11 +32,9 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
-            let scene_deserializer = SceneDeserializer {
-                type_registry: &*self.type_registry.read(),
-            };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
+            let (scene, validation) =
+                DynamicScene::from_ron_validated(bytes, &self.type_registry.read())?;
+            report_validation(validation, self.validation_settings, load_context)?;
             load_context.set_default_asset(LoadedAsset::new(scene));
             Ok(())
         })
@@ -41,3 +44,211 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// Loads [`DynamicScene`]s from the binary scene format, see
+/// [`serialize_binary`](crate::serialize_binary).
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+    validation_settings: SceneValidationSettings,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        BinarySceneLoader {
+            type_registry: type_registry.clone(),
+            validation_settings: validation_settings(world),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let (scene, validation) =
+                DynamicScene::from_binary_validated(bytes, &self.type_registry.read())?;
+            report_validation(validation, self.validation_settings, load_context)?;
+            load_context.set_default_asset(LoadedAsset::new(scene));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+fn validation_settings(world: &World) -> SceneValidationSettings {
+    world
+        .get_resource::<SceneValidationSettings>()
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Fails to load scenes with issues in strict mode, or logs the issues otherwise. Components and
+/// resources of unregistered types have already been skipped when the scene was deserialized.
+fn report_validation(
+    validation: SceneValidation,
+    validation_settings: SceneValidationSettings,
+    load_context: &LoadContext,
+) -> Result<()> {
+    if validation_settings.strict {
+        validation.into_result()?;
+    } else {
+        for issue in validation.issues {
+            warn!("{}: {}", load_context.path().display(), issue);
+        }
+    }
+    Ok(())
+}
+
+/// Loads [`Prefab`](crate::Prefab)s. The base of a prefab is loaded as a dependency, and is a
+/// prefab if its path has a prefab extension, or a scene otherwise.
+#[derive(Debug)]
+pub struct PrefabLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for PrefabLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        PrefabLoader {
+            type_registry: type_registry.clone(),
+        }
+    }
+}
+
+impl AssetLoader for PrefabLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+            let mut dependencies = Vec::new();
+            let prefab = {
+                let load_context = &*load_context;
+                let prefab_deserializer = PrefabDeserializer {
+                    type_registry: &self.type_registry.read(),
+                    load_base: &mut |path: AssetPath<'static>| {
+                        let base = if is_prefab_path(path.path()) {
+                            PrefabBase::Prefab(load_context.get_handle(path.get_id()))
+                        } else {
+                            PrefabBase::Scene(load_context.get_handle(path.get_id()))
+                        };
+                        dependencies.push(path);
+                        base
+                    },
+                };
+                prefab_deserializer.deserialize(&mut deserializer)?
+            };
+            load_context
+                .set_default_asset(LoadedAsset::new(prefab).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        PREFAB_EXTENSIONS
+    }
+}
+
+const PREFAB_EXTENSIONS: &[&str] = &["prefab", "prefab.ron"];
+
+fn is_prefab_path(path: &Path) -> bool {
+    let file_name = match path.file_name().and_then(|file_name| file_name.to_str()) {
+        Some(file_name) => file_name,
+        None => return false,
+    };
+    PREFAB_EXTENSIONS.iter().any(|extension| {
+        file_name
+            .strip_suffix(extension)
+            .is_some_and(|stem| stem.ends_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::Health, DynamicScene, SceneValidationSettings};
+    use bevy_app::App;
+    use bevy_asset::{AssetServer, Assets, EmbeddedAssets, Handle, LoadState};
+    use bevy_reflect::Reflect;
+
+    const SCENE: &str = r#"[
+  (
+    entity: 0,
+    components: [
+      {
+        "type": "bevy_scene::test_utils::Health",
+        "struct": {
+          "current": {
+            "type": "u32",
+            "value": 3,
+          },
+        },
+      },
+      {
+        "type": "game::Secret",
+        "value": 5,
+      },
+    ],
+  ),
+]"#;
+
+    fn app(strict: bool) -> App {
+        let assets = EmbeddedAssets::default();
+        assets.insert("scene.scn.ron", SCENE.as_bytes());
+        crate::test_utils::app_with_settings(assets, SceneValidationSettings { strict })
+    }
+
+    fn load(app: &mut App) -> (Handle<DynamicScene>, LoadState) {
+        let handle = app
+            .world
+            .get_resource::<AssetServer>()
+            .unwrap()
+            .load("scene.scn.ron");
+        for _ in 0..1000 {
+            app.update();
+            let load_state = app
+                .world
+                .get_resource::<AssetServer>()
+                .unwrap()
+                .get_load_state(&handle);
+            if matches!(load_state, LoadState::Loaded | LoadState::Failed) {
+                return (handle, load_state);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("the scene did not finish loading");
+    }
+
+    #[test]
+    fn load_skips_unregistered_components() {
+        let mut app = app(false);
+        let (handle, load_state) = load(&mut app);
+        assert_eq!(load_state, LoadState::Loaded);
+
+        let scenes = app.world.get_resource::<Assets<DynamicScene>>().unwrap();
+        let scene = scenes.get(&handle).unwrap();
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].components.len(), 1);
+        let mut health = Health::default();
+        health.apply(&*scene.entities[0].components[0]);
+        assert_eq!(health, Health { current: 3, max: 0 });
+    }
+
+    #[test]
+    fn strict_load_fails() {
+        let mut app = app(true);
+        let (handle, load_state) = load(&mut app);
+        assert_eq!(load_state, LoadState::Failed);
+        let scenes = app.world.get_resource::<Assets<DynamicScene>>().unwrap();
+        assert!(scenes.get(&handle).is_none());
+    }
+}
@@ -0,0 +1,613 @@
+use crate::{
+    dynamic_scene::{
+        map_reflect_entities, remove_component, remove_resource, write_component, write_resource,
+    },
+    format::{binary_scene_data, serialize_binary, SceneFormatError},
+    serde::{ScenePatchDeserializer, ScenePatchSerializer},
+    DynamicScene, Entity as DynamicEntity, SceneSpawnError,
+};
+use bevy_asset::AssetServer;
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    world::World,
+};
+use bevy_reflect::{
+    reflect_diff, DynamicStruct, PathSegment, Reflect, ReflectChange, ReflectRef, TypeRegistry,
+    TypeRegistryArc,
+};
+use serde::de::DeserializeSeed;
+
+/// The difference between two [`DynamicScene`]s, which turns a world containing the first scene
+/// into a world containing the second scene when it is written to the world.
+///
+/// Entities are matched by their id in the scenes, and components and resources by their type.
+/// Use [`DynamicScene::from_instance`] to compare a scene to an instance of it that was spawned
+/// and modified since.
+///
+/// Components and resources are compared with [`reflect_diff`], but unlike a
+/// [`ReflectPatch`](bevy_reflect::ReflectPatch), their changes are stored as partial values, so
+/// that they serialize like the components of a [`DynamicScene`].
+#[derive(Default)]
+pub struct ScenePatch {
+    /// Entities that are only in the new scene
+    pub added_entities: Vec<DynamicEntity>,
+    /// The ids of the entities that are only in the old scene
+    pub removed_entities: Vec<u32>,
+    /// Entities that are in both scenes, but whose components differ
+    pub changed_entities: Vec<EntityPatch>,
+    /// Resources that are only in the new scene
+    pub added_resources: Vec<Box<dyn Reflect>>,
+    /// Resources that changed, containing only their changed fields, like
+    /// [`EntityPatch::changed_components`]
+    pub changed_resources: Vec<Box<dyn Reflect>>,
+    /// Resources that changed in a way that cannot be applied, like
+    /// [`EntityPatch::replaced_components`]
+    pub replaced_resources: Vec<Box<dyn Reflect>>,
+    /// The type names of the resources that are only in the old scene
+    pub removed_resources: Vec<String>,
+}
+
+/// The difference between the components of an entity in two [`DynamicScene`]s.
+#[derive(Default)]
+pub struct EntityPatch {
+    /// The id of the entity in the scenes
+    pub entity: u32,
+    /// Components that the entity only has in the new scene
+    pub added_components: Vec<Box<dyn Reflect>>,
+    /// Components that changed, containing only their changed fields. They are applied to the
+    /// existing components with [`Reflect::apply`].
+    pub changed_components: Vec<Box<dyn Reflect>>,
+    /// Components that changed in a way that cannot be applied, like lists that became shorter or
+    /// maps with different keys. They replace the existing components.
+    pub replaced_components: Vec<Box<dyn Reflect>>,
+    /// The type names of the components that the entity only has in the old scene
+    pub removed_components: Vec<String>,
+}
+
+impl ScenePatch {
+    /// Computes the patch that turns the `old` scene into the `new` scene.
+    pub fn diff(old: &DynamicScene, new: &DynamicScene) -> Self {
+        let mut patch = ScenePatch::default();
+        for new_entity in new.entities.iter() {
+            match old
+                .entities
+                .iter()
+                .find(|old_entity| old_entity.entity == new_entity.entity)
+            {
+                Some(old_entity) => {
+                    let entity_patch = EntityPatch::diff(old_entity, new_entity);
+                    if !entity_patch.is_empty() {
+                        patch.changed_entities.push(entity_patch);
+                    }
+                }
+                None => patch.added_entities.push(DynamicEntity {
+                    entity: new_entity.entity,
+                    components: clone_components(&new_entity.components),
+                }),
+            }
+        }
+        for old_entity in old.entities.iter() {
+            if !new
+                .entities
+                .iter()
+                .any(|new_entity| new_entity.entity == old_entity.entity)
+            {
+                patch.removed_entities.push(old_entity.entity);
+            }
+        }
+        let resources = ValuesDiff::diff(&old.resources, &new.resources);
+        patch.added_resources = resources.added;
+        patch.changed_resources = resources.changed;
+        patch.replaced_resources = resources.replaced;
+        patch.removed_resources = resources.removed;
+        patch
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.changed_entities.is_empty()
+            && self.added_resources.is_empty()
+            && self.changed_resources.is_empty()
+            && self.replaced_resources.is_empty()
+            && self.removed_resources.is_empty()
+    }
+
+    /// Writes the patch to `world`, where `entity_map` maps the entities of the patched scene to
+    /// the entities of `world`, like the map of a spawned scene instance. Added entities are
+    /// spawned and inserted into `entity_map`, and removed entities are despawned and removed
+    /// from it.
+    ///
+    /// The entities referenced by the written components are mapped with `entity_map`. Unlike
+    /// [`DynamicScene::write_to_world`], components that are not in the patch are left as they
+    /// are.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let type_registry = registry.read();
+        let asset_server = world.get_resource::<AssetServer>().cloned();
+
+        for type_name in self.removed_resources.iter() {
+            remove_resource(world, type_name, &type_registry)?;
+        }
+        for resource in self.replaced_resources.iter() {
+            remove_resource(world, resource.type_name(), &type_registry)?;
+            write_resource(world, &**resource, &type_registry, asset_server.as_ref())?;
+        }
+        for resource in self
+            .added_resources
+            .iter()
+            .chain(self.changed_resources.iter())
+        {
+            write_resource(world, &**resource, &type_registry, asset_server.as_ref())?;
+        }
+
+        for &scene_entity in self.removed_entities.iter() {
+            let scene_entity = Entity::new(scene_entity);
+            if let Ok(entity) = entity_map.get(scene_entity) {
+                world.despawn(entity);
+                entity_map.remove(scene_entity);
+            }
+        }
+
+        // all entities are spawned first, so the components can reference any of them
+        let scene_entities = self
+            .added_entities
+            .iter()
+            .map(|entity| entity.entity)
+            .chain(self.changed_entities.iter().map(|entity| entity.entity));
+        for scene_entity in scene_entities {
+            entity_map
+                .entry(Entity::new(scene_entity))
+                .or_insert_with(|| world.spawn().id());
+        }
+
+        let writer = ComponentWriter {
+            entity_map,
+            type_registry: &type_registry,
+            asset_server: asset_server.as_ref(),
+        };
+        for scene_entity in self.added_entities.iter() {
+            let entity = entity_map.get(Entity::new(scene_entity.entity)).unwrap();
+            for component in scene_entity.components.iter() {
+                writer.write(world, entity, &**component)?;
+            }
+        }
+        for entity_patch in self.changed_entities.iter() {
+            let entity = entity_map.get(Entity::new(entity_patch.entity)).unwrap();
+            for type_name in entity_patch.removed_components.iter() {
+                remove_component(world, entity, type_name, &type_registry)?;
+            }
+            for component in entity_patch.replaced_components.iter() {
+                remove_component(world, entity, component.type_name(), &type_registry)?;
+                writer.write(world, entity, &**component)?;
+            }
+            for component in entity_patch
+                .added_components
+                .iter()
+                .chain(entity_patch.changed_components.iter())
+            {
+                writer.write(world, entity, &**component)?;
+            }
+        }
+        Ok(())
+    }
+
+    // TODO: move to AssetSaver when it is implemented
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        crate::serialize_ron(ScenePatchSerializer::new(self, registry))
+    }
+
+    /// Serializes the patch into the binary scene format, see [`serialize_binary`].
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, SceneFormatError> {
+        serialize_binary(ScenePatchSerializer::new(self, registry))
+    }
+
+    /// Deserializes a patch from the RON scene format.
+    pub fn from_ron(bytes: &[u8], type_registry: &TypeRegistry) -> Result<Self, SceneFormatError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let patch_deserializer = ScenePatchDeserializer { type_registry };
+        Ok(patch_deserializer.deserialize(&mut deserializer)?)
+    }
+
+    /// Deserializes a patch from the binary scene format.
+    pub fn from_binary(
+        bytes: &[u8],
+        type_registry: &TypeRegistry,
+    ) -> Result<Self, SceneFormatError> {
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(binary_scene_data(bytes)?);
+        let patch_deserializer = ScenePatchDeserializer { type_registry };
+        Ok(patch_deserializer.deserialize(&mut deserializer)?)
+    }
+}
+
+impl EntityPatch {
+    fn diff(old: &DynamicEntity, new: &DynamicEntity) -> Self {
+        let components = ValuesDiff::diff(&old.components, &new.components);
+        EntityPatch {
+            entity: new.entity,
+            added_components: components.added,
+            changed_components: components.changed,
+            replaced_components: components.replaced,
+            removed_components: components.removed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_components.is_empty()
+            && self.changed_components.is_empty()
+            && self.replaced_components.is_empty()
+            && self.removed_components.is_empty()
+    }
+}
+
+/// The difference between two lists of components or resources, which are matched by their type.
+#[derive(Default)]
+struct ValuesDiff {
+    added: Vec<Box<dyn Reflect>>,
+    changed: Vec<Box<dyn Reflect>>,
+    replaced: Vec<Box<dyn Reflect>>,
+    removed: Vec<String>,
+}
+
+impl ValuesDiff {
+    fn diff(old: &[Box<dyn Reflect>], new: &[Box<dyn Reflect>]) -> Self {
+        let mut diff = ValuesDiff::default();
+        for new_value in new.iter() {
+            let old_value = old
+                .iter()
+                .find(|old_value| old_value.type_name() == new_value.type_name());
+            let old_value = match old_value {
+                Some(old_value) => old_value,
+                None => {
+                    diff.added.push(new_value.clone_value());
+                    continue;
+                }
+            };
+            match diff_value(&**old_value, &**new_value) {
+                ValueDiff::Unchanged => {}
+                ValueDiff::Changed(changed) => diff.changed.push(changed),
+                ValueDiff::Replaced => diff.replaced.push(new_value.clone_value()),
+            }
+        }
+        for old_value in old.iter() {
+            if !new
+                .iter()
+                .any(|new_value| new_value.type_name() == old_value.type_name())
+            {
+                diff.removed.push(old_value.type_name().to_string());
+            }
+        }
+        diff
+    }
+}
+
+struct ComponentWriter<'a> {
+    entity_map: &'a EntityMap,
+    type_registry: &'a TypeRegistry,
+    asset_server: Option<&'a AssetServer>,
+}
+
+impl<'a> ComponentWriter<'a> {
+    fn write(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        component: &dyn Reflect,
+    ) -> Result<(), SceneSpawnError> {
+        let mut component = component.clone_value();
+        map_reflect_entities(&mut *component, &|scene_entity| {
+            self.entity_map.get(Entity::new(scene_entity.id())).ok()
+        });
+        write_component(
+            world,
+            entity,
+            &*component,
+            self.type_registry,
+            self.asset_server,
+        )
+    }
+}
+
+enum ValueDiff {
+    Unchanged,
+    /// The value can be patched by applying the contained value to it
+    Changed(Box<dyn Reflect>),
+    /// The value cannot be patched with [`Reflect::apply`]
+    Replaced,
+}
+
+/// Diffs two values with [`reflect_diff`]. Its changes are kept as a value that is applied with
+/// [`Reflect::apply`], so that they are serialized like the components of a scene, and changes
+/// that applying a value cannot make, like inserting or removing list items, replace the value.
+fn diff_value(old: &dyn Reflect, new: &dyn Reflect) -> ValueDiff {
+    let patch = reflect_diff(old, new);
+    let mut changed_paths = Vec::new();
+    for change in patch.changes.iter() {
+        match change {
+            ReflectChange::Modify { path, old, new } => {
+                // `reflect_diff` modifies the values that cannot be compared with `PartialEq`
+                if value_eq(&**old, &**new) == Some(true) {
+                    continue;
+                }
+                if old.type_name() != new.type_name() || !can_apply(&**old, &**new) {
+                    return ValueDiff::Replaced;
+                }
+                changed_paths.push(path.as_slice());
+            }
+            _ => return ValueDiff::Replaced,
+        }
+    }
+    if changed_paths.is_empty() {
+        ValueDiff::Unchanged
+    } else {
+        ValueDiff::Changed(changed_value(new, &changed_paths))
+    }
+}
+
+/// Whether applying `new` to `old` makes it equal to `new`. Structs and tuples are only modified
+/// as a whole by [`reflect_diff`] when their fields differ, which applying them does not change.
+fn can_apply(old: &dyn Reflect, new: &dyn Reflect) -> bool {
+    matches!(
+        (old.reflect_ref(), new.reflect_ref()),
+        (ReflectRef::Value(_), ReflectRef::Value(_)) | (ReflectRef::Enum(_), ReflectRef::Enum(_))
+    )
+}
+
+/// Keeps only the fields of the structs in `value` that contain a changed path, as applying a
+/// struct ignores missing fields. Other values can only be applied as a whole.
+fn changed_value(value: &dyn Reflect, changed_paths: &[&[PathSegment]]) -> Box<dyn Reflect> {
+    let value_struct = match value.reflect_ref() {
+        ReflectRef::Struct(value_struct) if changed_paths.iter().all(|path| !path.is_empty()) => {
+            value_struct
+        }
+        _ => return value.clone_value(),
+    };
+    let mut changed = DynamicStruct::default();
+    changed.set_name(value.type_name().to_string());
+    for (i, field) in value_struct.iter_fields().enumerate() {
+        let name = value_struct.name_at(i).unwrap();
+        let field_paths = changed_paths
+            .iter()
+            .filter_map(|path| match path.split_first() {
+                Some((PathSegment::Field(field_name), rest)) if field_name == name => Some(rest),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !field_paths.is_empty() {
+            changed.insert_boxed(name, changed_value(field, &field_paths));
+        }
+    }
+    Box::new(changed)
+}
+
+/// Compares two values with [`Reflect::reflect_partial_eq`], or by their serialized form if their
+/// type does not implement `PartialEq`.
+fn value_eq(old: &dyn Reflect, new: &dyn Reflect) -> Option<bool> {
+    old.reflect_partial_eq(new).or_else(|| {
+        let old = rmp_serde::to_vec(&old.serializable()?.borrow()).ok()?;
+        let new = rmp_serde::to_vec(&new.serializable()?.borrow()).ok()?;
+        Some(old == new)
+    })
+}
+
+fn clone_components(components: &[Box<dyn Reflect>]) -> Vec<Box<dyn Reflect>> {
+    components
+        .iter()
+        .map(|component| component.clone_value())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{Gravity, Health},
+        DynamicScene, DynamicSceneBuilder, Entity as SceneEntity, ScenePatch,
+    };
+    use bevy_ecs::{
+        entity::{Entity, EntityMap},
+        reflect::ReflectComponent,
+        world::World,
+    };
+    use bevy_reflect::{Reflect, ReflectDeserialize, TypeRegistryArc};
+    use bevy_transform::prelude::Parent;
+    use serde::{Deserialize, Serialize};
+
+    /// A value without `PartialEq`, which is compared by its serialized form
+    #[derive(Reflect, Default, Clone, Serialize, Deserialize)]
+    #[reflect_value(Component, Serialize, Deserialize)]
+    struct Tag(String);
+
+    fn world() -> World {
+        let world = crate::test_utils::world();
+        world
+            .get_resource::<TypeRegistryArc>()
+            .unwrap()
+            .write()
+            .register::<Tag>();
+        world
+    }
+
+    fn scene() -> DynamicScene {
+        DynamicScene {
+            entities: vec![
+                SceneEntity {
+                    entity: 0,
+                    components: vec![
+                        Box::new(Health {
+                            current: 10,
+                            max: 10,
+                        }),
+                        Box::new(Tag("player".to_string())),
+                    ],
+                },
+                SceneEntity {
+                    entity: 1,
+                    components: vec![
+                        Box::new(Parent(Entity::new(0))),
+                        Box::new(Health { current: 5, max: 5 }),
+                    ],
+                },
+            ],
+            resources: vec![Box::new(Gravity { value: -9.81 })],
+        }
+    }
+
+    /// Creates a scene from a spawned scene instance, including its resources.
+    fn scene_from_instance(world: &World, entity_map: &EntityMap) -> DynamicScene {
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        let mut scene = DynamicScene::from_instance(world, entity_map, registry);
+        scene.resources = DynamicSceneBuilder::new(world, registry)
+            .extract_resource::<Gravity>()
+            .build()
+            .resources;
+        scene
+    }
+
+    #[test]
+    fn unchanged_scene() {
+        let world = world();
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        let scene = scene();
+        assert!(ScenePatch::diff(&scene, &scene).is_empty());
+
+        let scene = DynamicScene::from_ron(
+            scene.serialize_ron(registry).unwrap().as_bytes(),
+            &registry.read(),
+        )
+        .unwrap();
+        assert!(ScenePatch::diff(&scene, &scene).is_empty());
+    }
+
+    #[test]
+    fn diff_and_apply_instance() {
+        let mut world = world();
+        let scene = scene();
+        let mut entity_map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        let entity_0 = entity_map.get(Entity::new(0)).unwrap();
+        let entity_1 = entity_map.get(Entity::new(1)).unwrap();
+        world.get_mut::<Health>(entity_0).unwrap().current = 3;
+        world.entity_mut(entity_1).remove::<Health>();
+        let entity_2 = world
+            .spawn()
+            .insert(Parent(entity_0))
+            .insert(Tag("pet".to_string()))
+            .id();
+        entity_map.insert(Entity::new(2), entity_2);
+        world.get_resource_mut::<Gravity>().unwrap().value = -1.62;
+        let modified_scene = scene_from_instance(&world, &entity_map);
+
+        let patch = ScenePatch::diff(&scene, &modified_scene);
+        assert_eq!(patch.added_entities.len(), 1);
+        assert_eq!(patch.added_entities[0].entity, 2);
+        assert!(patch.removed_entities.is_empty());
+        assert_eq!(patch.changed_entities.len(), 2);
+        let entity_patch_0 = &patch.changed_entities[0];
+        assert_eq!(entity_patch_0.entity, 0);
+        assert_eq!(entity_patch_0.changed_components.len(), 1);
+        assert!(entity_patch_0.added_components.is_empty());
+        let entity_patch_1 = &patch.changed_entities[1];
+        assert_eq!(entity_patch_1.entity, 1);
+        assert_eq!(
+            entity_patch_1.removed_components,
+            [std::any::type_name::<Health>()]
+        );
+        assert_eq!(patch.changed_resources.len(), 1);
+
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        let ron_patch = ScenePatch::from_ron(
+            patch.serialize_ron(registry).unwrap().as_bytes(),
+            &registry.read(),
+        )
+        .unwrap();
+        let binary_patch =
+            ScenePatch::from_binary(&patch.serialize_binary(registry).unwrap(), &registry.read())
+                .unwrap();
+
+        for patch in [patch, ron_patch, binary_patch].iter() {
+            let mut patched_world = self::world();
+            let mut entity_map = EntityMap::default();
+            scene
+                .write_to_world(&mut patched_world, &mut entity_map)
+                .unwrap();
+            patch
+                .write_to_world(&mut patched_world, &mut entity_map)
+                .unwrap();
+
+            let patched_scene = scene_from_instance(&patched_world, &entity_map);
+            assert!(ScenePatch::diff(&modified_scene, &patched_scene).is_empty());
+            let entity_0 = entity_map.get(Entity::new(0)).unwrap();
+            let entity_2 = entity_map.get(Entity::new(2)).unwrap();
+            assert_eq!(
+                patched_world.get::<Health>(entity_0),
+                Some(&Health {
+                    current: 3,
+                    max: 10
+                })
+            );
+            assert_eq!(patched_world.get::<Parent>(entity_2).unwrap().0, entity_0);
+            assert_eq!(
+                patched_world.get_resource::<Gravity>(),
+                Some(&Gravity { value: -1.62 })
+            );
+        }
+    }
+
+    #[test]
+    fn diff_values() {
+        use super::{diff_value, ValueDiff};
+        use bevy_reflect::{GetField, ReflectRef, Struct};
+
+        #[derive(Reflect, Clone)]
+        struct Inventory {
+            health: Health,
+            items: Vec<u32>,
+        }
+
+        let old = Inventory {
+            health: Health { current: 1, max: 2 },
+            items: vec![1, 2],
+        };
+        assert!(matches!(
+            diff_value(&old, &old.clone()),
+            ValueDiff::Unchanged
+        ));
+
+        let mut new = old.clone();
+        new.health.current = 2;
+        new.items[0] = 3;
+        let changed = match diff_value(&old, &new) {
+            ValueDiff::Changed(changed) => changed,
+            _ => panic!("expected the value to be changed"),
+        };
+        let changed = match changed.reflect_ref() {
+            ReflectRef::Struct(changed) => changed.clone_dynamic(),
+            _ => panic!("expected a struct"),
+        };
+        // only the changed fields of structs are kept, and lists are kept as a whole
+        let health = match changed.field("health").unwrap().reflect_ref() {
+            ReflectRef::Struct(health) => health.clone_dynamic(),
+            _ => panic!("expected a struct"),
+        };
+        assert_eq!(health.field_len(), 1);
+        assert_eq!(health.get_field::<u32>("current"), Some(&2));
+        assert_eq!(
+            changed
+                .field("items")
+                .unwrap()
+                .reflect_partial_eq(&vec![3u32, 2]),
+            Some(true)
+        );
+
+        new.items.push(4);
+        assert!(matches!(diff_value(&old, &new), ValueDiff::Replaced));
+    }
+}
@@ -1,9 +1,9 @@
-use crate::{DynamicScene, Scene};
+use crate::{dynamic_scene::map_entities, DynamicScene, Prefab, Scene};
 use bevy_app::{Events, ManualEventReader};
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
-    entity::{Entity, EntityMap},
-    reflect::{ReflectComponent, ReflectMapEntities},
+    entity::{Entity, EntityMap, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectComponentError},
     world::{Mut, World},
 };
 use bevy_reflect::TypeRegistryArc;
@@ -15,11 +15,21 @@ use uuid::Uuid;
 #[derive(Debug)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    parent: Option<Entity>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
 
+/// Event sent by the [`scene_spawner_system`] when a scene instance finished spawning, after it
+/// was parented to its parent entity if it was spawned as a child.
+#[derive(Debug)]
+pub struct SceneInstanceReady {
+    pub instance_id: InstanceId,
+    /// The parent entity of the instance, if it was spawned as a child
+    pub parent: Option<Entity>,
+}
+
 impl InstanceId {
     fn new() -> Self {
         InstanceId(Uuid::new_v4())
@@ -30,29 +40,59 @@ impl InstanceId {
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
+    spawned_prefabs: HashMap<Handle<Prefab>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
-    dynamic_scenes_to_spawn: Vec<Handle<DynamicScene>>,
+    prefab_asset_event_reader: ManualEventReader<AssetEvent<Prefab>>,
+    dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
+    prefabs_to_spawn: Vec<(Handle<Prefab>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
+    instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    ready_instances: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
 pub enum SceneSpawnError {
     #[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("prefab does not exist")]
+    NonExistentPrefab { handle: Handle<Prefab> },
+    #[error("prefab is based on itself")]
+    RecursivePrefab { handle: Handle<Prefab> },
+    #[error(transparent)]
+    ReflectComponent(#[from] ReflectComponentError),
+    #[error("failed to map the entities referenced by the scene: {0}")]
+    MapEntities(#[from] MapEntitiesError),
 }
 
 impl SceneSpawner {
-    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
-        self.dynamic_scenes_to_spawn.push(scene_handle);
+    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        instance_id
+    }
+
+    pub fn spawn_dynamic_as_child(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        parent: Entity,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        self.scenes_with_parent.push((instance_id, parent));
+        instance_id
     }
 
     pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
@@ -68,46 +108,95 @@ impl SceneSpawner {
         instance_id
     }
 
+    /// Spawns the base scene of a prefab with the overrides of the prefab applied.
+    pub fn spawn_prefab(&mut self, prefab_handle: Handle<Prefab>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.prefabs_to_spawn.push((prefab_handle, instance_id));
+        instance_id
+    }
+
+    /// Despawns all instances of a dynamic scene.
     pub fn despawn(&mut self, scene_handle: Handle<DynamicScene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    /// Despawns a single instance of a scene, dynamic scene or prefab. Instances that are not
+    /// spawned yet are not spawned anymore.
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.remove_queued_instance(instance_id);
+        self.instances_to_despawn.push(instance_id);
+    }
+
+    fn remove_queued_instance(&mut self, instance_id: InstanceId) {
+        self.dynamic_scenes_to_spawn
+            .retain(|(_, id)| *id != instance_id);
+        self.scenes_to_spawn.retain(|(_, id)| *id != instance_id);
+        self.prefabs_to_spawn.retain(|(_, id)| *id != instance_id);
+        self.scenes_with_parent.retain(|(id, _)| *id != instance_id);
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(entity); // Ignore the result, despawn only cares if
-                                                       // it exists.
-                    }
-                }
+                self.despawn_instance_sync(world, &instance_id);
             }
-
-            self.spawned_dynamic_scenes.remove(&scene_handle);
         }
         Ok(())
     }
 
+    pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
+        self.remove_queued_instance(*instance_id);
+        if let Some(instance) = self.spawned_instances.remove(instance_id) {
+            despawn_entities(world, &instance.entity_map);
+        }
+        for instance_ids in self.spawned_scenes.values_mut() {
+            instance_ids.retain(|id| id != instance_id);
+        }
+        for instance_ids in self.spawned_dynamic_scenes.values_mut() {
+            instance_ids.retain(|id| id != instance_id);
+        }
+        for instance_ids in self.spawned_prefabs.values_mut() {
+            instance_ids.retain(|id| id != instance_id);
+        }
+    }
+
     pub fn spawn_dynamic_sync(
         &mut self,
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
+        self.spawn_dynamic_sync_internal(world, scene_handle, InstanceId::new())
+    }
+
+    fn spawn_dynamic_sync_internal(
+        &mut self,
+        world: &mut World,
+        scene_handle: &Handle<DynamicScene>,
+        instance_id: InstanceId,
+    ) -> Result<InstanceId, SceneSpawnError> {
         let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
-        let instance_id = InstanceId::new();
-        self.spawned_instances
-            .insert(instance_id, InstanceInfo { entity_map });
+        if let Err(err) = Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map) {
+            despawn_entities(world, &entity_map);
+            return Err(err);
+        }
+        self.spawned_instances.insert(
+            instance_id,
+            InstanceInfo {
+                entity_map,
+                parent: None,
+            },
+        );
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
-        Ok(())
+        self.ready_instances.push(instance_id);
+        Ok(instance_id)
     }
 
     fn spawn_dynamic_internal(
@@ -126,6 +215,56 @@ impl SceneSpawner {
         })
     }
 
+    pub fn spawn_prefab_sync(
+        &mut self,
+        world: &mut World,
+        prefab_handle: Handle<Prefab>,
+    ) -> Result<InstanceId, SceneSpawnError> {
+        self.spawn_prefab_sync_internal(world, prefab_handle, InstanceId::new())
+    }
+
+    fn spawn_prefab_sync_internal(
+        &mut self,
+        world: &mut World,
+        prefab_handle: Handle<Prefab>,
+        instance_id: InstanceId,
+    ) -> Result<InstanceId, SceneSpawnError> {
+        let mut entity_map = EntityMap::default();
+        if let Err(err) = Self::spawn_prefab_internal(world, &prefab_handle, &mut entity_map) {
+            despawn_entities(world, &entity_map);
+            return Err(err);
+        }
+        self.spawned_instances.insert(
+            instance_id,
+            InstanceInfo {
+                entity_map,
+                parent: None,
+            },
+        );
+        let spawned = self.spawned_prefabs.entry(prefab_handle).or_default();
+        spawned.push(instance_id);
+        self.ready_instances.push(instance_id);
+        Ok(instance_id)
+    }
+
+    fn spawn_prefab_internal(
+        world: &mut World,
+        prefab_handle: &Handle<Prefab>,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        world.resource_scope(|world, prefabs: Mut<Assets<Prefab>>| {
+            let prefab =
+                prefabs
+                    .get(prefab_handle)
+                    .ok_or_else(|| SceneSpawnError::NonExistentPrefab {
+                        handle: prefab_handle.clone_weak(),
+                    })?;
+            world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+                prefab.write_to_world(world, entity_map, &scenes, &prefabs)
+            })
+        })
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
@@ -142,66 +281,65 @@ impl SceneSpawner {
     ) -> Result<InstanceId, SceneSpawnError> {
         let mut instance_info = InstanceInfo {
             entity_map: EntityMap::default(),
+            parent: None,
         };
         let type_registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
         let type_registry = type_registry.read();
-        world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
-            let scene =
-                scenes
-                    .get(&scene_handle)
-                    .ok_or_else(|| SceneSpawnError::NonExistentRealScene {
+        let result =
+            world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
+                let scene = scenes.get(&scene_handle).ok_or_else(|| {
+                    SceneSpawnError::NonExistentRealScene {
                         handle: scene_handle.clone(),
-                    })?;
-
-            for archetype in scene.world.archetypes().iter() {
-                for scene_entity in archetype.entities() {
-                    let entity = *instance_info
-                        .entity_map
-                        .entry(*scene_entity)
-                        .or_insert_with(|| world.spawn().id());
-                    for component_id in archetype.components() {
-                        let component_info = scene
-                            .world
-                            .components()
-                            .get_info(component_id)
-                            .expect("component_ids in archetypes should have ComponentInfo");
-
-                        let reflect_component = type_registry
-                            .get(component_info.type_id().unwrap())
-                            .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                                type_name: component_info.name().to_string(),
-                            })
-                            .and_then(|registration| {
-                                registration.data::<ReflectComponent>().ok_or_else(|| {
-                                    SceneSpawnError::UnregisteredComponent {
-                                        type_name: component_info.name().to_string(),
-                                    }
+                    }
+                })?;
+
+                for archetype in scene.world.archetypes().iter() {
+                    for scene_entity in archetype.entities() {
+                        let entity = *instance_info
+                            .entity_map
+                            .entry(*scene_entity)
+                            .or_insert_with(|| world.spawn().id());
+                        for component_id in archetype.components() {
+                            let component_info =
+                                scene.world.components().get_info(component_id).expect(
+                                    "component_ids in archetypes should have ComponentInfo",
+                                );
+
+                            let reflect_component = type_registry
+                                .get(component_info.type_id().unwrap())
+                                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                                    type_name: component_info.name().to_string(),
                                 })
-                            })?;
-                        reflect_component.copy_component(
-                            &scene.world,
-                            world,
-                            *scene_entity,
-                            entity,
-                        );
+                                .and_then(|registration| {
+                                    registration.data::<ReflectComponent>().ok_or_else(|| {
+                                        SceneSpawnError::UnregisteredComponent {
+                                            type_name: component_info.name().to_string(),
+                                        }
+                                    })
+                                })?;
+                            reflect_component.try_copy_component(
+                                &scene.world,
+                                world,
+                                *scene_entity,
+                                entity,
+                            )?;
+                        }
                     }
                 }
-            }
-            for registration in type_registry.iter() {
-                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                    map_entities_reflect
-                        .map_entities(world, &instance_info.entity_map)
-                        .unwrap();
-                }
-            }
-            self.spawned_instances.insert(instance_id, instance_info);
-            let spawned = self
-                .spawned_scenes
-                .entry(scene_handle)
-                .or_insert_with(Vec::new);
-            spawned.push(instance_id);
-            Ok(instance_id)
-        })
+                map_entities(world, &instance_info.entity_map, &type_registry)
+            });
+        if let Err(err) = result {
+            despawn_entities(world, &instance_info.entity_map);
+            return Err(err);
+        }
+        self.spawned_instances.insert(instance_id, instance_info);
+        let spawned = self
+            .spawned_scenes
+            .entry(scene_handle)
+            .or_insert_with(Vec::new);
+        spawned.push(instance_id);
+        self.ready_instances.push(instance_id);
+        Ok(instance_id)
     }
 
     pub fn update_spawned_scenes(
@@ -210,14 +348,45 @@ impl SceneSpawner {
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
         for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-                for instance_id in spawned_instances.iter() {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            scene_handle,
-                            &mut instance_info.entity_map,
-                        )?;
+            let instance_ids = match self.spawned_dynamic_scenes.get(scene_handle) {
+                Some(instance_ids) => instance_ids.clone(),
+                None => continue,
+            };
+            for instance_id in instance_ids {
+                if let Some(instance_info) = self.spawned_instances.get_mut(&instance_id) {
+                    if let Err(err) = Self::spawn_dynamic_internal(
+                        world,
+                        scene_handle,
+                        &mut instance_info.entity_map,
+                    ) {
+                        self.drop_failed_instance(world, instance_id, err);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies modified prefabs, or prefabs whose base scene was modified, to their instances.
+    /// Instances that fail to update are logged and dropped.
+    pub fn update_spawned_prefabs(
+        &mut self,
+        world: &mut World,
+        prefab_handles: &[Handle<Prefab>],
+    ) -> Result<(), SceneSpawnError> {
+        for prefab_handle in prefab_handles {
+            let instance_ids = match self.spawned_prefabs.get(prefab_handle) {
+                Some(instance_ids) => instance_ids.clone(),
+                None => continue,
+            };
+            for instance_id in instance_ids {
+                if let Some(instance_info) = self.spawned_instances.get_mut(&instance_id) {
+                    if let Err(err) = Self::spawn_prefab_internal(
+                        world,
+                        prefab_handle,
+                        &mut instance_info.entity_map,
+                    ) {
+                        self.drop_failed_instance(world, instance_id, err);
                     }
                 }
             }
@@ -231,19 +400,40 @@ impl SceneSpawner {
         for scene_handle in scenes_to_despawn {
             self.despawn_sync(world, scene_handle)?;
         }
+
+        let instances_to_despawn = std::mem::take(&mut self.instances_to_despawn);
+
+        for instance_id in instances_to_despawn {
+            self.despawn_instance_sync(world, &instance_id);
+        }
         Ok(())
     }
 
+    /// Spawns the queued instances whose scene is loaded. Instances that fail to spawn are logged
+    /// and dropped.
     pub fn spawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_dynamic_sync(world, &scene_handle) {
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_dynamic_sync_internal(world, &scene_handle, instance_id) {
                 Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn.push(scene_handle)
+                Err(SceneSpawnError::NonExistentScene { .. }) => self
+                    .dynamic_scenes_to_spawn
+                    .push((scene_handle, instance_id)),
+                Err(err) => self.drop_failed_instance(world, instance_id, err),
+            }
+        }
+
+        let prefabs_to_spawn = std::mem::take(&mut self.prefabs_to_spawn);
+
+        for (prefab_handle, instance_id) in prefabs_to_spawn {
+            match self.spawn_prefab_sync_internal(world, prefab_handle.clone(), instance_id) {
+                Ok(_) => {}
+                Err(SceneSpawnError::NonExistentPrefab { .. })
+                | Err(SceneSpawnError::NonExistentScene { .. }) => {
+                    self.prefabs_to_spawn.push((prefab_handle, instance_id))
                 }
-                Err(err) => return Err(err),
+                Err(err) => self.drop_failed_instance(world, instance_id, err),
             }
         }
 
@@ -255,18 +445,31 @@ impl SceneSpawner {
                 Err(SceneSpawnError::NonExistentRealScene { handle }) => {
                     self.scenes_to_spawn.push((handle, instance_id))
                 }
-                Err(err) => return Err(err),
+                Err(err) => self.drop_failed_instance(world, instance_id, err),
             }
         }
 
         Ok(())
     }
 
+    /// Logs why an instance could not be spawned or updated, and despawns it so that it isn't
+    /// retried.
+    fn drop_failed_instance(
+        &mut self,
+        world: &mut World,
+        instance_id: InstanceId,
+        err: SceneSpawnError,
+    ) {
+        error!("Dropping scene instance {:?}: {}", instance_id, err);
+        self.despawn_instance_sync(world, &instance_id);
+    }
+
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
         for (instance_id, parent) in scenes_with_parent {
-            if let Some(instance) = self.spawned_instances.get(&instance_id) {
+            if let Some(instance) = self.spawned_instances.get_mut(&instance_id) {
+                instance.parent = Some(parent);
                 for entity in instance.entity_map.values() {
                     if let Some(mut entity_mut) = world.get_entity_mut(entity) {
                         if !entity_mut.contains::<Parent>() {
@@ -280,11 +483,38 @@ impl SceneSpawner {
         }
     }
 
+    /// Sends a [`SceneInstanceReady`] event for every instance that was spawned since the last
+    /// call, once it is parented.
+    pub(crate) fn send_instance_ready_events(&mut self, world: &mut World) {
+        let ready_instances = std::mem::take(&mut self.ready_instances);
+        let mut events = match world.get_resource_mut::<Events<SceneInstanceReady>>() {
+            Some(events) => events,
+            None => return,
+        };
+        for instance_id in ready_instances {
+            // instances can be despawned before they are reported
+            if let Some(instance) = self.spawned_instances.get(&instance_id) {
+                events.send(SceneInstanceReady {
+                    instance_id,
+                    parent: instance.parent,
+                });
+            }
+        }
+    }
+
     /// Check that an scene instance spawned previously is ready to use
     pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
         self.spawned_instances.contains_key(&instance_id)
     }
 
+    /// Get the map from the scene entities to the spawned entities of an instance, once it's
+    /// spawned
+    pub fn instance_entity_map(&self, instance_id: InstanceId) -> Option<&EntityMap> {
+        self.spawned_instances
+            .get(&instance_id)
+            .map(|instance| &instance.entity_map)
+    }
+
     /// Get an iterator over the entities in an instance, once it's spawned
     pub fn iter_instance_entities(
         &'_ self,
@@ -296,6 +526,13 @@ impl SceneSpawner {
     }
 }
 
+fn despawn_entities(world: &mut World, entity_map: &EntityMap) {
+    for entity in entity_map.values() {
+        // Ignore the result, despawn only cares if it exists.
+        let _ = world.despawn(entity);
+    }
+}
+
 pub fn scene_spawner_system(world: &mut World) {
     world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
         let scene_asset_events = world
@@ -303,6 +540,7 @@ pub fn scene_spawner_system(world: &mut World) {
             .unwrap();
 
         let mut updated_spawned_scenes = Vec::new();
+        let mut updated_base_scenes = Vec::new();
         for event in scene_spawner
             .scene_asset_event_reader
             .iter(scene_asset_events)
@@ -311,16 +549,228 @@ pub fn scene_spawner_system(world: &mut World) {
                 if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
                     updated_spawned_scenes.push(handle.clone_weak());
                 }
+                updated_base_scenes.push(handle.clone_weak());
             }
         }
 
-        scene_spawner.despawn_queued_scenes(world).unwrap();
-        scene_spawner
-            .spawn_queued_scenes(world)
-            .unwrap_or_else(|err| panic!("{}", err));
-        scene_spawner
-            .update_spawned_scenes(world, &updated_spawned_scenes)
-            .unwrap();
+        // prefabs are updated when they, or the prefabs or scene they are based on, are modified
+        let prefab_asset_events = world.get_resource::<Events<AssetEvent<Prefab>>>().unwrap();
+        let prefabs = world.get_resource::<Assets<Prefab>>().unwrap();
+        let mut modified_prefabs = Vec::new();
+        for event in scene_spawner
+            .prefab_asset_event_reader
+            .iter(prefab_asset_events)
+        {
+            if let AssetEvent::Modified { handle } = event {
+                modified_prefabs.push(handle.clone_weak());
+            }
+        }
+        let updated_spawned_prefabs = scene_spawner
+            .spawned_prefabs
+            .keys()
+            .filter(|prefab_handle| {
+                Prefab::is_modified(
+                    prefab_handle,
+                    prefabs,
+                    &modified_prefabs,
+                    &updated_base_scenes,
+                )
+            })
+            .map(|prefab_handle| prefab_handle.clone_weak())
+            .collect::<Vec<_>>();
+
+        if let Err(err) = scene_spawner.despawn_queued_scenes(world) {
+            error!("{}", err);
+        }
+        if let Err(err) = scene_spawner.spawn_queued_scenes(world) {
+            error!("{}", err);
+        }
+        if let Err(err) = scene_spawner.update_spawned_scenes(world, &updated_spawned_scenes) {
+            error!("{}", err);
+        }
+        if let Err(err) = scene_spawner.update_spawned_prefabs(world, &updated_spawned_prefabs) {
+            error!("{}", err);
+        }
         scene_spawner.set_scene_instance_parent_sync(world);
+        scene_spawner.send_instance_ready_events(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::Health, DynamicScene, Entity as SceneEntity, InstanceId, SceneInstanceReady,
+        SceneSpawner,
+    };
+    use bevy_app::{App, Events};
+    use bevy_asset::{AssetServer, Assets, EmbeddedAssets, Handle};
+    use bevy_ecs::{entity::Entity, reflect::ReflectComponent};
+    use bevy_reflect::Reflect;
+    use bevy_transform::prelude::Parent;
+
+    /// A component whose type is not in the registry, so it can't be spawned.
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Unregistered;
+
+    fn app() -> App {
+        crate::test_utils::app(EmbeddedAssets::default())
+    }
+
+    /// A scene with a root entity and a child of the root.
+    fn scene() -> DynamicScene {
+        DynamicScene {
+            entities: vec![
+                SceneEntity {
+                    entity: 0,
+                    components: vec![Box::new(Health { current: 1, max: 1 })],
+                },
+                SceneEntity {
+                    entity: 1,
+                    components: vec![
+                        Box::new(Health { current: 2, max: 2 }),
+                        Box::new(Parent(Entity::new(0))),
+                    ],
+                },
+            ],
+            resources: Vec::new(),
+        }
+    }
+
+    fn add_scene(app: &mut App) -> Handle<DynamicScene> {
+        app.world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(scene())
+    }
+
+    fn scene_spawner(app: &mut App) -> &mut SceneSpawner {
+        app.world
+            .get_resource_mut::<SceneSpawner>()
+            .unwrap()
+            .into_inner()
+    }
+
+    fn instance_entity(app: &App, instance_id: InstanceId, scene_entity: u32) -> Entity {
+        app.world
+            .get_resource::<SceneSpawner>()
+            .unwrap()
+            .instance_entity_map(instance_id)
+            .unwrap()
+            .get(Entity::new(scene_entity))
+            .unwrap()
+    }
+
+    fn ready_events(app: &App) -> &Events<SceneInstanceReady> {
+        app.world
+            .get_resource::<Events<SceneInstanceReady>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn spawn_dynamic_as_child() {
+        let mut app = app();
+        let scene_handle = add_scene(&mut app);
+        let parent = app.world.spawn().id();
+        let instance_id = scene_spawner(&mut app).spawn_dynamic_as_child(scene_handle, parent);
+        let mut ready_reader = ready_events(&app).get_reader();
+        app.update();
+
+        let root = instance_entity(&app, instance_id, 0);
+        let child = instance_entity(&app, instance_id, 1);
+        assert_eq!(app.world.get::<Parent>(root), Some(&Parent(parent)));
+        assert_eq!(app.world.get::<Parent>(child), Some(&Parent(root)));
+
+        let ready = ready_reader.iter(ready_events(&app)).collect::<Vec<_>>();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].instance_id, instance_id);
+        assert_eq!(ready[0].parent, Some(parent));
+        app.update();
+        assert_eq!(ready_reader.iter(ready_events(&app)).count(), 0);
+    }
+
+    #[test]
+    fn despawn_instance() {
+        let mut app = app();
+        let scene_handle = add_scene(&mut app);
+        let despawned_id = scene_spawner(&mut app).spawn_dynamic(scene_handle.clone());
+        let kept_id = scene_spawner(&mut app).spawn_dynamic(scene_handle);
+        app.update();
+        let despawned_entity = instance_entity(&app, despawned_id, 0);
+        let kept_entity = instance_entity(&app, kept_id, 0);
+
+        scene_spawner(&mut app).despawn_instance(despawned_id);
+        app.update();
+        assert!(app.world.get_entity(despawned_entity).is_none());
+        assert!(app.world.get_entity(kept_entity).is_some());
+        assert_eq!(app.world.entities().len(), 2);
+        let scene_spawner = scene_spawner(&mut app);
+        assert!(!scene_spawner.instance_is_ready(despawned_id));
+        assert!(scene_spawner.instance_is_ready(kept_id));
+    }
+
+    #[test]
+    fn despawn_pending_instance() {
+        let mut app = app();
+        let parent = app.world.spawn().id();
+        // the scene is added after the instances are queued, so they stay pending until then
+        let scene_handle = app
+            .world
+            .get_resource::<AssetServer>()
+            .unwrap()
+            .get_handle("scene.scn.ron");
+        let despawned_id =
+            scene_spawner(&mut app).spawn_dynamic_as_child(scene_handle.clone(), parent);
+        let kept_id = scene_spawner(&mut app).spawn_dynamic(scene_handle.clone());
+        let mut ready_reader = ready_events(&app).get_reader();
+        app.update();
+        assert!(!scene_spawner(&mut app).instance_is_ready(kept_id));
+
+        scene_spawner(&mut app).despawn_instance(despawned_id);
+        app.world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .set_untracked(scene_handle, scene());
+        app.update();
+
+        let scene_spawner = scene_spawner(&mut app);
+        assert!(!scene_spawner.instance_is_ready(despawned_id));
+        assert!(scene_spawner.instance_is_ready(kept_id));
+        // the parent and the entities of the kept instance
+        assert_eq!(app.world.entities().len(), 3);
+        let ready = ready_reader.iter(ready_events(&app)).collect::<Vec<_>>();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].instance_id, kept_id);
+        assert_eq!(ready[0].parent, None);
+    }
+
+    #[test]
+    fn drop_instance_that_fails_to_spawn() {
+        let mut app = app();
+        let mut failing_scene = scene();
+        failing_scene.entities[1]
+            .components
+            .push(Box::new(Unregistered));
+        let failing_handle = app
+            .world
+            .get_resource_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(failing_scene);
+        let scene_handle = add_scene(&mut app);
+        let failing_id = scene_spawner(&mut app).spawn_dynamic(failing_handle);
+        let kept_id = scene_spawner(&mut app).spawn_dynamic(scene_handle);
+        let mut ready_reader = ready_events(&app).get_reader();
+        app.update();
+        app.update();
+
+        let scene_spawner = scene_spawner(&mut app);
+        assert!(!scene_spawner.instance_is_ready(failing_id));
+        assert!(scene_spawner.instance_is_ready(kept_id));
+        assert!(scene_spawner.dynamic_scenes_to_spawn.is_empty());
+        // the entities spawned before the failure are despawned
+        assert_eq!(app.world.entities().len(), 2);
+        let ready = ready_reader.iter(ready_events(&app)).collect::<Vec<_>>();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].instance_id, kept_id);
+    }
+}
@@ -1,11 +1,18 @@
-use crate::{DynamicScene, Entity};
+use crate::{
+    DynamicScene, Entity, EntityPatch, Prefab, PrefabBase, PrefabEntity, ScenePatch,
+    SceneValidation, SceneValidationIssueKind,
+};
 use anyhow::Result;
+use bevy_asset::AssetPath;
 use bevy_reflect::{
-    serde::{ReflectDeserializer, ReflectSerializer},
+    serde::{type_fields, ReflectDeserializer, ReflectSerializer},
     Reflect, TypeRegistry, TypeRegistryArc,
 };
 use serde::{
-    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    de::{
+        value::MapAccessDeserializer, DeserializeSeed, Error, IgnoredAny, IntoDeserializer,
+        MapAccess, SeqAccess, Visitor,
+    },
     ser::{SerializeSeq, SerializeStruct},
     Deserialize, Serialize,
 };
@@ -26,8 +33,40 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let entities = EntitiesSerializer {
+            entities: &self.scene.entities,
+            registry: self.registry,
+        };
+        // scenes without resources are a list of entities, like before scenes had resources
+        if self.scene.resources.is_empty() {
+            return entities.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(SCENE_FIELD_ENTITIES, &entities)?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -91,17 +130,140 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+            validation: None,
+        })
+    }
+}
+
+/// Deserializes a [`DynamicScene`] like [`SceneDeserializer`], but skips the components and
+/// resources whose types are not registered, and reports them in `validation` instead of failing.
+pub struct ValidatingSceneDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub validation: &'a mut SceneValidation,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ValidatingSceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+            validation: Some(self.validation),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Resources,
+    Entities,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub validation: Option<&'a mut SceneValidation>,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entities, or scene struct")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
     {
         Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            entities: SceneEntitySeqVisitor {
                 type_registry: self.type_registry,
-            })?,
+                validation: self.validation,
+            }
+            .visit_seq(seq)?,
+            resources: Vec::new(),
+        })
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    let mut skipped = Vec::new();
+                    resources = Some(map.next_value_seed(ComponentSeqVisitor {
+                        registry: self.type_registry,
+                        skipped: self.validation.as_ref().map(|_| &mut skipped),
+                    })?);
+                    if let Some(validation) = self.validation.as_deref_mut() {
+                        for type_name in skipped {
+                            validation.push(
+                                None,
+                                &type_name,
+                                SceneValidationIssueKind::UnregisteredType,
+                            );
+                        }
+                    }
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                        validation: self.validation.as_deref_mut(),
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            entities: entities.unwrap_or_default(),
+            resources: resources.unwrap_or_default(),
+        })
+    }
+}
+
+struct SceneEntitySeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub validation: Option<&'a mut SceneValidation>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitySeqDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
+            validation: self.validation,
         })
     }
 }
 
 struct SceneEntitySeqVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub validation: Option<&'a mut SceneValidation>,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntitySeqVisitor<'a> {
@@ -111,13 +273,14 @@ impl<'a, 'de> Visitor<'de> for SceneEntitySeqVisitor<'a> {
         formatter.write_str("list of entities")
     }
 
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
         let mut entities = Vec::new();
-        while let Some(entity) = seq.next_element_seed(SceneEntityDeserializer {
-            type_registry: self.type_registry,
+        while let Some(entity) = seq.next_element_seed(SceneEntityVisitor {
+            registry: self.type_registry,
+            validation: self.validation.as_deref_mut(),
         })? {
             entities.push(entity);
         }
@@ -137,13 +300,11 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_struct(
-            ENTITY_STRUCT,
-            &[ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
-            SceneEntityVisitor {
-                registry: self.type_registry,
-            },
-        )
+        SceneEntityVisitor {
+            registry: self.type_registry,
+            validation: None,
+        }
+        .deserialize(deserializer)
     }
 }
 
@@ -160,6 +321,22 @@ pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
 struct SceneEntityVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub validation: Option<&'a mut SceneValidation>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntityVisitor<'a> {
+    type Value = Entity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_STRUCT,
+            &[ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
+            self,
+        )
+    }
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
@@ -175,6 +352,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
     {
         let mut id = None;
         let mut components = None;
+        let mut skipped = Vec::new();
         while let Some(key) = map.next_key()? {
             match key {
                 EntityField::Entity => {
@@ -188,8 +366,9 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
                         return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
                     }
 
-                    components = Some(map.next_value_seed(ComponentVecDeserializer {
+                    components = Some(map.next_value_seed(ComponentSeqVisitor {
                         registry: self.registry,
+                        skipped: self.validation.as_ref().map(|_| &mut skipped),
                     })?);
                 }
             }
@@ -202,6 +381,15 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         let components = components
             .take()
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        if let Some(validation) = self.validation {
+            for type_name in skipped {
+                validation.push(
+                    Some(*entity),
+                    &type_name,
+                    SceneValidationIssueKind::UnregisteredType,
+                );
+            }
+        }
         Ok(Entity {
             entity: *entity,
             components,
@@ -220,14 +408,29 @@ impl<'a, 'de> DeserializeSeed<'de> for ComponentVecDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_seq(ComponentSeqVisitor {
+        ComponentSeqVisitor {
             registry: self.registry,
-        })
+            skipped: None,
+        }
+        .deserialize(deserializer)
     }
 }
 
 struct ComponentSeqVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    /// The type names of the skipped components, if components of unregistered types are skipped
+    pub skipped: Option<&'a mut Vec<String>>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentSeqVisitor<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
 }
 
 impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
@@ -242,10 +445,729 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         A: SeqAccess<'de>,
     {
         let mut dynamic_properties = Vec::new();
-        while let Some(entity) = seq.next_element_seed(ReflectDeserializer::new(self.registry))? {
-            dynamic_properties.push(entity);
+        match self.skipped {
+            Some(skipped) => {
+                while let Some(component) = seq.next_element_seed(RegisteredValueDeserializer {
+                    registry: self.registry,
+                    skipped: &mut *skipped,
+                })? {
+                    dynamic_properties.extend(component);
+                }
+            }
+            None => {
+                while let Some(component) =
+                    seq.next_element_seed(ReflectDeserializer::new(self.registry))?
+                {
+                    dynamic_properties.push(component);
+                }
+            }
         }
 
         Ok(dynamic_properties)
     }
 }
+
+/// Deserializes a reflected value like [`ReflectDeserializer`], or skips it if its type is not
+/// registered.
+struct RegisteredValueDeserializer<'a> {
+    registry: &'a TypeRegistry,
+    skipped: &'a mut Vec<String>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for RegisteredValueDeserializer<'a> {
+    type Value = Option<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for RegisteredValueDeserializer<'a> {
+    type Value = Option<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("reflect value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        // reflected values are serialized with their type first
+        if map.next_key::<String>()?.as_deref() != Some(type_fields::TYPE) {
+            return Err(Error::missing_field(type_fields::TYPE));
+        }
+        let type_name = map.next_value::<String>()?;
+        if self.registry.get_with_name(&type_name).is_none() {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            self.skipped.push(type_name);
+            return Ok(None);
+        }
+        ReflectDeserializer::new(self.registry)
+            .deserialize(MapAccessDeserializer::new(TypeFirstMapAccess {
+                type_name: Some(type_name),
+                type_key_read: false,
+                map,
+            }))
+            .map(Some)
+    }
+}
+
+/// A [`MapAccess`] that returns the type entry of a reflected value that was already read from
+/// `map`, followed by the remaining entries of `map`.
+struct TypeFirstMapAccess<A> {
+    type_name: Option<String>,
+    type_key_read: bool,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TypeFirstMapAccess<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if !self.type_key_read {
+            self.type_key_read = true;
+            return seed
+                .deserialize(type_fields::TYPE.into_deserializer())
+                .map(Some);
+        }
+        self.map.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.type_name.take() {
+            Some(type_name) => seed.deserialize(type_name.into_deserializer()),
+            None => self.map.next_value_seed(seed),
+        }
+    }
+}
+
+/// Deserializes a [`Prefab`]. The base scene or prefab of the prefab is stored as its asset path,
+/// which is turned into a [`PrefabBase`] with `load_base`.
+pub struct PrefabDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub load_base: &'a mut dyn FnMut(AssetPath<'static>) -> PrefabBase,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabField {
+    Base,
+    Entities,
+}
+
+pub const PREFAB_STRUCT: &str = "Prefab";
+pub const PREFAB_FIELD_BASE: &str = "base";
+pub const PREFAB_FIELD_ENTITIES: &str = "entities";
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabDeserializer<'a> {
+    type Value = Prefab;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_STRUCT,
+            &[PREFAB_FIELD_BASE, PREFAB_FIELD_ENTITIES],
+            PrefabVisitor {
+                type_registry: self.type_registry,
+                load_base: self.load_base,
+            },
+        )
+    }
+}
+
+struct PrefabVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub load_base: &'a mut dyn FnMut(AssetPath<'static>) -> PrefabBase,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabVisitor<'a> {
+    type Value = Prefab;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut base = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabField::Base => {
+                    if base.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_BASE));
+                    }
+                    base = Some(map.next_value::<String>()?);
+                }
+                PrefabField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(PrefabEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let base = base.ok_or_else(|| Error::missing_field(PREFAB_FIELD_BASE))?;
+        Ok(Prefab {
+            base: (self.load_base)(AssetPath::from(base.as_str()).to_owned()),
+            entities: entities.unwrap_or_default(),
+        })
+    }
+}
+
+struct PrefabEntitySeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabEntitySeqDeserializer<'a> {
+    type Value = Vec<PrefabEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabEntitySeqDeserializer<'a> {
+    type Value = Vec<PrefabEntity>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of prefab entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(PrefabEntityDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            entities.push(entity);
+        }
+
+        Ok(entities)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum PrefabEntityField {
+    Entity,
+    Components,
+    RemovedComponents,
+}
+
+pub const PREFAB_ENTITY_FIELD_REMOVED_COMPONENTS: &str = "removed_components";
+
+struct PrefabEntityDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabEntityDeserializer<'a> {
+    type Value = PrefabEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_STRUCT,
+            &[
+                ENTITY_FIELD_ENTITY,
+                ENTITY_FIELD_COMPONENTS,
+                PREFAB_ENTITY_FIELD_REMOVED_COMPONENTS,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabEntityDeserializer<'a> {
+    type Value = PrefabEntity;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab entity")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut id = None;
+        let mut components = None;
+        let mut removed_components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabEntityField::Entity => {
+                    if id.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_ENTITY));
+                    }
+                    id = Some(map.next_value::<u32>()?);
+                }
+                PrefabEntityField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                PrefabEntityField::RemovedComponents => {
+                    if removed_components.is_some() {
+                        return Err(Error::duplicate_field(
+                            PREFAB_ENTITY_FIELD_REMOVED_COMPONENTS,
+                        ));
+                    }
+                    removed_components = Some(map.next_value::<Vec<String>>()?);
+                }
+            }
+        }
+
+        let entity = id.ok_or_else(|| Error::missing_field(ENTITY_FIELD_ENTITY))?;
+        Ok(PrefabEntity {
+            entity,
+            components: components.unwrap_or_default(),
+            removed_components: removed_components.unwrap_or_default(),
+        })
+    }
+}
+
+pub struct ScenePatchSerializer<'a> {
+    pub patch: &'a ScenePatch,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> ScenePatchSerializer<'a> {
+    pub fn new(patch: &'a ScenePatch, registry: &'a TypeRegistryArc) -> Self {
+        ScenePatchSerializer { patch, registry }
+    }
+}
+
+impl<'a> Serialize for ScenePatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SCENE_PATCH_STRUCT, 7)?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_ADDED_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.patch.added_entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_REMOVED_ENTITIES,
+            &self.patch.removed_entities,
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_CHANGED_ENTITIES,
+            &EntityPatchesSerializer {
+                entity_patches: &self.patch.changed_entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_ADDED_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.patch.added_resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_CHANGED_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.patch.changed_resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_REPLACED_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.patch.replaced_resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_PATCH_FIELD_REMOVED_RESOURCES,
+            &self.patch.removed_resources,
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntityPatchesSerializer<'a> {
+    pub entity_patches: &'a [EntityPatch],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntityPatchesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entity_patches.len()))?;
+        for entity_patch in self.entity_patches.iter() {
+            state.serialize_element(&EntityPatchSerializer {
+                entity_patch,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct EntityPatchSerializer<'a> {
+    pub entity_patch: &'a EntityPatch,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntityPatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 5)?;
+        state.serialize_field(ENTITY_FIELD_ENTITY, &self.entity_patch.entity)?;
+        state.serialize_field(
+            ENTITY_PATCH_FIELD_ADDED_COMPONENTS,
+            &ComponentsSerializer {
+                components: &self.entity_patch.added_components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            ENTITY_PATCH_FIELD_CHANGED_COMPONENTS,
+            &ComponentsSerializer {
+                components: &self.entity_patch.changed_components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            ENTITY_PATCH_FIELD_REPLACED_COMPONENTS,
+            &ComponentsSerializer {
+                components: &self.entity_patch.replaced_components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            ENTITY_PATCH_FIELD_REMOVED_COMPONENTS,
+            &self.entity_patch.removed_components,
+        )?;
+        state.end()
+    }
+}
+
+pub struct ScenePatchDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier)]
+enum ScenePatchField {
+    #[serde(rename = "added_entities")]
+    Added,
+    #[serde(rename = "removed_entities")]
+    Removed,
+    #[serde(rename = "changed_entities")]
+    Changed,
+    #[serde(rename = "added_resources")]
+    AddedResources,
+    #[serde(rename = "changed_resources")]
+    ChangedResources,
+    #[serde(rename = "replaced_resources")]
+    ReplacedResources,
+    #[serde(rename = "removed_resources")]
+    RemovedResources,
+}
+
+pub const SCENE_PATCH_STRUCT: &str = "ScenePatch";
+pub const SCENE_PATCH_FIELD_ADDED_ENTITIES: &str = "added_entities";
+pub const SCENE_PATCH_FIELD_REMOVED_ENTITIES: &str = "removed_entities";
+pub const SCENE_PATCH_FIELD_CHANGED_ENTITIES: &str = "changed_entities";
+pub const SCENE_PATCH_FIELD_ADDED_RESOURCES: &str = "added_resources";
+pub const SCENE_PATCH_FIELD_CHANGED_RESOURCES: &str = "changed_resources";
+pub const SCENE_PATCH_FIELD_REPLACED_RESOURCES: &str = "replaced_resources";
+pub const SCENE_PATCH_FIELD_REMOVED_RESOURCES: &str = "removed_resources";
+
+impl<'a, 'de> DeserializeSeed<'de> for ScenePatchDeserializer<'a> {
+    type Value = ScenePatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SCENE_PATCH_STRUCT,
+            &[
+                SCENE_PATCH_FIELD_ADDED_ENTITIES,
+                SCENE_PATCH_FIELD_REMOVED_ENTITIES,
+                SCENE_PATCH_FIELD_CHANGED_ENTITIES,
+                SCENE_PATCH_FIELD_ADDED_RESOURCES,
+                SCENE_PATCH_FIELD_CHANGED_RESOURCES,
+                SCENE_PATCH_FIELD_REPLACED_RESOURCES,
+                SCENE_PATCH_FIELD_REMOVED_RESOURCES,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ScenePatchDeserializer<'a> {
+    type Value = ScenePatch;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene patch")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added_entities = None;
+        let mut removed_entities = None;
+        let mut changed_entities = None;
+        let mut added_resources = None;
+        let mut changed_resources = None;
+        let mut replaced_resources = None;
+        let mut removed_resources = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                ScenePatchField::Added => {
+                    if added_entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_ADDED_ENTITIES));
+                    }
+                    added_entities = Some(map.next_value_seed(SceneEntitySeqDeserializer {
+                        type_registry: self.type_registry,
+                        validation: None,
+                    })?);
+                }
+                ScenePatchField::Removed => {
+                    if removed_entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_REMOVED_ENTITIES));
+                    }
+                    removed_entities = Some(map.next_value::<Vec<u32>>()?);
+                }
+                ScenePatchField::Changed => {
+                    if changed_entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_CHANGED_ENTITIES));
+                    }
+                    changed_entities = Some(map.next_value_seed(EntityPatchSeqDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                ScenePatchField::AddedResources => {
+                    if added_resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_ADDED_RESOURCES));
+                    }
+                    added_resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                ScenePatchField::ChangedResources => {
+                    if changed_resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_CHANGED_RESOURCES));
+                    }
+                    changed_resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                ScenePatchField::ReplacedResources => {
+                    if replaced_resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_REPLACED_RESOURCES));
+                    }
+                    replaced_resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                ScenePatchField::RemovedResources => {
+                    if removed_resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_PATCH_FIELD_REMOVED_RESOURCES));
+                    }
+                    removed_resources = Some(map.next_value::<Vec<String>>()?);
+                }
+            }
+        }
+
+        Ok(ScenePatch {
+            added_entities: added_entities.unwrap_or_default(),
+            removed_entities: removed_entities.unwrap_or_default(),
+            changed_entities: changed_entities.unwrap_or_default(),
+            added_resources: added_resources.unwrap_or_default(),
+            changed_resources: changed_resources.unwrap_or_default(),
+            replaced_resources: replaced_resources.unwrap_or_default(),
+            removed_resources: removed_resources.unwrap_or_default(),
+        })
+    }
+}
+
+struct EntityPatchSeqDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchSeqDeserializer<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchSeqDeserializer<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entity patches")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entity_patches = Vec::new();
+        while let Some(entity_patch) = seq.next_element_seed(EntityPatchDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            entity_patches.push(entity_patch);
+        }
+
+        Ok(entity_patches)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum EntityPatchField {
+    Entity,
+    AddedComponents,
+    ChangedComponents,
+    ReplacedComponents,
+    RemovedComponents,
+}
+
+pub const ENTITY_PATCH_FIELD_ADDED_COMPONENTS: &str = "added_components";
+pub const ENTITY_PATCH_FIELD_CHANGED_COMPONENTS: &str = "changed_components";
+pub const ENTITY_PATCH_FIELD_REPLACED_COMPONENTS: &str = "replaced_components";
+pub const ENTITY_PATCH_FIELD_REMOVED_COMPONENTS: &str = "removed_components";
+
+struct EntityPatchDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchDeserializer<'a> {
+    type Value = EntityPatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_STRUCT,
+            &[
+                ENTITY_FIELD_ENTITY,
+                ENTITY_PATCH_FIELD_ADDED_COMPONENTS,
+                ENTITY_PATCH_FIELD_CHANGED_COMPONENTS,
+                ENTITY_PATCH_FIELD_REPLACED_COMPONENTS,
+                ENTITY_PATCH_FIELD_REMOVED_COMPONENTS,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchDeserializer<'a> {
+    type Value = EntityPatch;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("entity patch")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut id = None;
+        let mut added_components = None;
+        let mut changed_components = None;
+        let mut replaced_components = None;
+        let mut removed_components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntityPatchField::Entity => {
+                    if id.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_ENTITY));
+                    }
+                    id = Some(map.next_value::<u32>()?);
+                }
+                EntityPatchField::AddedComponents => {
+                    if added_components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_PATCH_FIELD_ADDED_COMPONENTS));
+                    }
+                    added_components = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                EntityPatchField::ChangedComponents => {
+                    if changed_components.is_some() {
+                        return Err(Error::duplicate_field(
+                            ENTITY_PATCH_FIELD_CHANGED_COMPONENTS,
+                        ));
+                    }
+                    changed_components = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                EntityPatchField::ReplacedComponents => {
+                    if replaced_components.is_some() {
+                        return Err(Error::duplicate_field(
+                            ENTITY_PATCH_FIELD_REPLACED_COMPONENTS,
+                        ));
+                    }
+                    replaced_components = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                EntityPatchField::RemovedComponents => {
+                    if removed_components.is_some() {
+                        return Err(Error::duplicate_field(
+                            ENTITY_PATCH_FIELD_REMOVED_COMPONENTS,
+                        ));
+                    }
+                    removed_components = Some(map.next_value::<Vec<String>>()?);
+                }
+            }
+        }
+
+        let entity = id.ok_or_else(|| Error::missing_field(ENTITY_FIELD_ENTITY))?;
+        Ok(EntityPatch {
+            entity,
+            added_components: added_components.unwrap_or_default(),
+            changed_components: changed_components.unwrap_or_default(),
+            replaced_components: replaced_components.unwrap_or_default(),
+            removed_components: removed_components.unwrap_or_default(),
+        })
+    }
+}
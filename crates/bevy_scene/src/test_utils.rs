@@ -0,0 +1,79 @@
+//! Fixtures shared by the tests of this crate.
+
+use crate::{ScenePlugin, SceneValidationSettings};
+use bevy_app::App;
+use bevy_asset::{AssetPlugin, AssetServer, EmbeddedAssetIo, EmbeddedAssets};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc};
+use bevy_tasks::TaskPool;
+use bevy_transform::prelude::{Children, Parent};
+
+#[derive(Reflect, Default, Clone, Debug, PartialEq)]
+#[reflect(Component)]
+pub(crate) struct Health {
+    pub(crate) current: u32,
+    pub(crate) max: u32,
+}
+
+#[derive(Reflect, Default, Debug, PartialEq)]
+#[reflect(Component)]
+pub(crate) struct Speed {
+    pub(crate) value: f32,
+}
+
+#[derive(Reflect, Default, Debug, PartialEq)]
+#[reflect(Resource)]
+pub(crate) struct Gravity {
+    pub(crate) value: f32,
+}
+
+/// Registers the fixture types, the hierarchy components and the types of their fields.
+pub(crate) fn register_types(registry: &mut TypeRegistry) {
+    registry.register::<u32>();
+    registry.register::<f32>();
+    registry.register::<Entity>();
+    registry.register::<Parent>();
+    registry.register::<Children>();
+    registry.register::<Health>();
+    registry.register::<Speed>();
+    registry.register::<Gravity>();
+}
+
+pub(crate) fn registry() -> TypeRegistryArc {
+    let registry = TypeRegistryArc::default();
+    register_types(&mut registry.write());
+    registry
+}
+
+/// A world with the [`registry`] as a resource.
+pub(crate) fn world() -> World {
+    let mut world = World::new();
+    world.insert_resource(registry());
+    world
+}
+
+/// An app with the asset and scene plugins, whose `AssetServer` loads `assets`.
+pub(crate) fn app(assets: EmbeddedAssets) -> App {
+    app_with_settings(assets, SceneValidationSettings::default())
+}
+
+/// Like [`app`], with the settings that the scene loaders are created with.
+pub(crate) fn app_with_settings(
+    assets: EmbeddedAssets,
+    validation_settings: SceneValidationSettings,
+) -> App {
+    let mut app = App::new();
+    app.insert_resource(AssetServer::new(
+        EmbeddedAssetIo::new(assets),
+        TaskPool::new(),
+    ))
+    .insert_resource(validation_settings)
+    .add_plugin(AssetPlugin)
+    .add_plugin(ScenePlugin);
+    register_types(&mut app.world.get_resource::<TypeRegistryArc>().unwrap().write());
+    app
+}
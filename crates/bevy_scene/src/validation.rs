@@ -0,0 +1,342 @@
+use bevy_ecs::reflect::{ReflectComponent, ReflectResource};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectRef, TypeRegistry};
+use std::fmt;
+use thiserror::Error;
+
+/// Configures how scene loaders handle the issues found by validating the loaded scenes. The
+/// loaders read this resource when they are created, so it needs to be inserted before the
+/// [`ScenePlugin`](crate::ScenePlugin) is added.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SceneValidationSettings {
+    /// Fails to load scenes with issues, instead of logging a warning for every issue
+    pub strict: bool,
+}
+
+/// A component or resource that is skipped when a scene is captured or spawned, or that cannot be
+/// saved and loaded again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SceneValidationIssue {
+    /// The id of the entity with the component, or `None` for resources
+    pub entity: Option<u32>,
+    /// The type name of the component or resource
+    pub type_name: String,
+    pub kind: SceneValidationIssueKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SceneValidationIssueKind {
+    /// The type is not registered in the [`TypeRegistry`]
+    UnregisteredType,
+    /// The type is registered without [`ReflectComponent`]
+    UnregisteredComponent,
+    /// The type is registered without [`ReflectResource`]
+    UnregisteredResource,
+    /// The value contains a value of the type `type_name`, which cannot be serialized, or is not
+    /// registered with [`ReflectDeserialize`]
+    Unserializable { type_name: String },
+}
+
+impl fmt::Display for SceneValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.entity {
+            Some(entity) => write!(f, "component `{}` of entity {}", self.type_name, entity)?,
+            None => write!(f, "resource `{}`", self.type_name)?,
+        }
+        match &self.kind {
+            SceneValidationIssueKind::UnregisteredType => write!(
+                f,
+                " is not registered. consider registering the type using `app.register_type::<T>()`"
+            ),
+            SceneValidationIssueKind::UnregisteredComponent => write!(
+                f,
+                " is not registered as a component. consider adding `#[reflect(Component)]` to your type"
+            ),
+            SceneValidationIssueKind::UnregisteredResource => write!(
+                f,
+                " is not registered as a resource. consider adding `#[reflect(Resource)]` to your type"
+            ),
+            SceneValidationIssueKind::Unserializable { type_name } => write!(
+                f,
+                " contains the type `{}`, which cannot be serialized and deserialized",
+                type_name
+            ),
+        }
+    }
+}
+
+/// The issues found by validating a scene.
+#[derive(Clone, Debug, Default)]
+pub struct SceneValidation {
+    pub issues: Vec<SceneValidationIssue>,
+}
+
+impl SceneValidation {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Turns the issues into an error, for strict validation.
+    pub fn into_result(self) -> Result<(), SceneValidationError> {
+        if self.issues.is_empty() {
+            Ok(())
+        } else {
+            Err(SceneValidationError {
+                issues: self.issues,
+            })
+        }
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        entity: Option<u32>,
+        type_name: &str,
+        kind: SceneValidationIssueKind,
+    ) {
+        self.issues.push(SceneValidationIssue {
+            entity,
+            type_name: type_name.to_string(),
+            kind,
+        });
+    }
+
+    pub(crate) fn validate_component(
+        &mut self,
+        entity: u32,
+        component: &dyn Reflect,
+        type_registry: &TypeRegistry,
+    ) {
+        match type_registry.get_with_name(component.type_name()) {
+            None => self.push(
+                Some(entity),
+                component.type_name(),
+                SceneValidationIssueKind::UnregisteredType,
+            ),
+            Some(registration) if registration.data::<ReflectComponent>().is_none() => self.push(
+                Some(entity),
+                component.type_name(),
+                SceneValidationIssueKind::UnregisteredComponent,
+            ),
+            Some(_) => self.validate_serializable(Some(entity), component, type_registry),
+        }
+    }
+
+    pub(crate) fn validate_resource(
+        &mut self,
+        resource: &dyn Reflect,
+        type_registry: &TypeRegistry,
+    ) {
+        match type_registry.get_with_name(resource.type_name()) {
+            None => self.push(
+                None,
+                resource.type_name(),
+                SceneValidationIssueKind::UnregisteredType,
+            ),
+            Some(registration) if registration.data::<ReflectResource>().is_none() => self.push(
+                None,
+                resource.type_name(),
+                SceneValidationIssueKind::UnregisteredResource,
+            ),
+            Some(_) => self.validate_serializable(None, resource, type_registry),
+        }
+    }
+
+    /// Validates a value that is not skipped, but can still fail to serialize or deserialize.
+    pub(crate) fn validate_serializable(
+        &mut self,
+        entity: Option<u32>,
+        value: &dyn Reflect,
+        type_registry: &TypeRegistry,
+    ) {
+        if let Some(type_name) = find_unserializable(value, type_registry) {
+            self.push(
+                entity,
+                value.type_name(),
+                SceneValidationIssueKind::Unserializable { type_name },
+            );
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("scene is invalid: {}", display_issues(.issues))]
+pub struct SceneValidationError {
+    pub issues: Vec<SceneValidationIssue>,
+}
+
+fn display_issues(issues: &[SceneValidationIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Returns the type name of the first value in `value` that cannot be serialized and deserialized.
+fn find_unserializable(value: &dyn Reflect, type_registry: &TypeRegistry) -> Option<String> {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .find_map(|field| find_unserializable(field, type_registry)),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .find_map(|field| find_unserializable(field, type_registry)),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .find_map(|field| find_unserializable(field, type_registry)),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .find_map(|field| find_unserializable(field, type_registry)),
+        ReflectRef::List(value) => value
+            .iter()
+            .find_map(|item| find_unserializable(item, type_registry)),
+        ReflectRef::Map(value) => value.iter().find_map(|(key, value)| {
+            find_unserializable(key, type_registry)
+                .or_else(|| find_unserializable(value, type_registry))
+        }),
+        ReflectRef::Value(value) => {
+            let deserializable = type_registry
+                .get_with_name(value.type_name())
+                .and_then(|registration| registration.data::<ReflectDeserialize>())
+                .is_some();
+            if value.serializable().is_some() && deserializable {
+                None
+            } else {
+                Some(value.type_name().to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{registry, Health},
+        DynamicScene, DynamicSceneBuilder, SceneValidationIssue, SceneValidationIssueKind,
+    };
+    use bevy_ecs::world::World;
+    use bevy_reflect::Reflect;
+
+    struct Unregistered;
+
+    fn issue(entity: Option<u32>, type_name: &str) -> SceneValidationIssue {
+        SceneValidationIssue {
+            entity,
+            type_name: type_name.to_string(),
+            kind: SceneValidationIssueKind::UnregisteredType,
+        }
+    }
+
+    const SCENE: &str = r#"(
+  resources: [
+    {
+      "type": "game::Weather",
+      "value": "Rain",
+    },
+  ],
+  entities: [
+    (
+      entity: 0,
+      components: [
+        {
+          "type": "game::Secret",
+          "value": 5,
+        },
+        {
+          "type": "bevy_scene::test_utils::Health",
+          "struct": {
+            "current": {
+              "type": "u32",
+              "value": 3,
+            },
+          },
+        },
+      ],
+    ),
+    (
+      entity: 1,
+      components: [
+        {
+          "type": "bevy_scene::test_utils::Health",
+          "struct": {
+            "current": {
+              "type": "u32",
+              "value": 4,
+            },
+          },
+        },
+        {
+          "type": "game::Inventory",
+          "struct": {
+            "items": {
+              "type": "game::Items",
+              "list": [
+                {
+                  "type": "u32",
+                  "value": 1,
+                },
+              ],
+            },
+          },
+        },
+      ],
+    ),
+  ],
+)"#;
+
+    #[test]
+    fn capture_unregistered_component() {
+        let mut world = World::new();
+        let registry = registry();
+        let entity = world
+            .spawn()
+            .insert(Health { current: 1, max: 1 })
+            .insert(Unregistered)
+            .id();
+
+        let error = match DynamicSceneBuilder::new(&world, &registry)
+            .extract_entity(entity)
+            .try_build()
+        {
+            Ok(_) => panic!("the unregistered component should fail the build"),
+            Err(error) => error,
+        };
+        assert_eq!(
+            error.issues,
+            vec![issue(
+                Some(entity.id()),
+                std::any::type_name::<Unregistered>()
+            )]
+        );
+    }
+
+    #[test]
+    fn load_skips_unregistered_types() {
+        let registry = registry();
+        assert!(DynamicScene::from_ron(SCENE.as_bytes(), &registry.read()).is_err());
+
+        let (scene, validation) =
+            DynamicScene::from_ron_validated(SCENE.as_bytes(), &registry.read()).unwrap();
+        assert_eq!(
+            validation.issues,
+            vec![
+                issue(None, "game::Weather"),
+                issue(Some(0), "game::Secret"),
+                issue(Some(1), "game::Inventory"),
+            ]
+        );
+        assert!(scene.resources.is_empty());
+        assert_eq!(scene.entities.len(), 2);
+        for (scene_entity, current) in scene.entities.iter().zip([3, 4]) {
+            assert_eq!(scene_entity.components.len(), 1);
+            let mut health = Health::default();
+            health.apply(&*scene_entity.components[0]);
+            assert_eq!(health, Health { current, max: 0 });
+        }
+
+        // the skipped values are not saved again
+        let ron = scene.serialize_ron(&registry).unwrap();
+        let (_, validation) =
+            DynamicScene::from_ron_validated(ron.as_bytes(), &registry.read()).unwrap();
+        assert!(validation.is_valid());
+    }
+}
@@ -87,6 +87,9 @@ fn setup() {
         // This exposes "map" operations on your type, such as getting / inserting by key.
         // Map is automatically implemented for relevant core types like HashMap<K, V>
         ReflectRef::Map(_) => {}
+        // `Enum` is automatically implemented for enums that derive Reflect. It exposes the name
+        // and index of the current variant, and the fields of that variant.
+        ReflectRef::Enum(_) => {}
         // `Value` types do not implement any of the other traits above. They are simply a Reflect
         // implementation. Value is implemented for core types like i32, usize, f32, and
         // String.
@@ -57,6 +57,12 @@ fn load_scene_system(asset_server: Res<AssetServer>, mut scene_spawner: ResMut<S
     // entities.
     scene_spawner.spawn_dynamic(scene_handle);
 
+    // Prefabs are variants of a scene. They spawn their base scene, with some fields of its
+    // components overridden, and components added or removed. The overrides are applied again
+    // when the base scene changes.
+    let prefab_handle: Handle<Prefab> = asset_server.load("scenes/load_scene_example.prefab.ron");
+    scene_spawner.spawn_prefab(prefab_handle);
+
     // This tells the AssetServer to watch for changes to assets.
     // It enables our scenes to automatically reload in game when we modify their files
     asset_server.watch_for_changes().unwrap();